@@ -11,8 +11,8 @@ use walkdir::WalkDir;
 
 const LR1_OUTPUT: &str = "grammar.lr1";
 const NODE_KIND_FILE_NAME: &str = "node_kind.rs";
-const NODE_KIND_HEADER: &[u8] =
-    b"#[derive(Clone, Copy, Debug, PartialEq, Eq)]\npub enum NodeKind {\n";
+const NODE_KIND_HEADER: &[u8] = b"#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]\npub enum NodeKind {\n";
 
 const RULE_FILE_NAME: &str = "rules.rs";
 const RULE_FILE_HEADER: &[u8] = b"pub const RULES: [Production; N] = [\n";
@@ -201,7 +201,7 @@ fn process_lr1_grammar(output_dir: &Path) -> Result<()> {
     generate_symbol_map(&terminals, &non_terminals, output_dir)?;
     let symbol_map = terminals
         .into_iter()
-        .chain(non_terminals.into_iter())
+        .chain(non_terminals)
         .enumerate()
         .map(|(index, symbol)| (symbol, index))
         .collect::<HashMap<_, _>>();
@@ -267,16 +267,23 @@ fn write_production_rules<'a>(
 
 fn terminals_to_token_kind(s: &str) -> Option<&str> {
     let kind = match s {
-        "Identifier" | "ParsingStart" | "ParsingEnd" | "NewLine" => s,
+        "Identifier" | "ParsingStart" | "ParsingEnd" | "NewLine" | "DocComment" => s,
         "IntegerLiteral" => "Literal(LiteralKind::Integer)",
         "FloatingLiteral" => "Literal(LiteralKind::Floating)",
         "BooleanLiteral" => "Literal(LiteralKind::Boolean)",
         "StringLiteral" => "Literal(LiteralKind::String)",
+        "StringHeadLiteral" => "Literal(LiteralKind::StringHead)",
+        "StringMiddleLiteral" => "Literal(LiteralKind::StringMiddle)",
+        "StringTailLiteral" => "Literal(LiteralKind::StringTail)",
         "BinaryLiteral" => "Literal(LiteralKind::Binary)",
+        "OctalLiteral" => "Literal(LiteralKind::Octal)",
         "HexLiteral" => "Literal(LiteralKind::Hex)",
+        "LengthLiteral" => "Literal(LiteralKind::Length)",
+        "ColorLiteral" => "Literal(LiteralKind::Color)",
         "break" | "default" | "const" | "continue" | "else" | "for" | "fn" | "if" | "impl"
-        | "return" | "super" | "struct" | "self" | "use" | "trait" => "Keyword",
-        "(" | ")" | "{" | "}" | "[" | "]" | ";" | "," | "." | ":" => "Separator",
+        | "return" | "super" | "struct" | "self" | "use" | "trait" | "pub" | "as" | "match"
+        | "assert" | "type" => "Keyword",
+        "(" | ")" | "{" | "}" | "[" | "]" | ";" | "," | "." | ".." | ":" => "Separator",
         "=" | "==" | ">" | ">=" | ">>" | ">>=" | "<" | "<=" | "<<" | "<<=" | "!" | "!=" | "~"
         | "+" | "+=" | "-" | "-=" | "*" | "*=" | "/" | "/=" | "&" | "&&" | "&=" | "|" | "||"
         | "|=" | "^" | "^=" | "%" | "%=" | "->" | "=>" => "Operator",
@@ -405,7 +412,7 @@ fn write_lookup_table(
 ) -> Result<()> {
     writeln!(
         writer,
-        "const {}: [&[(usize, usize)]; {}] = [",
+        "static {}: [&[(usize, usize)]; {}] = [",
         name,
         lookup_table.len()
     )?;
@@ -427,9 +434,16 @@ fn should_ignore_lexeme(symbol: &str) -> bool {
             | "FloatingLiteral"
             | "BooleanLiteral"
             | "StringLiteral"
+            | "StringHeadLiteral"
+            | "StringMiddleLiteral"
+            | "StringTailLiteral"
             | "BinaryLiteral"
+            | "OctalLiteral"
             | "HexLiteral"
+            | "LengthLiteral"
+            | "ColorLiteral"
             | "NewLine"
+            | "DocComment"
             | "ParsingStart"
             | "ParsingEnd"
     )
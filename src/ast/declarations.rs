@@ -1,14 +1,17 @@
-use super::{debug_check, weeder, Expression, Field, Node, NodeKind};
+use super::{debug_check, weeder, Documentation, Expression, Field, Name, Node, NodeKind, Type};
 use crate::ast::check_unpack;
 use crate::ast::scoped_elements::StructBody;
+use crate::env::scope::*;
 use crate::search::BreadthFirst;
-#[cfg(debug_assertions)]
 use crate::tokenizer::{Token, TokenKind};
+use scope_macro::Scoped;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct ConstantDeclaration<'a> {
     pub name: &'a str,
     pub value: Expression<'a>,
+    pub documentation: Documentation<'a>,
+    pub is_public: bool,
 }
 
 impl<'a> From<Node<'a>> for ConstantDeclaration<'a> {
@@ -25,56 +28,179 @@ impl<'a> From<Node<'a>> for ConstantDeclaration<'a> {
             .and_then(|leaf| leaf.token())
             .map(|token| token.lexeme)
             .expect("Failed to find name for constant");
-        ConstantDeclaration { name, value }
+        let (documentation, is_public) = eat_modifiers(&mut children);
+        ConstantDeclaration {
+            name,
+            value,
+            documentation,
+            is_public,
+        }
+    }
+}
+
+/// Binds each element of a tuple to its own constant
+///
+/// # Example
+/// ```doc_script
+/// const (width, height) = (595, 842)
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct DestructuringDeclaration<'a> {
+    pub names: Vec<&'a str>,
+    pub value: Expression<'a>,
+    pub documentation: Documentation<'a>,
+    pub is_public: bool,
+}
+
+impl<'a> From<Node<'a>> for DestructuringDeclaration<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::DestructuringDeclaration);
+        let value = children
+            .pop()
+            .map(Expression::from)
+            .expect("Expect Expression");
+        let _equal_sign = children.pop();
+        debug_check! { _equal_sign, Some(Node::Leaf( Token { kind: TokenKind::Operator, lexeme: "=" })) };
+        let declared_names = children.split_off(2);
+        let is_public = children.pop().map(is_public).expect("Expect Visibility");
+        let documentation = children
+            .pop()
+            .map(Documentation::from)
+            .expect("Expect Documentation");
+        let names = BreadthFirst::find_from(
+            declared_names,
+            |node| node.is_leaf(),
+            |node| node.children().unwrap_or_default(),
+        )
+        .filter_map(|node| node.token())
+        .filter(|token| token.kind == TokenKind::Identifier)
+        .map(|token| token.lexeme)
+        .collect::<Vec<_>>();
+        weeder::destructuring::weed(&names);
+        DestructuringDeclaration {
+            names,
+            value,
+            documentation,
+            is_public,
+        }
+    }
+}
+
+/// Another name for a type, usable wherever the type is
+///
+/// # Example
+/// ```doc_script
+/// type Paragraphs = [Paragraph]
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct TypeAlias<'a> {
+    pub name: &'a str,
+    pub aliased: Type<'a>,
+    pub documentation: Documentation<'a>,
+    pub is_public: bool,
+}
+
+impl<'a> From<Node<'a>> for TypeAlias<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::TypeAliasDeclaration);
+        let aliased = children.pop().map(Type::from).expect("Expect Type");
+        let _equal_sign = children.pop();
+        debug_check! { _equal_sign, Some(Node::Leaf( Token { kind: TokenKind::Operator, lexeme: "=" })) };
+        let name = children
+            .pop()
+            .and_then(|leaf| leaf.token())
+            .map(|token| token.lexeme)
+            .expect("Failed to find name for type alias");
+        let (documentation, is_public) = eat_modifiers(&mut children);
+        TypeAlias {
+            name,
+            aliased,
+            documentation,
+            is_public,
+        }
     }
 }
 
+/// Checked once the documents are evaluated, failing the compilation with the message
+///
+/// # Example
+/// ```doc_script
+/// assert(self.title.len() <= 80, "Title should be at most 80 characters")
+/// ```
 #[derive(Debug, Eq, PartialEq)]
+pub struct Assertion<'a> {
+    pub condition: Expression<'a>,
+    pub message: Expression<'a>,
+    /// The `assert` keyword, locating the assertion in its source
+    pub keyword: &'a str,
+}
+
+impl<'a> From<Node<'a>> for Assertion<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::Assertion);
+        let _close_bracket = children.pop();
+        debug_check! { _close_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: ")" })) };
+        let message = children
+            .pop()
+            .map(Expression::from)
+            .expect("Expect assertion message");
+        let _comma = children.pop();
+        debug_check! { _comma, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "," })) };
+        let condition = children
+            .pop()
+            .map(Expression::from)
+            .expect("Expect assertion condition");
+        let _open_bracket = children.pop();
+        debug_check! { _open_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "(" })) };
+        let keyword = children
+            .pop()
+            .and_then(|leaf| leaf.token())
+            .map(|token| token.lexeme)
+            .expect("Expect assert keyword");
+        Assertion {
+            condition,
+            message,
+            keyword,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Scoped)]
 pub struct StructDeclaration<'a> {
     pub name: &'a str,
     pub fields: Vec<Field<'a>>,
     pub body: Option<StructBody<'a>>,
+    pub documentation: Documentation<'a>,
+    pub is_public: bool,
+    /// Scope of the field default values, where `self` is the instance being created
+    pub(crate) scope: Option<ScopeId>,
 }
 
 impl<'a> From<Node<'a>> for StructDeclaration<'a> {
     fn from(node: Node<'a>) -> Self {
         let mut children = check_unpack!(node, NodeKind::StructDeclaration);
-        let body = Self::eat_struct_body(&mut children);
+        let body = eat_body(&mut children);
         let fields = Self::eat_fields(&mut children);
         let name = children
             .pop()
             .and_then(|node| node.token())
             .map(|token| token.lexeme)
             .expect("Expect struct name");
-        let struct_declaration = StructDeclaration { name, fields, body };
+        let (documentation, is_public) = eat_modifiers(&mut children);
+        let struct_declaration = StructDeclaration {
+            name,
+            fields,
+            body,
+            documentation,
+            is_public,
+            scope: None,
+        };
         weeder::structure::weed(&struct_declaration);
         struct_declaration
     }
 }
 
 impl<'a> StructDeclaration<'a> {
-    fn eat_struct_body(children: &mut Vec<Node<'a>>) -> Option<StructBody<'a>> {
-        if !matches!(
-            children.last().and_then(Node::kind),
-            Some(NodeKind::StructBody)
-        ) {
-            None
-        } else {
-            let body = BreadthFirst::find(
-                children.pop().unwrap(),
-                |node| matches!(node.kind(), Some(NodeKind::ConstantDeclaration)),
-                |node| node.children().unwrap_or_default(),
-            )
-            .map(ConstantDeclaration::from)
-            .collect::<StructBody>();
-            if body.attributes.is_empty() {
-                None
-            } else {
-                Some(body)
-            }
-        }
-    }
-
     fn eat_fields(children: &mut Vec<Node<'a>>) -> Vec<Field<'a>> {
         if !matches!(
             children.last(),
@@ -95,6 +221,56 @@ impl<'a> StructDeclaration<'a> {
         debug_check! { _open_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "(" })) };
         fields
     }
+
+    pub fn has_default_values(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|field| field.default_value.is_some())
+    }
+
+    /// Fields whose default values depend on other fields through `self`,
+    /// ordered so that every field comes after the fields it depends on
+    pub fn dependent_fields(&self) -> Vec<&Field<'a>> {
+        let mut ordered = vec![];
+        for field in &self.fields {
+            self.visit_dependencies(field, &mut vec![], &mut ordered);
+        }
+        ordered
+    }
+
+    fn visit_dependencies<'s>(
+        &'s self,
+        field: &'s Field<'a>,
+        visiting: &mut Vec<&'a str>,
+        ordered: &mut Vec<&'s Field<'a>>,
+    ) {
+        let dependencies = field.self_dependencies();
+        if dependencies.is_empty() || ordered.iter().any(|visited| visited.name == field.name) {
+            return;
+        }
+        assert!(
+            !visiting.contains(&field.name),
+            "Cycle detected in default values of struct `{}` at field `{}`",
+            self.name,
+            field.name
+        );
+        visiting.push(field.name);
+        for dependency in dependencies {
+            let dependency = self
+                .fields
+                .iter()
+                .find(|field| field.name == dependency)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Default value of field `{}` uses `{}`, which is not a field of struct `{}`",
+                        field.name, dependency, self.name
+                    )
+                });
+            self.visit_dependencies(dependency, visiting, ordered);
+        }
+        visiting.pop();
+        ordered.push(field);
+    }
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -122,15 +298,33 @@ impl<'a> From<Vec<Expression<'a>>> for StructInitContent<'a> {
     }
 }
 
+/// A trait requires fields from conforming types,
+/// and provides default attributes to conforming structs
+///
+/// # Example
+/// ```doc_script
+/// trait Titled(title: String): Named {
+///     const heading = Text(self.title)
+/// }
+/// ```
 #[derive(Debug, Eq, PartialEq)]
 pub struct TraitDeclaration<'a> {
     pub name: &'a str,
     pub required: Vec<Field<'a>>,
+    pub super_traits: Vec<Name<'a>>,
+    pub body: Option<StructBody<'a>>,
+    pub documentation: Documentation<'a>,
+    pub is_public: bool,
 }
 
 impl<'a> From<Node<'a>> for TraitDeclaration<'a> {
     fn from(node: Node<'a>) -> Self {
         let mut children = check_unpack!(node, NodeKind::TraitDeclaration);
+        let body = eat_body(&mut children);
+        let super_traits = children
+            .pop()
+            .map(Self::eat_super_traits)
+            .expect("Expect SuperTraits");
         let required = children
             .pop()
             .map(Self::eat_fields)
@@ -140,11 +334,36 @@ impl<'a> From<Node<'a>> for TraitDeclaration<'a> {
             .and_then(|node| node.token())
             .map(|token| token.lexeme)
             .expect("Expect trait name");
-        Self { name, required }
+        assert!(
+            body.as_ref().is_none_or(|body| body.assertions.is_empty()),
+            "Trait `{name}` cannot declare assertions"
+        );
+        let (documentation, is_public) = eat_modifiers(&mut children);
+        Self {
+            name,
+            required,
+            super_traits,
+            body,
+            documentation,
+            is_public,
+        }
     }
 }
 
 impl<'a> TraitDeclaration<'a> {
+    fn eat_super_traits(super_traits: Node<'a>) -> Vec<Name<'a>> {
+        let children = check_unpack!(super_traits, NodeKind::SuperTraits);
+        let mut names = BreadthFirst::find_from(
+            children,
+            |node| matches!(node.kind(), Some(NodeKind::Name)),
+            |node| node.children().unwrap_or_default(),
+        )
+        .map(Name::from)
+        .collect::<Vec<_>>();
+        names.reverse();
+        names
+    }
+
     fn eat_fields(requirement: Node<'a>) -> Vec<Field<'a>> {
         let mut children = check_unpack!(requirement, NodeKind::TraitRequirement);
         if children.is_empty() {
@@ -167,3 +386,51 @@ impl<'a> TraitDeclaration<'a> {
         }
     }
 }
+
+/// Pops the attributes declared in the body of a struct or a trait
+fn eat_body<'a>(children: &mut Vec<Node<'a>>) -> Option<StructBody<'a>> {
+    if !matches!(
+        children.last().and_then(Node::kind),
+        Some(NodeKind::StructBody)
+    ) {
+        None
+    } else {
+        let (attributes, assertions): (Vec<_>, Vec<_>) = BreadthFirst::find(
+            children.pop().unwrap(),
+            |node| {
+                matches!(
+                    node.kind(),
+                    Some(NodeKind::ConstantDeclaration | NodeKind::Assertion)
+                )
+            },
+            |node| node.children().unwrap_or_default(),
+        )
+        .partition(|node| node.kind() == Some(NodeKind::ConstantDeclaration));
+        let mut body = attributes
+            .into_iter()
+            .map(ConstantDeclaration::from)
+            .collect::<StructBody>();
+        body.assertions = assertions.into_iter().map(Assertion::from).collect();
+        if body.attributes.is_empty() && body.assertions.is_empty() {
+            None
+        } else {
+            Some(body)
+        }
+    }
+}
+
+/// Pops the declaration keyword and the modifiers in front of it
+fn eat_modifiers<'a>(children: &mut Vec<Node<'a>>) -> (Documentation<'a>, bool) {
+    let _keyword = children.pop();
+    debug_check! { _keyword, Some(Node::Leaf(Token { kind: TokenKind::Keyword, .. })) };
+    let is_public = children.pop().map(is_public).expect("Expect Visibility");
+    let documentation = children
+        .pop()
+        .map(Documentation::from)
+        .expect("Expect Documentation");
+    (documentation, is_public)
+}
+
+pub(super) fn is_public(visibility: Node) -> bool {
+    !check_unpack!(visibility, NodeKind::Visibility).is_empty()
+}
@@ -0,0 +1,50 @@
+use super::{debug_check, Node, NodeKind};
+use crate::search::DepthFirst;
+use crate::tokenizer::TokenKind;
+use std::fmt::{Display, Formatter};
+
+/// Lines of `///` comments placed right before a declaration or a field
+///
+/// # Example
+/// ```doc_script
+/// /// Width of A4 paper
+/// /// in points
+/// const width = 595
+/// ```
+/// is documented by the lines `Width of A4 paper` and `in points`
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Documentation<'a>(pub Vec<&'a str>);
+
+impl<'a> From<Node<'a>> for Documentation<'a> {
+    fn from(node: Node<'a>) -> Self {
+        debug_check!(node, Node::Internal { kind: NodeKind::Documentation, .. });
+        let lines = DepthFirst::find(
+            node,
+            |node| node.is_leaf(),
+            |node| {
+                let mut children = node.children().unwrap_or_default();
+                children.reverse();
+                children
+            },
+        )
+        .filter_map(|node| node.token())
+        .filter(|token| token.kind == TokenKind::DocComment)
+        .map(|token| Self::strip_marker(token.lexeme))
+        .collect();
+        Documentation(lines)
+    }
+}
+
+impl<'a> Documentation<'a> {
+    /// Removes the leading `///` and at most one space following it
+    fn strip_marker(lexeme: &'a str) -> &'a str {
+        let line = lexeme.strip_prefix("///").expect("Doc comments start with ///");
+        line.strip_prefix(' ').unwrap_or(line)
+    }
+}
+
+impl Display for Documentation<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join("\n"))
+    }
+}
@@ -1,6 +1,6 @@
 use super::weeder;
 use super::{check_unpack, debug_check};
-use super::{Name, Parameter, Statement};
+use super::{Match, Name, Parameter, Statement};
 use super::{Node, NodeKind};
 use crate::ast::scoped_elements::Block;
 use crate::ast::StructInitContent;
@@ -12,10 +12,122 @@ use enum_as_inner::EnumAsInner;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Accessor<'a> {
+    /// Fields leading to the instance whose field is updated.
+    /// Empty when the field belongs to the receiver itself
+    pub path: Vec<&'a str>,
     pub identifier: &'a str,
     pub value: Option<Expression<'a>>,
 }
 
+impl<'a> Accessor<'a> {
+    /// Name of the method that updates several fields, nested ones included
+    ///
+    /// # Example
+    /// ```doc_script
+    /// const wide = doc.with(size.width: 600, title: "Wide")
+    /// ```
+    pub const UPDATE_METHOD: &'static str = "with";
+
+    /// Converts a parameter of the update method to an accessor
+    pub fn update(parameter: Parameter<'a>) -> Self {
+        match parameter {
+            Parameter::Labelled { label, content } => Accessor {
+                path: vec![],
+                identifier: label,
+                value: Some(content),
+            },
+            Parameter::Nested { mut path, content } => {
+                let identifier = path.pop().expect("Field path cannot be empty");
+                Accessor {
+                    path,
+                    identifier,
+                    value: Some(content),
+                }
+            }
+            Parameter::Plain(_) | Parameter::Spread(_) => panic!(
+                "Only labelled fields can be updated with `{}`",
+                Self::UPDATE_METHOD
+            ),
+        }
+    }
+}
+
+/// Part of an interpolated string
+#[derive(Debug, Eq, PartialEq)]
+pub enum StringPart<'a> {
+    /// Text around interpolations, with the quote or braces surrounding it, e.g. `} of {`
+    Text(&'a str),
+    /// Expression embedded in the string
+    Interpolation(Expression<'a>),
+}
+
+impl<'a> StringPart<'a> {
+    pub fn expression(&self) -> Option<&Expression<'a>> {
+        match self {
+            StringPart::Interpolation(expression) => Some(expression),
+            StringPart::Text(_) => None,
+        }
+    }
+
+    pub fn expression_mut(&mut self) -> Option<&mut Expression<'a>> {
+        match self {
+            StringPart::Interpolation(expression) => Some(expression),
+            StringPart::Text(_) => None,
+        }
+    }
+}
+
+/// Operators of arithmetic expressions
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ArithmeticOperator {
+    Add,
+    Subtract,
+}
+
+impl std::fmt::Display for ArithmeticOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticOperator::Add => write!(f, "+"),
+            ArithmeticOperator::Subtract => write!(f, "-"),
+        }
+    }
+}
+
+/// Operators comparing two values of the same type
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl ComparisonOperator {
+    /// Whether the operator compares the order of the values, not only their equality
+    pub fn is_ordering(self) -> bool {
+        !matches!(
+            self,
+            ComparisonOperator::Equal | ComparisonOperator::NotEqual
+        )
+    }
+}
+
+impl std::fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operator = match self {
+            ComparisonOperator::Equal => "==",
+            ComparisonOperator::NotEqual => "!=",
+            ComparisonOperator::Less => "<",
+            ComparisonOperator::LessOrEqual => "<=",
+            ComparisonOperator::Greater => ">",
+            ComparisonOperator::GreaterOrEqual => ">=",
+        };
+        write!(f, "{operator}")
+    }
+}
+
 #[cfg_attr(test, derive(EnumAsInner))]
 #[derive(Debug, Eq, PartialEq)]
 pub enum Expression<'a> {
@@ -69,6 +181,7 @@ pub enum Expression<'a> {
     /// const string = "string"
     /// const float = 3.14
     /// const boolean = true
+    /// const length = 2.5cm
     /// ```
     Literal { kind: LiteralKind, lexeme: &'a str },
     /// A way to create new instance based on an existing instance
@@ -128,6 +241,83 @@ pub enum Expression<'a> {
     /// const collection = [1, 2, 3]
     /// ```
     Collection(Vec<Expression<'a>>),
+    /// Tuple literal, with at least one comma inside the brackets
+    /// # Example
+    /// ```doc_script
+    /// const tuple = (595, "A4")
+    /// const single = (1,)
+    /// ```
+    Tuple(Vec<Expression<'a>>),
+    /// Addition or subtraction of two numbers of the same type
+    ///
+    /// Lengths in different absolute units are converted to the unit of the left operand
+    /// # Example
+    /// ```doc_script
+    /// const margin = 1in - 2cm
+    /// ```
+    Arithmetic {
+        operator: ArithmeticOperator,
+        /// The operator as written, locating the expression in its source
+        symbol: &'a str,
+        left: Box<Expression<'a>>,
+        right: Box<Expression<'a>>,
+    },
+    /// String with expressions embedded in braces.
+    /// The value of each expression is converted to text
+    /// # Example
+    /// ```doc_script
+    /// const footer = "Page {self.number} of {TOTAL}"
+    /// ```
+    InterpolatedString(Vec<StringPart<'a>>),
+    /// Element of a collection at the given position, starting from 0
+    /// # Example
+    /// ```doc_script
+    /// const first_author = authors[0]
+    /// ```
+    Index {
+        receiver: Box<Expression<'a>>,
+        index: Box<Expression<'a>>,
+        /// The opening bracket, where the access is reported when out of bounds
+        bracket: &'a str,
+    },
+    /// Elements of a collection from the start position (inclusive)
+    /// to the end position (exclusive).
+    /// A missing start or end means the start or end of the collection
+    /// # Example
+    /// ```doc_script
+    /// const coauthors = authors[1..]
+    /// ```
+    Slice {
+        receiver: Box<Expression<'a>>,
+        start: Option<Box<Expression<'a>>>,
+        end: Option<Box<Expression<'a>>>,
+        /// The opening bracket, where the slice is reported when out of bounds
+        bracket: &'a str,
+    },
+    /// Comparison of two values of the same type, with a `Bool` value.
+    /// Only numbers and lengths can be ordered
+    /// # Example
+    /// ```doc_script
+    /// const short = self.title.len() <= 80
+    /// ```
+    Comparison {
+        operator: ComparisonOperator,
+        /// The operator as written, locating the expression in its source
+        symbol: &'a str,
+        left: Box<Expression<'a>>,
+        right: Box<Expression<'a>>,
+    },
+    /// Value of the first arm whose pattern matches the subject.
+    /// The arms need to cover every possible value of the subject
+    /// # Example
+    /// ```doc_script
+    /// const size = match self.level {
+    ///     1 => 24,
+    ///     2 => 18,
+    ///     _ => 12,
+    /// }
+    /// ```
+    Match(Match<'a>),
 }
 
 impl<'a> From<Node<'a>> for Expression<'a> {
@@ -141,9 +331,18 @@ impl<'a> From<Node<'a>> for Expression<'a> {
             Some(NodeKind::FieldAccess) => Self::field_access(node),
             Some(NodeKind::VoidExpression) => Expression::Void,
             Some(NodeKind::CollectionLiteral) => Self::collection_literal(node),
-            Some(NodeKind::Expression | NodeKind::ChainableExpression) => {
-                Self::expression_recursive(node)
-            }
+            Some(NodeKind::TupleLiteral) => Self::tuple_literal(node),
+            Some(NodeKind::ArithmeticExpression) => Self::arithmetic(node),
+            Some(NodeKind::InterpolatedString) => Self::interpolated_string(node),
+            Some(NodeKind::IndexExpression) => Self::index(node),
+            Some(NodeKind::MatchExpression) => Expression::Match(Match::from(node)),
+            Some(NodeKind::ComparisonExpression) => Self::comparison(node),
+            Some(
+                NodeKind::Expression
+                | NodeKind::ChainableExpression
+                | NodeKind::Operand
+                | NodeKind::Sum,
+            ) => Self::expression_recursive(node),
             None => unreachable!("Unexpected leaf node reached: {:?}", node),
             Some(kind) => unreachable!("Unexpected kind reached: {:?}", kind),
         }
@@ -240,25 +439,51 @@ impl<'a> Expression<'a> {
 
     fn chaining_method_invocation(node: Node<'a>) -> Expression<'a> {
         let mut children = check_unpack!(node, NodeKind::ChainingMethodInvocation);
-        let _close_bracket = children.pop();
-        debug_check! { _close_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: ")" })) };
-        let value = match children.pop() {
-            Some(node @ Node::Internal { .. }) => {
-                let _open_bracket = children.pop();
-                debug_check! { _open_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "(" })) };
-                Some(Expression::from(node))
-            }
-            Some(_open_bracket @ Node::Leaf(_)) => {
-                debug_check! { _open_bracket, Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "(" }) };
-                None
-            }
-            _ => unreachable!("ChainingMethod has either value or bracket"),
+        let has_updates = matches!(
+            children.iter().rev().nth(1).and_then(Node::kind),
+            Some(NodeKind::NamedParameters)
+        );
+        let new_accessors = if has_updates {
+            let updates = Self::eat_parameters(&mut children);
+            let identifier = Self::eat_method_name(&mut children);
+            assert_eq!(
+                identifier,
+                Accessor::UPDATE_METHOD,
+                "Only `{}` accepts labelled fields",
+                Accessor::UPDATE_METHOD
+            );
+            updates.into_iter().map(Accessor::update).collect()
+        } else if children.last().and_then(Node::kind) == Some(NodeKind::TupleLiteral) {
+            // several arguments are passed together as a tuple
+            let arguments = children.pop().map(Expression::from);
+            let identifier = Self::eat_method_name(&mut children);
+            vec![Accessor {
+                path: vec![],
+                identifier,
+                value: arguments,
+            }]
+        } else {
+            let _close_bracket = children.pop();
+            debug_check! { _close_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: ")" })) };
+            let value = match children.pop() {
+                Some(node @ Node::Internal { .. }) => {
+                    let _open_bracket = children.pop();
+                    debug_check! { _open_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "(" })) };
+                    Some(Expression::from(node))
+                }
+                Some(_open_bracket @ Node::Leaf(_)) => {
+                    debug_check! { _open_bracket, Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "(" }) };
+                    None
+                }
+                _ => unreachable!("ChainingMethod has either value or bracket"),
+            };
+            let identifier = Self::eat_method_name(&mut children);
+            vec![Accessor {
+                path: vec![],
+                identifier,
+                value,
+            }]
         };
-        let identifier = children
-            .pop()
-            .and_then(|node| node.token())
-            .map(|token| token.lexeme)
-            .expect("Method name is missing");
         let _dot = children.pop();
         debug_check! { _dot, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "." })) };
         let receiver = children
@@ -271,7 +496,7 @@ impl<'a> Expression<'a> {
                 receiver,
                 accessors: mut accesses,
             } => {
-                accesses.push(Accessor { identifier, value });
+                accesses.extend(new_accessors);
                 Expression::ChainingMethodInvocation {
                     receiver,
                     accessors: accesses,
@@ -279,11 +504,19 @@ impl<'a> Expression<'a> {
             }
             _ => Expression::ChainingMethodInvocation {
                 receiver,
-                accessors: vec![Accessor { identifier, value }],
+                accessors: new_accessors,
             },
         }
     }
 
+    fn eat_method_name(nodes: &mut Vec<Node<'a>>) -> &'a str {
+        nodes
+            .pop()
+            .and_then(|node| node.token())
+            .map(|token| token.lexeme)
+            .expect("Method name is missing")
+    }
+
     fn eat_struct_body_init(nodes: &mut Vec<Node<'a>>) -> Option<StructInitContent<'a>> {
         if nodes.last().and_then(Node::kind) != Some(NodeKind::StructInitContent) {
             return None;
@@ -312,7 +545,11 @@ impl<'a> Expression<'a> {
                 |node| {
                     matches!(
                         node.kind(),
-                        Some(NodeKind::NamedParameter | NodeKind::PositionalParameter)
+                        Some(
+                            NodeKind::NamedParameter
+                                | NodeKind::PositionalParameter
+                                | NodeKind::SpreadParameter
+                        )
                     )
                 },
                 |node| node.children().unwrap_or_default(),
@@ -370,4 +607,137 @@ impl<'a> Expression<'a> {
         .collect::<Vec<Expression>>();
         Expression::Collection(elements)
     }
+
+    fn tuple_literal(node: Node<'a>) -> Expression<'a> {
+        let children = check_unpack!(node, NodeKind::TupleLiteral);
+        let elements = BreadthFirst::find_from(
+            children,
+            |node| matches!(node.kind(), Some(NodeKind::Expression)),
+            |node| node.children().unwrap_or_default(),
+        )
+        .map(Expression::from)
+        .collect::<Vec<Expression>>();
+        Expression::Tuple(elements)
+    }
+
+    fn arithmetic(node: Node<'a>) -> Expression<'a> {
+        let mut children = check_unpack!(node, NodeKind::ArithmeticExpression);
+        let right = children
+            .pop()
+            .map(Expression::from)
+            .map(Box::new)
+            .expect("Right operand missing");
+        let (operator, symbol) = match children.pop().and_then(|node| node.token()) {
+            Some(Token {
+                kind: TokenKind::Operator,
+                lexeme: symbol @ "+",
+            }) => (ArithmeticOperator::Add, symbol),
+            Some(Token {
+                kind: TokenKind::Operator,
+                lexeme: symbol @ "-",
+            }) => (ArithmeticOperator::Subtract, symbol),
+            token => unreachable!("Unexpected arithmetic operator: {:?}", token),
+        };
+        let left = children
+            .pop()
+            .map(Expression::from)
+            .map(Box::new)
+            .expect("Left operand missing");
+        Expression::Arithmetic {
+            operator,
+            symbol,
+            left,
+            right,
+        }
+    }
+
+    fn comparison(node: Node<'a>) -> Expression<'a> {
+        let mut children = check_unpack!(node, NodeKind::ComparisonExpression);
+        let right = children
+            .pop()
+            .map(Expression::from)
+            .map(Box::new)
+            .expect("Right operand missing");
+        let symbol = children
+            .pop()
+            .and_then(|node| node.children()?.pop()?.token())
+            .map(|token| token.lexeme)
+            .expect("Comparison operator missing");
+        let operator = match symbol {
+            "==" => ComparisonOperator::Equal,
+            "!=" => ComparisonOperator::NotEqual,
+            "<" => ComparisonOperator::Less,
+            "<=" => ComparisonOperator::LessOrEqual,
+            ">" => ComparisonOperator::Greater,
+            ">=" => ComparisonOperator::GreaterOrEqual,
+            lexeme => unreachable!("Unexpected comparison operator: {lexeme}"),
+        };
+        let left = children
+            .pop()
+            .map(Expression::from)
+            .map(Box::new)
+            .expect("Left operand missing");
+        Expression::Comparison {
+            operator,
+            symbol,
+            left,
+            right,
+        }
+    }
+
+    fn interpolated_string(node: Node<'a>) -> Expression<'a> {
+        debug_assert!(matches!(node.kind(), Some(NodeKind::InterpolatedString)));
+        // each level has one text and at most one expression before the nested interpolation,
+        // so the parts are found in order
+        let parts = BreadthFirst::find(
+            node,
+            |node| matches!(node, Node::Leaf(_)) || node.kind() == Some(NodeKind::Expression),
+            |node| node.children().unwrap_or_default(),
+        )
+        .map(|node| match node {
+            Node::Leaf(token) => StringPart::Text(token.lexeme),
+            expression => StringPart::Interpolation(Expression::from(expression)),
+        })
+        .collect();
+        Expression::InterpolatedString(parts)
+    }
+
+    fn index(node: Node<'a>) -> Expression<'a> {
+        let mut children = check_unpack!(node, NodeKind::IndexExpression);
+        let _close_bracket = children.pop();
+        debug_check! { _close_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "]" })) };
+        let subscript = children.pop().expect("Index or slice missing");
+        let bracket = children
+            .pop()
+            .and_then(|leaf| leaf.token())
+            .map(|token| token.lexeme)
+            .expect("Expect opening bracket");
+        let receiver = children
+            .pop()
+            .map(Expression::from)
+            .map(Box::new)
+            .expect("Receiver missing");
+        if subscript.kind() != Some(NodeKind::Slice) {
+            return Expression::Index {
+                receiver,
+                index: Box::new(Expression::from(subscript)),
+                bracket,
+            };
+        }
+        let (mut start, mut end) = (None, None);
+        let mut after_range = false;
+        for bound in check_unpack!(subscript, NodeKind::Slice) {
+            match bound {
+                Node::Leaf(_range) => after_range = true,
+                bound if after_range => end = Some(Box::new(Expression::from(bound))),
+                bound => start = Some(Box::new(Expression::from(bound))),
+            }
+        }
+        Expression::Slice {
+            receiver,
+            start,
+            end,
+            bracket,
+        }
+    }
 }
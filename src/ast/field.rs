@@ -1,16 +1,20 @@
 use super::check_unpack;
 use super::Name;
 use super::{Node, NodeKind};
-use crate::ast::{debug_check, weeder, Expression};
-use crate::search::DepthFirst;
+use crate::ast::{
+    debug_check, weeder, Documentation, Expression, Parameter, Statement, StringPart,
+};
+use crate::search::{BreadthFirst, DepthFirst};
 #[cfg(debug_assertions)]
 use crate::tokenizer::{Token, TokenKind};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Field<'a> {
     pub name: &'a str,
     pub field_type: Type<'a>,
     pub default_value: Option<Expression<'a>>,
+    pub documentation: Documentation<'a>,
 }
 
 impl<'a> From<Node<'a>> for Field<'a> {
@@ -25,10 +29,15 @@ impl<'a> From<Node<'a>> for Field<'a> {
             .and_then(|node| node.token())
             .map(|token| token.lexeme)
             .expect("Expect field name");
+        let documentation = children
+            .pop()
+            .map(Documentation::from)
+            .expect("Expect Documentation");
         Field {
             name,
             field_type,
             default_value,
+            documentation,
         }
     }
 }
@@ -49,6 +58,111 @@ impl<'a> Field<'a> {
         }
     }
 
+    /// Fields of `self` the default value depends on
+    pub fn self_dependencies(&self) -> Vec<&'a str> {
+        let mut dependencies = vec![];
+        if let Some(default_value) = &self.default_value {
+            self.collect_self_accesses(default_value, &mut dependencies);
+        }
+        dependencies
+    }
+
+    fn collect_self_accesses(&self, expression: &Expression<'a>, accesses: &mut Vec<&'a str>) {
+        match expression {
+            Expression::FieldAccess {
+                receiver,
+                field_names,
+            } if matches!(receiver.as_ref(), Expression::SelfRef(_)) => {
+                accesses.push(field_names[0])
+            }
+            Expression::SelfRef(_) => panic!(
+                "`self` in the default value of field `{}` can only be used to access fields",
+                self.name
+            ),
+            Expression::FieldAccess { receiver, .. } => {
+                self.collect_self_accesses(receiver, accesses)
+            }
+            Expression::ChainingMethodInvocation {
+                receiver,
+                accessors,
+            } => {
+                self.collect_self_accesses(receiver, accesses);
+                for value in accessors
+                    .iter()
+                    .filter_map(|accessor| accessor.value.as_ref())
+                {
+                    self.collect_self_accesses(value, accesses)
+                }
+            }
+            Expression::StructInit {
+                parameters,
+                init_content,
+                ..
+            } => {
+                for parameter in parameters {
+                    self.collect_self_accesses(Parameter::expression(parameter), accesses)
+                }
+                for expression in init_content.iter().flat_map(|content| &content.0) {
+                    self.collect_self_accesses(expression, accesses)
+                }
+            }
+            Expression::Block(block) => {
+                for statement in &block.statements {
+                    match statement {
+                        Statement::Expression(expression) => {
+                            self.collect_self_accesses(expression, accesses)
+                        }
+                        Statement::ConstantDeclaration(constant) => {
+                            self.collect_self_accesses(&constant.value, accesses)
+                        }
+                        Statement::Destructuring(destructuring) => {
+                            self.collect_self_accesses(&destructuring.value, accesses)
+                        }
+                    }
+                }
+            }
+            Expression::Collection(elements) | Expression::Tuple(elements) => {
+                for element in elements {
+                    self.collect_self_accesses(element, accesses)
+                }
+            }
+            Expression::Arithmetic { left, right, .. }
+            | Expression::Comparison { left, right, .. } => {
+                self.collect_self_accesses(left, accesses);
+                self.collect_self_accesses(right, accesses);
+            }
+            Expression::InterpolatedString(parts) => {
+                for expression in parts.iter().filter_map(StringPart::expression) {
+                    self.collect_self_accesses(expression, accesses)
+                }
+            }
+            Expression::Index {
+                receiver, index, ..
+            } => {
+                self.collect_self_accesses(receiver, accesses);
+                self.collect_self_accesses(index, accesses);
+            }
+            Expression::Slice {
+                receiver,
+                start,
+                end,
+                ..
+            } => {
+                self.collect_self_accesses(receiver, accesses);
+                for bound in start.iter().chain(end) {
+                    self.collect_self_accesses(bound, accesses)
+                }
+            }
+            Expression::Match(r#match) => {
+                self.collect_self_accesses(&r#match.subject, accesses);
+                for arm in &r#match.arms {
+                    self.collect_self_accesses(&arm.value, accesses)
+                }
+            }
+            Expression::ConstUse(_) | Expression::Literal { .. } | Expression::Void => (),
+        }
+    }
+
     pub fn find_all_fields(fields_node: Node<'a>) -> Vec<Field<'a>> {
         let fields = DepthFirst::find(
             fields_node,
@@ -72,14 +186,19 @@ impl<'a> Field<'a> {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-pub struct Type<'a> {
-    pub name: Name<'a>,
-    pub is_collection: bool,
+pub enum Type<'a> {
+    /// A declared or primitive type, optionally a collection of it. E.g. `Int`, `[Paragraph]`
+    Named { name: Name<'a>, is_collection: bool },
+    /// A fixed size group of types. E.g. `(Int, String)`
+    Tuple(Vec<Type<'a>>),
 }
 
 impl<'a> From<Node<'a>> for Type<'a> {
     fn from(node: Node<'a>) -> Self {
         let mut children = check_unpack!(node, NodeKind::Type);
+        if let Some(NodeKind::TupleType) = children.last().and_then(Node::kind) {
+            return children.pop().map(Type::tuple).expect("Expect TupleType");
+        }
         let is_collection = children.len() > 1;
         if is_collection {
             let _close_bracket = children.pop();
@@ -91,9 +210,62 @@ impl<'a> From<Node<'a>> for Type<'a> {
             let _open_bracket = children.pop();
             debug_check! { _open_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "[" })) };
         }
-        Type {
+        Type::Named {
             name,
             is_collection,
         }
     }
 }
+
+impl<'a> Type<'a> {
+    fn tuple(node: Node<'a>) -> Self {
+        let children = check_unpack!(node, NodeKind::TupleType);
+        let element_types = BreadthFirst::find_from(
+            children,
+            |node| matches!(node.kind(), Some(NodeKind::Type)),
+            |node| node.children().unwrap_or_default(),
+        )
+        .map(Type::from)
+        .collect();
+        Type::Tuple(element_types)
+    }
+
+    /// All the type names used in this type, including the ones in tuple elements
+    pub fn names(&self) -> Vec<&Name<'a>> {
+        match self {
+            Type::Named { name, .. } => vec![name],
+            Type::Tuple(element_types) => element_types.iter().flat_map(Type::names).collect(),
+        }
+    }
+
+    pub fn names_mut(&mut self) -> Vec<&mut Name<'a>> {
+        match self {
+            Type::Named { name, .. } => vec![name],
+            Type::Tuple(element_types) => {
+                element_types.iter_mut().flat_map(Type::names_mut).collect()
+            }
+        }
+    }
+}
+
+impl<'a> Display for Type<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Named {
+                name,
+                is_collection: false,
+            } => write!(f, "{name}"),
+            Type::Named {
+                name,
+                is_collection: true,
+            } => write!(f, "[{name}]"),
+            Type::Tuple(element_types) => {
+                let element_types = element_types
+                    .iter()
+                    .map(Type::to_string)
+                    .collect::<Vec<_>>();
+                write!(f, "({})", element_types.join(", "))
+            }
+        }
+    }
+}
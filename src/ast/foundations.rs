@@ -1,7 +1,10 @@
 use super::check_unpack;
-use super::Import;
+use super::ImportDeclaration;
 use super::{Node, NodeKind};
-use crate::ast::{debug_check, ConstantDeclaration, StructDeclaration, TraitDeclaration};
+use crate::ast::{
+    debug_check, Assertion, ConstantDeclaration, DestructuringDeclaration, StructDeclaration,
+    TraitDeclaration, TypeAlias,
+};
 use crate::search::BreadthFirst;
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
@@ -27,38 +30,45 @@ impl<'a> From<Node<'a>> for CompilationUnit<'a> {
 
 #[cfg_attr(test, derive(Debug, Eq, PartialEq, EnumAsInner))]
 pub enum Declaration<'a> {
-    Import(Import<'a>),
+    Import(ImportDeclaration<'a>),
     Constant(ConstantDeclaration<'a>),
+    Destructuring(DestructuringDeclaration<'a>),
     Struct(StructDeclaration<'a>),
     Trait(TraitDeclaration<'a>),
+    Assertion(Assertion<'a>),
+    TypeAlias(TypeAlias<'a>),
 }
 
 impl<'a> From<Node<'a>> for Declaration<'a> {
     fn from(node: Node<'a>) -> Self {
         match node {
-            Node::Internal {
+            node @ Node::Internal {
                 kind: NodeKind::ImportDeclarationStatement,
+                ..
+            } => Declaration::Import(ImportDeclaration::from(node)),
+            Node::Internal {
+                kind: NodeKind::ConstantDeclarationStatement,
                 mut children,
             } => {
                 let _end_of_line = children.pop();
                 debug_check! { _end_of_line, Some(Node::Internal { kind: NodeKind::EOL, .. }) }
                 children
                     .pop()
-                    .map(Import::from)
-                    .map(Declaration::Import)
-                    .expect("ImportDeclaration should have one child")
+                    .map(ConstantDeclaration::from)
+                    .map(Declaration::Constant)
+                    .expect("ConstantDeclaration should have one child")
             }
             Node::Internal {
-                kind: NodeKind::ConstantDeclarationStatement,
+                kind: NodeKind::DestructuringDeclarationStatement,
                 mut children,
             } => {
                 let _end_of_line = children.pop();
                 debug_check! { _end_of_line, Some(Node::Internal { kind: NodeKind::EOL, .. }) }
                 children
                     .pop()
-                    .map(ConstantDeclaration::from)
-                    .map(Declaration::Constant)
-                    .expect("ConstantDeclaration should have one child")
+                    .map(DestructuringDeclaration::from)
+                    .map(Declaration::Destructuring)
+                    .expect("DestructuringDeclaration should have one child")
             }
             Node::Internal {
                 kind: NodeKind::StructDeclarationStatement,
@@ -84,6 +94,30 @@ impl<'a> From<Node<'a>> for Declaration<'a> {
                     .map(Declaration::Trait)
                     .expect("TraitDeclarationStatement should have one child")
             }
+            Node::Internal {
+                kind: NodeKind::AssertionStatement,
+                mut children,
+            } => {
+                let _end_of_line = children.pop();
+                debug_check! { _end_of_line, Some(Node::Internal { kind: NodeKind::EOL, .. }) }
+                children
+                    .pop()
+                    .map(Assertion::from)
+                    .map(Declaration::Assertion)
+                    .expect("AssertionStatement should have one child")
+            }
+            Node::Internal {
+                kind: NodeKind::TypeAliasDeclarationStatement,
+                mut children,
+            } => {
+                let _end_of_line = children.pop();
+                debug_check! { _end_of_line, Some(Node::Internal { kind: NodeKind::EOL, .. }) }
+                children
+                    .pop()
+                    .map(TypeAlias::from)
+                    .map(Declaration::TypeAlias)
+                    .expect("TypeAliasDeclarationStatement should have one child")
+            }
             Node::Internal {
                 kind: NodeKind::DeclarationStatement,
                 mut children,
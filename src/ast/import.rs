@@ -1,56 +1,140 @@
-use super::debug_check;
-use super::Name;
+use super::declarations::is_public;
+use super::{check_unpack, debug_check};
+use super::{Documentation, Name};
 use super::{Node, NodeKind};
-use crate::search::BreadthFirst;
+use crate::search::{BreadthFirst, DepthFirst};
 #[cfg(debug_assertions)]
-use crate::tokenizer::{Token, TokenKind};
+use crate::tokenizer::Token;
+use crate::tokenizer::TokenKind;
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
 
+/// An import statement, `pub use` also makes the imported names available to other modules
+#[cfg_attr(test, derive(Debug, Eq, PartialEq))]
+pub struct ImportDeclaration<'a> {
+    pub import: Import<'a>,
+    pub is_public: bool,
+}
+
+impl<'a> From<Node<'a>> for ImportDeclaration<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::ImportDeclarationStatement);
+        let _end_of_line = children.pop();
+        debug_check! { _end_of_line, Some(Node::Internal { kind: NodeKind::EOL, .. }) }
+        let import = children
+            .pop()
+            .map(Import::from)
+            .expect("Expect ImportDeclaration");
+        let is_public = children.pop().map(is_public).expect("Expect Visibility");
+        let documentation = children
+            .pop()
+            .map(Documentation::from)
+            .expect("Expect Documentation");
+        assert!(
+            documentation.0.is_empty(),
+            "Imports cannot be documented, found `{documentation}` before `use`"
+        );
+        ImportDeclaration { import, is_public }
+    }
+}
+
 #[cfg_attr(test, derive(Debug, Eq, PartialEq, EnumAsInner))]
 pub enum Import<'a> {
-    Single(Vec<&'a str>),
+    Single(ImportedName<'a>),
     Multiple {
         prefix: Vec<&'a str>,
-        suffices: Vec<Vec<&'a str>>,
+        suffices: Vec<ImportedName<'a>>,
     },
     Wildcard(Vec<&'a str>),
 }
 
+/// Path of an imported element, which can be renamed, e.g. `std.rect.Size as PageSize`
+#[cfg_attr(test, derive(Debug, Eq, PartialEq))]
+pub struct ImportedName<'a> {
+    pub path: Vec<&'a str>,
+    pub alias: Option<&'a str>,
+}
+
+impl<'a> From<Node<'a>> for ImportedName<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::ImportedName);
+        let alias = if children.len() > 1 {
+            let alias = children
+                .pop()
+                .and_then(|node| node.token())
+                .map(|token| token.lexeme);
+            let _as = children.pop();
+            debug_check! { _as, Some(Node::Leaf(Token { kind: TokenKind::Keyword, lexeme: "as" })) };
+            alias
+        } else {
+            None
+        };
+        let path = children
+            .pop()
+            .map(Name::find_raw_name_lexeme)
+            .expect("Expect Name");
+        ImportedName { path, alias }
+    }
+}
+
+impl<'a> ImportedName<'a> {
+    /// The name used in the importing module
+    pub fn local_name(&self) -> &'a str {
+        self.alias
+            .or_else(|| self.path.last().copied())
+            .expect("Cannot import empty name")
+    }
+}
+
+#[cfg(test)]
+impl<'a> From<Vec<&'a str>> for ImportedName<'a> {
+    fn from(path: Vec<&'a str>) -> Self {
+        ImportedName { path, alias: None }
+    }
+}
+
 impl<'a> From<Node<'a>> for Import<'a> {
     fn from(node: Node<'a>) -> Self {
         match node {
             Node::Internal {
                 kind: NodeKind::SingleImportDeclarationStatement,
                 mut children,
-            } => Import::Single(Name::find_raw_name_lexeme(
-                children.pop().expect("Import should have one child"),
-            )),
+            } => {
+                let mut imported = children
+                    .pop()
+                    .map(ImportedName::from)
+                    .expect("Import should have one child");
+                imported.path.splice(0..0, Self::module_path(children));
+                Import::Single(imported)
+            }
             Node::Internal {
                 kind: NodeKind::WildcardImportDeclarationStatement,
                 mut children,
-            } => Import::Wildcard(Name::find_raw_name_lexeme(children.swap_remove(1))),
+            } => {
+                let _star = children.pop();
+                debug_check! { _star, Some(Node::Leaf(Token { kind: TokenKind::Operator, lexeme: "*" })) };
+                let _dot = children.pop();
+                debug_check! { _dot, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "." })) };
+                Import::Wildcard(Self::module_path(children))
+            }
             Node::Internal {
                 kind: NodeKind::MultipleImportDeclarationStatement,
                 mut children,
             } => {
                 let _close_brackets = children.pop();
                 debug_check! { _close_brackets, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "}" })) };
-                let suffices = children.pop().expect("Expect CommaSeparatedNames");
+                let suffices = children.pop().expect("Expect CommaSeparatedImportedNames");
                 let _open_brackets = children.pop();
                 debug_check! { _open_brackets, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "{" })) };
                 let _dot = children.pop();
                 debug_check! { _dot, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "." })) };
-                let prefix = children
-                    .pop()
-                    .map(Name::find_raw_name_lexeme)
-                    .expect("Expect Name");
+                let prefix = Self::module_path(children);
                 let suffices = BreadthFirst::find(
                     suffices,
-                    |node| matches!(node.kind(), Some(NodeKind::Name)),
+                    |node| matches!(node.kind(), Some(NodeKind::ImportedName)),
                     |node| node.children().unwrap_or_default(),
                 )
-                .map(Name::find_raw_name_lexeme)
+                .map(ImportedName::from)
                 .collect();
                 Import::Multiple { prefix, suffices }
             }
@@ -65,3 +149,33 @@ impl<'a> From<Node<'a>> for Import<'a> {
         }
     }
 }
+
+impl<'a> Import<'a> {
+    /// Collects the module path following `use`, relative paths start with `self` or `super`
+    fn module_path(children: Vec<Node<'a>>) -> Vec<&'a str> {
+        children
+            .into_iter()
+            .flat_map(|node| match node.kind() {
+                Some(NodeKind::Name) => Name::find_raw_name_lexeme(node),
+                Some(NodeKind::RelativeModule) => Self::relative_module(node),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    fn relative_module(node: Node<'a>) -> Vec<&'a str> {
+        DepthFirst::find(
+            node,
+            |node| node.is_leaf(),
+            |node| {
+                let mut children = node.children().unwrap_or_default();
+                children.reverse();
+                children
+            },
+        )
+        .filter_map(|node| node.token())
+        .filter(|token| token.kind == TokenKind::Keyword)
+        .map(|token| token.lexeme)
+        .collect()
+    }
+}
@@ -1,10 +1,12 @@
 mod declarations;
+mod documentation;
 mod expressions;
 mod field;
 mod foundations;
 mod import;
 mod name;
 mod parameter;
+mod pattern;
 mod scoped_elements;
 mod statements;
 #[cfg(test)]
@@ -14,12 +16,14 @@ mod weeder;
 use crate::parser::{Node, NodeKind};
 
 pub use declarations::*;
+pub use documentation::*;
 pub use expressions::*;
 pub use field::*;
 pub use foundations::*;
 pub use import::*;
 pub use name::*;
 pub use parameter::*;
+pub use pattern::*;
 pub use scoped_elements::*;
 pub use statements::*;
 
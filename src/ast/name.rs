@@ -2,7 +2,7 @@ use super::{Node, NodeKind};
 use crate::env::scope::*;
 use crate::search::BreadthFirst;
 #[cfg(debug_assertions)]
-use crate::tokenizer::{Token, TokenKind};
+use crate::tokenizer::{LiteralKind, Token, TokenKind};
 use scope_macro::Scoped;
 use std::fmt::{Display, Formatter};
 
@@ -59,7 +59,7 @@ impl<'a> Name<'a> {
                 )
                 .filter_map(|node| match node.token()? {
                     Token {
-                        kind: TokenKind::Identifier,
+                        kind: TokenKind::Identifier | TokenKind::Literal(LiteralKind::Integer),
                         lexeme,
                     } => Some(lexeme),
                     _ => None,
@@ -1,4 +1,4 @@
-use super::{check_unpack, debug_check, Expression, Node, NodeKind};
+use super::{check_unpack, debug_check, Expression, Name, Node, NodeKind};
 #[cfg(debug_assertions)]
 use crate::tokenizer::{Token, TokenKind};
 
@@ -9,13 +9,32 @@ pub enum Parameter<'a> {
         label: &'a str,
         content: Expression<'a>,
     },
+    /// The base instance of a struct initialization,
+    /// which provides values for the fields not labelled after it
+    ///
+    /// # Example
+    /// ```doc_script
+    /// const draft = Essay(..essay, title: "Draft")
+    /// ```
+    Spread(Expression<'a>),
+    /// A labelled parameter whose label reaches into nested fields.
+    /// Only used to update instances with `with`
+    ///
+    /// # Example
+    /// ```doc_script
+    /// const wide = doc.with(size.width: 600)
+    /// ```
+    Nested {
+        path: Vec<&'a str>,
+        content: Expression<'a>,
+    },
 }
 
 impl<'a> From<Node<'a>> for Parameter<'a> {
     fn from(node: Node<'a>) -> Self {
         let mut children = check_unpack!(
             node,
-            NodeKind::NamedParameter | NodeKind::PositionalParameter
+            NodeKind::NamedParameter | NodeKind::PositionalParameter | NodeKind::SpreadParameter
         );
         let expression = children
             .pop()
@@ -23,17 +42,22 @@ impl<'a> From<Node<'a>> for Parameter<'a> {
             .expect("Expect Expression");
         if children.is_empty() {
             Parameter::Plain(expression)
+        } else if children.len() == 1 {
+            let _spread = children.pop();
+            debug_check! { _spread, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: ".." })) };
+            Parameter::Spread(expression)
         } else {
             let _colon = children.pop();
             debug_check! { _colon, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: ":" })) };
-            let label = children
-                .pop()
-                .and_then(|node| node.token())
-                .expect("Expect Identifier")
-                .lexeme;
-            Parameter::Labelled {
-                label,
-                content: expression,
+            match children.pop().expect("Expect label") {
+                Node::Leaf(token) => Parameter::Labelled {
+                    label: token.lexeme,
+                    content: expression,
+                },
+                node => Parameter::Nested {
+                    path: Name::find_raw_name_lexeme(node),
+                    content: expression,
+                },
             }
         }
     }
@@ -42,20 +66,26 @@ impl<'a> From<Node<'a>> for Parameter<'a> {
 impl<'a> Parameter<'a> {
     pub fn is_labelled(&self) -> bool {
         match self {
-            Parameter::Plain(_) => false,
-            Parameter::Labelled { .. } => true,
+            Parameter::Plain(_) | Parameter::Spread(_) => false,
+            Parameter::Labelled { .. } | Parameter::Nested { .. } => true,
         }
     }
 
     pub fn expression(&self) -> &Expression<'a> {
         match self {
-            Parameter::Plain(content) | Parameter::Labelled { label: _, content } => content,
+            Parameter::Plain(content)
+            | Parameter::Labelled { label: _, content }
+            | Parameter::Spread(content)
+            | Parameter::Nested { path: _, content } => content,
         }
     }
 
     pub fn expression_owned(self) -> Expression<'a> {
         match self {
-            Parameter::Plain(content) | Parameter::Labelled { label: _, content } => content,
+            Parameter::Plain(content)
+            | Parameter::Labelled { label: _, content }
+            | Parameter::Spread(content)
+            | Parameter::Nested { path: _, content } => content,
         }
     }
 }
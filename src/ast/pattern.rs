@@ -0,0 +1,215 @@
+use super::{check_unpack, debug_check, Expression, Moniker, Name, Node, NodeKind};
+use crate::env::scope::*;
+use crate::search::DepthFirst;
+use crate::tokenizer::{LiteralKind, Token, TokenKind};
+use scope_macro::Scoped;
+
+/// Value of the first arm whose pattern matches the subject
+///
+/// # Example
+/// ```doc_script
+/// const label = match self.child {
+///     figure: Figure => figure.caption,
+///     Text => "text",
+///     _ => "other",
+/// }
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct Match<'a> {
+    pub subject: Box<Expression<'a>>,
+    pub arms: Vec<MatchArm<'a>>,
+}
+
+/// An arm binding the matched instance has its own scope, where the binding is declared
+#[derive(Scoped, Debug, Eq, PartialEq)]
+pub struct MatchArm<'a> {
+    pub pattern: Pattern<'a>,
+    pub value: Expression<'a>,
+    scope: Option<ScopeId>,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Pattern<'a> {
+    /// Matches a primitive value equal to the literal
+    Literal { kind: LiteralKind, lexeme: &'a str },
+    /// Either a constant, matching a primitive value equal to it,
+    /// or a struct, matching any instance of it
+    Name(Name<'a>),
+    /// Matches any instance of the struct, and binds it to the identifier
+    Binding {
+        identifier: &'a str,
+        structure: Name<'a>,
+    },
+    /// `_`, matches any value
+    Wildcard,
+}
+
+impl<'a> MatchArm<'a> {
+    pub fn binding(&self) -> Option<&'a str> {
+        match self.pattern {
+            Pattern::Binding { identifier, .. } => Some(identifier),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Pattern<'a> {
+    pub const WILDCARD: &'static str = "_";
+
+    pub fn name(&self) -> Option<&Name<'a>> {
+        match self {
+            Pattern::Name(name)
+            | Pattern::Binding {
+                structure: name, ..
+            } => Some(name),
+            Pattern::Literal { .. } | Pattern::Wildcard => None,
+        }
+    }
+
+    pub fn name_mut(&mut self) -> Option<&mut Name<'a>> {
+        match self {
+            Pattern::Name(name)
+            | Pattern::Binding {
+                structure: name, ..
+            } => Some(name),
+            Pattern::Literal { .. } | Pattern::Wildcard => None,
+        }
+    }
+}
+
+impl<'a> From<Node<'a>> for Match<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::MatchExpression);
+        let _close_bracket = children.pop();
+        debug_check! { _close_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "}" })) };
+        let arms = children.pop().expect("Match arms missing");
+        let arms = DepthFirst::find(
+            arms,
+            |node| matches!(node.kind(), Some(NodeKind::MatchArm)),
+            |node| {
+                // keep the order correct
+                let mut children = node.children().unwrap_or_default();
+                children.reverse();
+                children
+            },
+        )
+        .map(MatchArm::from)
+        .collect();
+        let _open_bracket = children.pop();
+        debug_check! { _open_bracket, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "{" })) };
+        let subject = children
+            .pop()
+            .map(Self::subject)
+            .map(Box::new)
+            .expect("Match subject missing");
+        Match { subject, arms }
+    }
+}
+
+impl<'a> Match<'a> {
+    fn subject(node: Node<'a>) -> Expression<'a> {
+        let mut children = check_unpack!(node, NodeKind::MatchSubject);
+        match children.pop().expect("Subject expected") {
+            self_access @ Node::Internal {
+                kind: NodeKind::SelfAccess,
+                ..
+            } => Self::self_access(self_access),
+            constant_use @ Node::Internal { .. } => Expression::from(constant_use),
+            Node::Leaf(_close_bracket) => {
+                Expression::from(children.pop().expect("Bracketed expression expected"))
+            }
+        }
+    }
+
+    fn self_access(node: Node<'a>) -> Expression<'a> {
+        let mut children = check_unpack!(node, NodeKind::SelfAccess);
+        let last = children.pop().expect("Expect self or field name");
+        if children.is_empty() {
+            debug_check! { last, Node::Leaf(Token { kind: TokenKind::Keyword, lexeme: "self" }) };
+            return Expression::SelfRef(None);
+        }
+        let field_name = last.token().expect("Field name").lexeme;
+        let _dot = children.pop();
+        debug_check! { _dot, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: "." })) };
+        let receiver = children
+            .pop()
+            .map(Self::self_access)
+            .expect("Receiver missing");
+        match receiver {
+            Expression::FieldAccess {
+                receiver,
+                mut field_names,
+            } => {
+                field_names.push(field_name);
+                Expression::FieldAccess {
+                    receiver,
+                    field_names,
+                }
+            }
+            receiver => Expression::FieldAccess {
+                receiver: Box::new(receiver),
+                field_names: vec![field_name],
+            },
+        }
+    }
+}
+
+impl<'a> From<Node<'a>> for MatchArm<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::MatchArm);
+        let value = children
+            .pop()
+            .map(Expression::from)
+            .expect("Arm value missing");
+        let _arrow = children.pop();
+        debug_check! { _arrow, Some(Node::Leaf(Token { kind: TokenKind::Operator, lexeme: "=>" })) };
+        let pattern = children.pop().map(Pattern::from).expect("Pattern missing");
+        MatchArm {
+            pattern,
+            value,
+            scope: None,
+        }
+    }
+}
+
+impl<'a> From<Node<'a>> for Pattern<'a> {
+    fn from(node: Node<'a>) -> Self {
+        let mut children = check_unpack!(node, NodeKind::Pattern);
+        let last = children.pop().expect("Pattern should have children");
+        if last.kind() == Some(NodeKind::Literal) {
+            return match Expression::from(last) {
+                Expression::Literal { kind, lexeme } => Pattern::Literal { kind, lexeme },
+                _ => unreachable!("Literal node is not a literal"),
+            };
+        }
+        let name = Name::from(last);
+        if children.is_empty() {
+            return match name.moniker {
+                Moniker::Simple(Self::WILDCARD) => Pattern::Wildcard,
+                _ => Pattern::Name(name),
+            };
+        }
+        let _colon = children.pop();
+        debug_check! { _colon, Some(Node::Leaf(Token { kind: TokenKind::Separator, lexeme: ":" })) };
+        let identifier = children
+            .pop()
+            .and_then(|node| node.token())
+            .map(|token| token.lexeme)
+            .expect("Binding name missing");
+        Pattern::Binding {
+            identifier,
+            structure: name,
+        }
+    }
+}
+
+#[cfg(test)]
+impl<'a> MatchArm<'a> {
+    pub fn new(pattern: Pattern<'a>, value: Expression<'a>) -> Self {
+        Self {
+            pattern,
+            value,
+            scope: None,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::ast::weeder::attributes;
-use crate::ast::{ConstantDeclaration, Expression, Statement};
+use crate::ast::{Assertion, ConstantDeclaration, Expression, Statement};
 use crate::env::scope::*;
 use scope_macro::Scoped;
 use std::collections::VecDeque;
@@ -17,7 +17,7 @@ impl<'a> FromIterator<Statement<'a>> for Block<'a> {
         while let Some(statement) = statement_stack.pop() {
             match statement {
                 expr_stmt @ Statement::Expression(_) => statements.push_front(expr_stmt),
-                const_stmt @ Statement::ConstantDeclaration(_) => {
+                const_stmt @ (Statement::ConstantDeclaration(_) | Statement::Destructuring(_)) => {
                     if !statements.is_empty() {
                         let block = Block {
                             statements: Vec::from(statements),
@@ -50,6 +50,8 @@ impl<'a> From<Vec<Statement<'a>>> for Block<'a> {
 #[derive(Default, Scoped, Debug, Eq, PartialEq)]
 pub struct StructBody<'a> {
     pub attributes: Vec<ConstantDeclaration<'a>>,
+    /// Checked on every instance, where `self` is the instance
+    pub assertions: Vec<Assertion<'a>>,
     scope: Option<ScopeId>,
 }
 
@@ -58,6 +60,7 @@ impl<'a> FromIterator<ConstantDeclaration<'a>> for StructBody<'a> {
         let declarations = iter.into_iter().inspect(attributes::weed).collect();
         Self {
             attributes: declarations,
+            assertions: vec![],
             scope: None,
         }
     }
@@ -68,6 +71,7 @@ impl<'a> From<Vec<ConstantDeclaration<'a>>> for StructBody<'a> {
     fn from(declarations: Vec<ConstantDeclaration<'a>>) -> Self {
         Self {
             attributes: declarations,
+            assertions: vec![],
             scope: None,
         }
     }
@@ -1,7 +1,8 @@
 use super::debug_check;
+use super::weeder::visibility;
 use super::Expression;
 use super::{Node, NodeKind};
-use crate::ast::declarations::ConstantDeclaration;
+use crate::ast::declarations::{ConstantDeclaration, DestructuringDeclaration};
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
 
@@ -10,6 +11,7 @@ use enum_as_inner::EnumAsInner;
 pub enum Statement<'a> {
     Expression(Expression<'a>),
     ConstantDeclaration(ConstantDeclaration<'a>),
+    Destructuring(DestructuringDeclaration<'a>),
 }
 
 impl<'a> From<Node<'a>> for Statement<'a> {
@@ -23,6 +25,10 @@ impl<'a> From<Node<'a>> for Statement<'a> {
                 kind: NodeKind::ConstantDeclarationStatement,
                 children,
             } => Self::constant_declaration(children),
+            Node::Internal {
+                kind: NodeKind::DestructuringDeclarationStatement,
+                children,
+            } => Self::destructuring(children),
             Node::Internal {
                 kind: NodeKind::Statement,
                 mut children,
@@ -52,7 +58,24 @@ impl<'a> Statement<'a> {
         children
             .pop()
             .map(ConstantDeclaration::from)
+            .inspect(|constant| visibility::weed(constant.name, constant.is_public))
             .map(Statement::ConstantDeclaration)
             .expect("Expect ConstantDeclaration")
     }
+
+    fn destructuring(mut children: Vec<Node<'a>>) -> Statement<'a> {
+        let _end_of_line = children.pop();
+        debug_check! { _end_of_line, Some(Node::Internal { kind: NodeKind::EOL, .. }) }
+        children
+            .pop()
+            .map(DestructuringDeclaration::from)
+            .inspect(|destructuring| {
+                destructuring
+                    .names
+                    .iter()
+                    .for_each(|name| visibility::weed(name, destructuring.is_public))
+            })
+            .map(Statement::Destructuring)
+            .expect("Expect DestructuringDeclaration")
+    }
 }
@@ -51,7 +51,7 @@ fn test_block_const_declaration_desugar() {
     assert_eq!(declaration_of_b, Some("b"))
 }
 
-fn get_block(program: &str) -> Block {
+fn get_block(program: &str) -> Block<'_> {
     let parse_tree = parse(tokenize(program));
     DepthFirst::find(
         parse_tree.root,
@@ -3,7 +3,7 @@ use super::super::CompilationUnit;
 use super::*;
 use crate::ast::parameter::Parameter;
 use crate::ast::Expression::{ChainingMethodInvocation, StructInit};
-use crate::ast::{Accessor, ConstantDeclaration, Declaration, Expression, Name};
+use crate::ast::{Accessor, ConstantDeclaration, Declaration, Documentation, Expression, Name};
 use crate::tokenizer::LiteralKind::Integer;
 
 #[test]
@@ -68,6 +68,7 @@ const main = View {
                                     ),
                                 }),
                                 accessors: vec![Accessor {
+                                    path: vec![],
                                     identifier: "width",
                                     value: Some(Expression::Literal {
                                         kind: Integer,
@@ -78,7 +79,9 @@ const main = View {
                         ]
                         .into(),
                     )
-                }
+                },
+                documentation: Documentation::default(),
+                is_public: false,
             })]
         }
     )
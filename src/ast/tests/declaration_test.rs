@@ -1,6 +1,6 @@
 use crate::ast::{
-    ConstantDeclaration, Declaration, Expression, Field, Name, StructDeclaration, TraitDeclaration,
-    Type,
+    Assertion, ComparisonOperator, ConstantDeclaration, Declaration, DestructuringDeclaration,
+    Documentation, Expression, Field, Name, StructDeclaration, TraitDeclaration, Type, TypeAlias,
 };
 use crate::parser::{parse, NodeKind};
 use crate::search::DepthFirst;
@@ -22,15 +22,16 @@ struct Square(
         fields: vec![
             Field {
                 name: "width",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("Int"),
                     is_collection: false,
                 },
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "content",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("String"),
                     is_collection: false,
                 },
@@ -38,15 +39,21 @@ struct Square(
                     kind: LiteralKind::String,
                     lexeme: r#""""#,
                 }),
+                documentation: Documentation::default(),
             },
         ],
         body: Some(
             vec![ConstantDeclaration {
                 name: "height",
                 value: Expression::ConstUse(Name::simple("width")),
+                documentation: Documentation::default(),
+                is_public: false,
             }]
             .into(),
         ),
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
@@ -65,15 +72,16 @@ struct Square(
         fields: vec![
             Field {
                 name: "width",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("Int"),
                     is_collection: false,
                 },
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "content",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("String"),
                     is_collection: false,
                 },
@@ -81,9 +89,13 @@ struct Square(
                     kind: LiteralKind::String,
                     lexeme: r#""""#,
                 }),
+                documentation: Documentation::default(),
             },
         ],
         body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
@@ -104,23 +116,25 @@ struct Square(
         fields: vec![
             Field {
                 name: "width",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("Int"),
                     is_collection: false,
                 },
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "height",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("Int"),
                     is_collection: false,
                 },
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "content",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("String"),
                     is_collection: false,
                 },
@@ -128,10 +142,11 @@ struct Square(
                     kind: LiteralKind::String,
                     lexeme: r#""""#,
                 }),
+                documentation: Documentation::default(),
             },
             Field {
                 name: "id",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("Int"),
                     is_collection: false,
                 },
@@ -139,9 +154,13 @@ struct Square(
                     kind: LiteralKind::Integer,
                     lexeme: r#"0"#,
                 }),
+                documentation: Documentation::default(),
             },
         ],
         body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
@@ -176,9 +195,14 @@ struct Square {
                     kind: LiteralKind::Integer,
                     lexeme: "3",
                 },
+                documentation: Documentation::default(),
+                is_public: false,
             }]
             .into(),
         ),
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
@@ -193,6 +217,9 @@ struct Square
         name: "Square",
         fields: vec![],
         body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
@@ -207,13 +234,17 @@ fn test_struct_with_collection_field() {
         name: "IntArray",
         fields: vec![Field {
             name: "elements",
-            field_type: Type {
+            field_type: Type::Named {
                 name: Name::simple("Int"),
                 is_collection: true,
             },
             default_value: None,
+            documentation: Documentation::default(),
         }],
         body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
@@ -228,7 +259,7 @@ fn test_struct_with_collection_field_with_default_value() {
         name: "IntArray",
         fields: vec![Field {
             name: "elements",
-            field_type: Type {
+            field_type: Type::Named {
                 name: Name::simple("Int"),
                 is_collection: true,
             },
@@ -236,13 +267,93 @@ fn test_struct_with_collection_field_with_default_value() {
                 kind: LiteralKind::Integer,
                 lexeme: "1",
             }])),
+            documentation: Documentation::default(),
         }],
         body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
     });
     assert_eq!(struct_declaration, expected)
 }
 
-fn get_struct(program: &str) -> Declaration {
+#[test]
+fn test_struct_with_tuple_field() {
+    let program = r#"
+    struct Sized(size: (Int, [String]))
+    "#;
+    let struct_declaration = get_struct(program);
+    let expected = Declaration::Struct(StructDeclaration {
+        name: "Sized",
+        fields: vec![Field {
+            name: "size",
+            field_type: Type::Tuple(vec![
+                Type::Named {
+                    name: Name::simple("Int"),
+                    is_collection: false,
+                },
+                Type::Named {
+                    name: Name::simple("String"),
+                    is_collection: true,
+                },
+            ]),
+            default_value: None,
+            documentation: Documentation::default(),
+        }],
+        body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+        scope: None,
+    });
+    assert_eq!(struct_declaration, expected)
+}
+
+#[test]
+fn test_destructuring_declaration() {
+    let program = "const (width, height) = SIZE\n";
+    let parse_tree = parse(tokenize(program));
+    let actual = DepthFirst::find(
+        parse_tree.root,
+        |node| {
+            matches!(
+                node.kind(),
+                Some(NodeKind::DestructuringDeclarationStatement)
+            )
+        },
+        |node| node.children().unwrap_or_default(),
+    )
+    .map(Declaration::from)
+    .next()
+    .expect("Unable to find DestructuringDeclarationStatement");
+    let expected = Declaration::Destructuring(DestructuringDeclaration {
+        names: vec!["width", "height"],
+        value: Expression::ConstUse(Name::simple("SIZE")),
+        documentation: Documentation::default(),
+        is_public: false,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+#[should_panic]
+fn test_destructuring_duplicate_names() {
+    let program = "const (width, width) = SIZE\n";
+    let parse_tree = parse(tokenize(program));
+    DepthFirst::find(
+        parse_tree.root,
+        |node| {
+            matches!(
+                node.kind(),
+                Some(NodeKind::DestructuringDeclarationStatement)
+            )
+        },
+        |node| node.children().unwrap_or_default(),
+    )
+    .map(Declaration::from)
+    .for_each(drop);
+}
+
+fn get_struct(program: &str) -> Declaration<'_> {
     let parse_tree = parse(tokenize(program));
     DepthFirst::find(
         parse_tree.root,
@@ -261,6 +372,10 @@ fn test_trait_declaration_empty_no_bracket() {
     let expected = Declaration::Trait(TraitDeclaration {
         name: "Trait",
         required: vec![],
+        super_traits: vec![],
+        body: None,
+        documentation: Documentation::default(),
+        is_public: false,
     });
     assert_eq!(actual, expected)
 }
@@ -272,6 +387,10 @@ fn test_trait_declaration_empty() {
     let expected = Declaration::Trait(TraitDeclaration {
         name: "Trait",
         required: vec![],
+        super_traits: vec![],
+        body: None,
+        documentation: Documentation::default(),
+        is_public: false,
     });
     assert_eq!(actual, expected)
 }
@@ -285,26 +404,276 @@ fn test_trait_declaration_fields() {
         required: vec![
             Field {
                 name: "first",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("Int"),
                     is_collection: false,
                 },
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "second",
-                field_type: Type {
+                field_type: Type::Named {
                     name: Name::simple("String"),
                     is_collection: false,
                 },
                 default_value: None,
+                documentation: Documentation::default(),
+            },
+        ],
+        super_traits: vec![],
+        body: None,
+        documentation: Documentation::default(),
+        is_public: false,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_documented_struct() {
+    let program = r#"
+/// A rectangle
+/* block comments are not documentation */
+/// with a fixed width
+struct Rectangle(
+    /// Horizontal size
+    width: Int,
+    // not documentation either
+    height: Int = 1
+) {
+    ///   Size of the rectangle
+    const area = width
+}
+"#;
+    let struct_declaration = get_struct(program);
+    let expected = Declaration::Struct(StructDeclaration {
+        name: "Rectangle",
+        fields: vec![
+            Field {
+                name: "width",
+                field_type: Type::Named {
+                    name: Name::simple("Int"),
+                    is_collection: false,
+                },
+                default_value: None,
+                documentation: Documentation(vec!["Horizontal size"]),
+            },
+            Field {
+                name: "height",
+                field_type: Type::Named {
+                    name: Name::simple("Int"),
+                    is_collection: false,
+                },
+                default_value: Some(Expression::Literal {
+                    kind: LiteralKind::Integer,
+                    lexeme: "1",
+                }),
+                documentation: Documentation::default(),
+            },
+        ],
+        body: Some(
+            vec![ConstantDeclaration {
+                name: "area",
+                value: Expression::ConstUse(Name::simple("width")),
+                documentation: Documentation(vec!["  Size of the rectangle"]),
+                is_public: false,
+            }]
+            .into(),
+        ),
+        documentation: Documentation(vec!["A rectangle", "with a fixed width"]),
+        is_public: false,
+        scope: None,
+    });
+    assert_eq!(struct_declaration, expected)
+}
+
+#[test]
+fn test_trait_declaration_super_traits_and_body() {
+    let program =
+        "trait Titled(title: String): Named + std.Render {\nconst heading = self.title\n}\n";
+    let actual = get_trait(program);
+    let expected = Declaration::Trait(TraitDeclaration {
+        name: "Titled",
+        required: vec![Field {
+            name: "title",
+            field_type: Type::Named {
+                name: Name::simple("String"),
+                is_collection: false,
             },
+            default_value: None,
+            documentation: Documentation::default(),
+        }],
+        super_traits: vec![
+            Name::simple("Named"),
+            Name::qualified(vec!["std", "Render"]),
         ],
+        body: Some(
+            vec![ConstantDeclaration {
+                name: "heading",
+                value: Expression::FieldAccess {
+                    receiver: Box::new(Expression::SelfRef(None)),
+                    field_names: vec!["title"],
+                },
+                documentation: Documentation::default(),
+                is_public: false,
+            }]
+            .into(),
+        ),
+        documentation: Documentation::default(),
+        is_public: false,
     });
     assert_eq!(actual, expected)
 }
 
-fn get_trait(program: &str) -> Declaration {
+#[test]
+fn test_documented_trait() {
+    let program = "/// Anything with a size\ntrait Sized(\n/// In points\nsize: Int)\n";
+    let actual = get_trait(program);
+    let expected = Declaration::Trait(TraitDeclaration {
+        name: "Sized",
+        required: vec![Field {
+            name: "size",
+            field_type: Type::Named {
+                name: Name::simple("Int"),
+                is_collection: false,
+            },
+            default_value: None,
+            documentation: Documentation(vec!["In points"]),
+        }],
+        super_traits: vec![],
+        body: None,
+        documentation: Documentation(vec!["Anything with a size"]),
+        is_public: false,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_public_declarations() {
+    let program = "/// Shared\npub struct Theme\n";
+    let actual = get_struct(program);
+    assert!(matches!(
+        actual,
+        Declaration::Struct(StructDeclaration {
+            is_public: true,
+            ..
+        })
+    ));
+    let program = "pub trait Themed\n";
+    let actual = get_trait(program);
+    assert!(matches!(
+        actual,
+        Declaration::Trait(TraitDeclaration {
+            is_public: true,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_dependent_fields_order() {
+    let declaration =
+        get_struct("struct Frame(base: Int, outer: Int = self.inner, inner: Int = self.base)\n");
+    let r#struct = declaration.as_struct().expect("Expect struct");
+    let dependent_fields = r#struct
+        .dependent_fields()
+        .into_iter()
+        .map(|field| field.name)
+        .collect::<Vec<_>>();
+    assert_eq!(dependent_fields, vec!["inner", "outer"])
+}
+
+#[test]
+#[should_panic(
+    expected = "`self` in the default value of field `copy` can only be used to access fields"
+)]
+fn test_default_value_uses_whole_self() {
+    get_struct("struct Node(copy: Node = self)\n");
+}
+
+#[test]
+#[should_panic]
+fn test_public_attribute() {
+    get_struct("struct Theme {\n pub const color = 1\n}\n");
+}
+
+#[test]
+fn test_type_alias_declaration() {
+    let program = "pub type Paragraphs = [Paragraph]\n";
+    let parse_tree = parse(tokenize(program));
+    let actual = DepthFirst::find(
+        parse_tree.root,
+        |node| matches!(node.kind(), Some(NodeKind::TypeAliasDeclarationStatement)),
+        |node| node.children().unwrap_or_default(),
+    )
+    .map(Declaration::from)
+    .next()
+    .expect("Unable to find TypeAliasDeclarationStatement");
+    let expected = Declaration::TypeAlias(TypeAlias {
+        name: "Paragraphs",
+        aliased: Type::Named {
+            name: Name::simple("Paragraph"),
+            is_collection: true,
+        },
+        documentation: Documentation::default(),
+        is_public: true,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_assertion_declaration() {
+    let program = "assert(PAGES <= 10, \"Too many pages\")\n";
+    let parse_tree = parse(tokenize(program));
+    let actual = DepthFirst::find(
+        parse_tree.root,
+        |node| matches!(node.kind(), Some(NodeKind::AssertionStatement)),
+        |node| node.children().unwrap_or_default(),
+    )
+    .map(Declaration::from)
+    .next()
+    .expect("Unable to find AssertionStatement");
+    let expected = Declaration::Assertion(Assertion {
+        condition: Expression::Comparison {
+            operator: ComparisonOperator::LessOrEqual,
+            symbol: "<=",
+            left: Box::new(Expression::ConstUse(Name::simple("PAGES"))),
+            right: Box::new(Expression::Literal {
+                kind: LiteralKind::Integer,
+                lexeme: "10",
+            }),
+        },
+        message: Expression::Literal {
+            kind: LiteralKind::String,
+            lexeme: "\"Too many pages\"",
+        },
+        keyword: "assert",
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_struct_body_assertion() {
+    let declaration = get_struct(
+        "struct Title(text: String) {\n    assert(self.text != \"\", \"Title is empty\")\n}\n",
+    );
+    let body = declaration
+        .as_struct()
+        .and_then(|r#struct| r#struct.body.as_ref())
+        .expect("Expect struct body");
+    assert!(body.attributes.is_empty());
+    assert_eq!(body.assertions.len(), 1)
+}
+
+#[test]
+#[should_panic(expected = "Trait `Titled` cannot declare assertions")]
+fn test_trait_body_assertion() {
+    get_trait(
+        "trait Titled(title: String) {\n    assert(self.title != \"\", \"Title is empty\")\n}\n",
+    );
+}
+
+fn get_trait(program: &str) -> Declaration<'_> {
     let parse_tree = parse(tokenize(program));
     DepthFirst::find(
         parse_tree.root,
@@ -1,7 +1,10 @@
 use super::super::{Expression, Name};
 use super::*;
 use crate::ast::parameter::Parameter;
-use crate::ast::{Accessor, StructInitContent};
+use crate::ast::{
+    Accessor, ArithmeticOperator, ComparisonOperator, Match, MatchArm, Pattern, StringPart,
+    StructInitContent,
+};
 use crate::search::BreadthFirst;
 
 #[test]
@@ -87,6 +90,30 @@ fn test_struct_init_with_labelled_parameter() {
     )
 }
 
+#[test]
+fn test_struct_init_with_base_instance() {
+    test_struct_init_basic(
+        "const view = View(..base, width: 30)\n",
+        vec![
+            Parameter::Spread(Expression::ConstUse(Name::simple("base"))),
+            Parameter::Labelled {
+                label: "width",
+                content: Expression::Literal {
+                    kind: LiteralKind::Integer,
+                    lexeme: "30",
+                },
+            },
+        ],
+        vec![],
+    )
+}
+
+#[test]
+#[should_panic]
+fn test_struct_init_base_instance_not_first() {
+    test_struct_init_basic("const view = View(width: 30, ..base)\n", vec![], vec![])
+}
+
 #[test]
 #[should_panic]
 fn test_struct_init_mixed_parameter_types() {
@@ -226,6 +253,7 @@ fn test_method_invocation(statement: &str) {
             }),
             accessors: vec![
                 Accessor {
+                    path: vec![],
                     identifier: "pow",
                     value: Some(Expression::Literal {
                         kind: LiteralKind::Integer,
@@ -233,6 +261,7 @@ fn test_method_invocation(statement: &str) {
                     })
                 },
                 Accessor {
+                    path: vec![],
                     identifier: "abs",
                     value: None
                 }
@@ -345,6 +374,7 @@ fn test_chaining_method_from_internal() {
             field_names: vec!["id"],
         }),
         accessors: vec![Accessor {
+            path: vec![],
             identifier: "number",
             value: Some(Expression::Literal {
                 kind: LiteralKind::Integer,
@@ -355,6 +385,59 @@ fn test_chaining_method_from_internal() {
     assert_eq!(expression, expected)
 }
 
+#[test]
+fn test_update_nested_fields() {
+    let program = r#"
+    struct Person(id: Id, name: String) {
+        const renamed = self.with(id.number: 42, name: "Ann")
+    }
+    "#;
+    let expression = find_first_expression(program).expect("Expression expected");
+    let expected = Expression::ChainingMethodInvocation {
+        receiver: Box::new(Expression::SelfRef(None)),
+        accessors: vec![
+            Accessor {
+                path: vec!["id"],
+                identifier: "number",
+                value: Some(Expression::Literal {
+                    kind: LiteralKind::Integer,
+                    lexeme: "42",
+                }),
+            },
+            Accessor {
+                path: vec![],
+                identifier: "name",
+                value: Some(Expression::Literal {
+                    kind: LiteralKind::String,
+                    lexeme: "\"Ann\"",
+                }),
+            },
+        ],
+    };
+    assert_eq!(expression, expected)
+}
+
+#[test]
+#[should_panic(expected = "Only `with` accepts labelled fields")]
+fn test_update_with_other_method() {
+    find_first_expression("const a = self.id(number: 42)\n");
+}
+
+#[test]
+fn test_struct_init_nested_label() {
+    test_struct_init_basic(
+        "const view = View(size.width: 30)\n",
+        vec![Parameter::Nested {
+            path: vec!["size", "width"],
+            content: Expression::Literal {
+                kind: LiteralKind::Integer,
+                lexeme: "30",
+            },
+        }],
+        vec![],
+    )
+}
+
 #[test]
 fn test_void_expression() {
     let actual = find_first_expression("const a = ()\n").expect("Expect Expression");
@@ -399,7 +482,262 @@ fn test_empty_collection_literal() {
     assert_eq!(actual, expected)
 }
 
-fn find_first_expression(program: &str) -> Option<Expression> {
+#[test]
+fn test_tuple_literal() {
+    let actual = find_first_expression("const a = (1, \"a\")\n").expect("Expect Expression");
+    let expected = Expression::Tuple(vec![
+        Expression::Literal {
+            kind: LiteralKind::Integer,
+            lexeme: "1",
+        },
+        Expression::Literal {
+            kind: LiteralKind::String,
+            lexeme: r#""a""#,
+        },
+    ]);
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_single_element_tuple_literal() {
+    let actual = find_first_expression("const a = (1,)\n").expect("Expect Expression");
+    let expected = Expression::Tuple(vec![Expression::Literal {
+        kind: LiteralKind::Integer,
+        lexeme: "1",
+    }]);
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_parenthesized_expression_is_not_tuple() {
+    let actual = find_first_expression("const a = (1)\n").expect("Expect Expression");
+    let expected = Expression::Literal {
+        kind: LiteralKind::Integer,
+        lexeme: "1",
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_tuple_element_access() {
+    let actual = find_first_expression("const a = (pair).0.1\n").expect("Expect Expression");
+    let expected = Expression::FieldAccess {
+        receiver: Box::new(Expression::ConstUse(Name::simple("pair"))),
+        field_names: vec!["0", "1"],
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_tuple_element_access_by_name() {
+    let actual = find_first_expression("const a = pair.1\n").expect("Expect Expression");
+    let expected = Expression::ConstUse(Name::qualified(vec!["pair", "1"]));
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_arithmetic_is_left_associative() {
+    let actual =
+        find_first_expression("const a = 1in - 2cm + margin\n").expect("Expect Expression");
+    let expected = Expression::Arithmetic {
+        operator: ArithmeticOperator::Add,
+        symbol: "+",
+        left: Box::new(Expression::Arithmetic {
+            operator: ArithmeticOperator::Subtract,
+            symbol: "-",
+            left: Box::new(Expression::Literal {
+                kind: LiteralKind::Length,
+                lexeme: "1in",
+            }),
+            right: Box::new(Expression::Literal {
+                kind: LiteralKind::Length,
+                lexeme: "2cm",
+            }),
+        }),
+        right: Box::new(Expression::ConstUse(Name::simple("margin"))),
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_arithmetic_with_parentheses() {
+    let actual = find_first_expression("const a = 1 - (2 + 3)\n").expect("Expect Expression");
+    let literal = |lexeme| {
+        Box::new(Expression::Literal {
+            kind: LiteralKind::Integer,
+            lexeme,
+        })
+    };
+    let expected = Expression::Arithmetic {
+        operator: ArithmeticOperator::Subtract,
+        symbol: "-",
+        left: literal("1"),
+        right: Box::new(Expression::Arithmetic {
+            operator: ArithmeticOperator::Add,
+            symbol: "+",
+            left: literal("2"),
+            right: literal("3"),
+        }),
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_comparison_binds_looser_than_arithmetic() {
+    let actual = find_first_expression("const a = 1 + 2 < limit\n").expect("Expect Expression");
+    let literal = |lexeme| {
+        Box::new(Expression::Literal {
+            kind: LiteralKind::Integer,
+            lexeme,
+        })
+    };
+    let expected = Expression::Comparison {
+        operator: ComparisonOperator::Less,
+        symbol: "<",
+        left: Box::new(Expression::Arithmetic {
+            operator: ArithmeticOperator::Add,
+            symbol: "+",
+            left: literal("1"),
+            right: literal("2"),
+        }),
+        right: Box::new(Expression::ConstUse(Name::simple("limit"))),
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_interpolated_string() {
+    let actual = find_first_expression("const a = \"Page {number} of {TOTAL}\"\n")
+        .expect("Expect Expression");
+    let expected = Expression::InterpolatedString(vec![
+        StringPart::Text("\"Page {"),
+        StringPart::Interpolation(Expression::ConstUse(Name::simple("number"))),
+        StringPart::Text("} of {"),
+        StringPart::Interpolation(Expression::ConstUse(Name::simple("TOTAL"))),
+        StringPart::Text("}\""),
+    ]);
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_index() {
+    let actual = find_first_expression("const a = authors[0]\n").expect("Expect Expression");
+    let expected = Expression::Index {
+        receiver: Box::new(Expression::ConstUse(Name::simple("authors"))),
+        index: Box::new(Expression::Literal {
+            kind: LiteralKind::Integer,
+            lexeme: "0",
+        }),
+        bracket: "[",
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_slices() {
+    let bound = |lexeme| {
+        Some(Box::new(Expression::Literal {
+            kind: LiteralKind::Integer,
+            lexeme,
+        }))
+    };
+    let cases = [
+        ("const a = (authors)[1..3]\n", bound("1"), bound("3")),
+        ("const a = (authors)[1..]\n", bound("1"), None),
+        ("const a = (authors)[..3]\n", None, bound("3")),
+    ];
+    for (program, start, end) in cases {
+        let actual = find_first_expression(program).expect("Expect Expression");
+        let expected = Expression::Slice {
+            receiver: Box::new(Expression::ConstUse(Name::simple("authors"))),
+            start,
+            end,
+            bracket: "[",
+        };
+        assert_eq!(actual, expected)
+    }
+}
+
+#[test]
+fn test_chaining_method_with_several_arguments() {
+    let actual = find_first_expression("const a = (title).replace(\"a\", \"b\")\n")
+        .expect("Expect Expression");
+    let string = |lexeme| Expression::Literal {
+        kind: LiteralKind::String,
+        lexeme,
+    };
+    let expected = Expression::ChainingMethodInvocation {
+        receiver: Box::new(Expression::ConstUse(Name::simple("title"))),
+        accessors: vec![Accessor {
+            path: vec![],
+            identifier: "replace",
+            value: Some(Expression::Tuple(vec![string("\"a\""), string("\"b\"")])),
+        }],
+    };
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_match_expression() {
+    let program = r#"const a = match child {
+        1 => "one",
+        LIMIT => "limit"
+        figure: Figure => figure.caption,
+        _ => "other",
+    }
+    "#;
+    let actual = find_first_expression(program).expect("Expect Expression");
+    let string = |lexeme| Expression::Literal {
+        kind: LiteralKind::String,
+        lexeme,
+    };
+    let expected = Expression::Match(Match {
+        subject: Box::new(Expression::ConstUse(Name::simple("child"))),
+        arms: vec![
+            MatchArm::new(
+                Pattern::Literal {
+                    kind: LiteralKind::Integer,
+                    lexeme: "1",
+                },
+                string("\"one\""),
+            ),
+            MatchArm::new(Pattern::Name(Name::simple("LIMIT")), string("\"limit\"")),
+            MatchArm::new(
+                Pattern::Binding {
+                    identifier: "figure",
+                    structure: Name::simple("Figure"),
+                },
+                Expression::ConstUse(Name::qualified(vec!["figure", "caption"])),
+            ),
+            MatchArm::new(Pattern::Wildcard, string("\"other\"")),
+        ],
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_match_on_self_field() {
+    let actual = find_first_expression("const a = match self.size.width { _ => 1 }\n")
+        .expect("Expect Expression");
+    let subject = actual.as_match().expect("Expect match").subject.as_ref();
+    let expected = Expression::FieldAccess {
+        receiver: Box::new(Expression::SelfRef(None)),
+        field_names: vec!["size", "width"],
+    };
+    assert_eq!(subject, &expected)
+}
+
+#[test]
+fn test_negative_literal() {
+    let actual = find_first_expression("const a = -1_000\n").expect("Expect Expression");
+    let expected = Expression::Literal {
+        kind: LiteralKind::Integer,
+        lexeme: "-1_000",
+    };
+    assert_eq!(actual, expected)
+}
+
+fn find_first_expression(program: &str) -> Option<Expression<'_>> {
     let parse_tree = parse(tokenize(program));
     BreadthFirst::find(
         parse_tree.root,
@@ -1,4 +1,4 @@
-use super::super::Import;
+use super::super::{Import, ImportDeclaration, ImportedName};
 use super::*;
 
 #[test]
@@ -17,7 +17,10 @@ fn test_simple_import() {
     .next()
     .expect("Cannot find ImportDeclaration");
     let import = Import::from(node);
-    assert_eq!(import, Import::Single(vec!["images", "mountains", "blue"]))
+    assert_eq!(
+        import,
+        Import::Single(vec!["images", "mountains", "blue"].into())
+    )
 }
 
 #[test]
@@ -43,9 +46,9 @@ fn test_multiple_import() {
         Import::Multiple {
             prefix: vec!["images", "canada"],
             suffices: vec![
-                vec!["mountains", "blue"],
-                vec!["lakes", "ontario"],
-                vec!["parks"]
+                vec!["mountains", "blue"].into(),
+                vec!["lakes", "ontario"].into(),
+                vec!["parks"].into()
             ]
         }
     )
@@ -70,6 +73,97 @@ fn test_imports_wildcard() {
     assert_eq!(import, Import::Wildcard(vec!["images", "canada"]))
 }
 
+#[test]
+fn test_import_aliases() {
+    let parse_tree = parse(tokenize(
+        "use images.canada.{ mountains.blue as Blue, parks }\n",
+    ));
+    let node = DepthFirst::find(
+        parse_tree.root,
+        |node| {
+            matches!(
+                node.kind(),
+                Some(NodeKind::MultipleImportDeclarationStatement)
+            )
+        },
+        |node| node.children().unwrap_or_default(),
+    )
+    .next()
+    .expect("Cannot find MultipleImportDeclarationStatement");
+    let import = Import::from(node);
+    assert_eq!(
+        import,
+        Import::Multiple {
+            prefix: vec!["images", "canada"],
+            suffices: vec![
+                ImportedName {
+                    path: vec!["mountains", "blue"],
+                    alias: Some("Blue"),
+                },
+                vec!["parks"].into()
+            ]
+        }
+    )
+}
+
+#[test]
+fn test_public_import() {
+    let parse_tree = parse(tokenize("pub use std.rect.Size as PageSize\n"));
+    let node = DepthFirst::find(
+        parse_tree.root,
+        |node| matches!(node.kind(), Some(NodeKind::ImportDeclarationStatement)),
+        |node| node.children().unwrap_or_default(),
+    )
+    .next()
+    .expect("Cannot find ImportDeclarationStatement");
+    let import = ImportDeclaration::from(node);
+    let expected = ImportDeclaration {
+        import: Import::Single(ImportedName {
+            path: vec!["std", "rect", "Size"],
+            alias: Some("PageSize"),
+        }),
+        is_public: true,
+    };
+    assert_eq!(import, expected)
+}
+
+#[test]
+#[should_panic(expected = "Imports cannot be documented, found `Page size` before `use`")]
+fn test_documented_import() {
+    let parse_tree = parse(tokenize("/// Page size\nuse std.rect.Size\n"));
+    let node = DepthFirst::find(
+        parse_tree.root,
+        |node| matches!(node.kind(), Some(NodeKind::ImportDeclarationStatement)),
+        |node| node.children().unwrap_or_default(),
+    )
+    .next()
+    .expect("Cannot find ImportDeclarationStatement");
+    let _import = ImportDeclaration::from(node);
+}
+
+#[test]
+fn test_relative_imports() {
+    let parse_tree = parse(tokenize(
+        "use super.super.shared.Header\nuse self.sub.{ Item, Other }\nuse super.*\n",
+    ));
+    let imports: Vec<_> = DepthFirst::find(
+        parse_tree.root,
+        |node| matches!(node.kind(), Some(NodeKind::ImportDeclaration)),
+        |node| node.children().unwrap_or_default().into_iter().rev(),
+    )
+    .map(Import::from)
+    .collect();
+    let expected = vec![
+        Import::Single(vec!["super", "super", "shared", "Header"].into()),
+        Import::Multiple {
+            prefix: vec!["self", "sub"],
+            suffices: vec![vec!["Item"].into(), vec!["Other"].into()],
+        },
+        Import::Wildcard(vec!["super"]),
+    ];
+    assert_eq!(imports, expected)
+}
+
 #[test]
 #[should_panic]
 fn negative_multiple_with_wildcard() {
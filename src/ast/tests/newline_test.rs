@@ -1,5 +1,5 @@
 use super::*;
-use crate::ast::{ConstantDeclaration, Expression, Import, Name, Statement};
+use crate::ast::{ConstantDeclaration, Documentation, Expression, Import, Name, Statement};
 use crate::parser::Node;
 use crate::search::BreadthFirst;
 
@@ -154,7 +154,7 @@ fn test_import_statement(text: &str) {
     .expect("Failed to find import");
     let expected = Import::Multiple {
         prefix: vec!["test", "import"],
-        suffices: vec![vec!["first"], vec!["second"]],
+        suffices: vec![vec!["first"].into(), vec!["second"].into()],
     };
     assert_eq!(import, expected)
 }
@@ -194,6 +194,8 @@ fn test_constants_separated(program: &str) {
                 kind: LiteralKind::Integer,
                 lexeme: "1",
             },
+            documentation: Documentation::default(),
+            is_public: false,
         }),
         Statement::ConstantDeclaration(ConstantDeclaration {
             name: "second",
@@ -201,6 +203,8 @@ fn test_constants_separated(program: &str) {
                 kind: LiteralKind::Integer,
                 lexeme: "2",
             },
+            documentation: Documentation::default(),
+            is_public: false,
         }),
     ];
     assert_eq!(imports, expected)
@@ -18,7 +18,7 @@ fn test_constant_declaration() {
         Statement::ConstantDeclaration(ConstantDeclaration { name: "value", .. })
     ));
     match statement {
-        Statement::ConstantDeclaration(ConstantDeclaration { name, value }) => {
+        Statement::ConstantDeclaration(ConstantDeclaration { name, value, .. }) => {
             assert_eq!(name, "value");
             assert!(matches!(
                 value,
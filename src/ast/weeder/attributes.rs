@@ -1,6 +1,8 @@
+use super::visibility;
 use crate::ast::{ConstantDeclaration, Expression};
 
 pub fn weed(const_declaration: &ConstantDeclaration) {
+    visibility::weed(const_declaration.name, const_declaration.is_public);
     if let Expression::SelfRef(_) = const_declaration.value {
         panic!("Struct attributes cannot expose self")
     }
@@ -9,7 +11,7 @@ pub fn weed(const_declaration: &ConstantDeclaration) {
 #[cfg(test)]
 mod attributes_weeder_tests {
     use super::weed;
-    use crate::ast::{ConstantDeclaration, Expression};
+    use crate::ast::{ConstantDeclaration, Documentation, Expression};
 
     #[test]
     #[should_panic]
@@ -17,6 +19,8 @@ mod attributes_weeder_tests {
         let constant_decl = ConstantDeclaration {
             name: "constant",
             value: Expression::SelfRef(None),
+            documentation: Documentation::default(),
+            is_public: false,
         };
         weed(&constant_decl)
     }
@@ -26,6 +30,8 @@ mod attributes_weeder_tests {
         let constant_decl = ConstantDeclaration {
             name: "constant",
             value: Expression::Void,
+            documentation: Documentation::default(),
+            is_public: false,
         };
         weed(&constant_decl);
     }
@@ -0,0 +1,26 @@
+use std::collections::HashSet;
+
+pub fn weed(names: &[&str]) {
+    let mut existing_names = HashSet::new();
+    for name in names {
+        if !existing_names.insert(name) {
+            panic!("Duplicate name ({}) appeared in destructuring", name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod destructuring_weeder_tests {
+    use super::weed;
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_names() {
+        weed(&["width", "height", "width"])
+    }
+
+    #[test]
+    fn test_unique_names() {
+        weed(&["width", "height"])
+    }
+}
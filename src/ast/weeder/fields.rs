@@ -13,10 +13,10 @@ pub fn weed(fields: &[Field]) {
 #[cfg(test)]
 mod fields_weeder_tests {
     use super::weed;
-    use crate::ast::{Field, Name, Type};
+    use crate::ast::{Documentation, Field, Name, Type};
 
     fn field_type() -> Type<'static> {
-        Type {
+        Type::Named {
             name: Name::simple("type"),
             is_collection: false,
         }
@@ -29,11 +29,13 @@ mod fields_weeder_tests {
                 name: "field1",
                 field_type: field_type(),
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "field2",
                 field_type: field_type(),
                 default_value: None,
+                documentation: Documentation::default(),
             },
         ];
         weed(&fields);
@@ -47,11 +49,13 @@ mod fields_weeder_tests {
                 name: "field1",
                 field_type: field_type(),
                 default_value: None,
+                documentation: Documentation::default(),
             },
             Field {
                 name: "field1",
                 field_type: field_type(),
                 default_value: None,
+                documentation: Documentation::default(),
             },
         ];
         weed(&fields);
@@ -1,4 +1,6 @@
 pub mod attributes;
+pub mod destructuring;
 pub mod fields;
 pub mod parameters;
 pub mod structure;
+pub mod visibility;
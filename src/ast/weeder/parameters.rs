@@ -6,11 +6,20 @@ pub fn weed(parameters: &[Parameter]) {
     for parameter in parameters {
         match parameter {
             Parameter::Plain(_) => return,
+            Parameter::Spread(_) => (),
             Parameter::Labelled { label, .. } => {
-                if !existing_labels.insert(label) {
+                if !existing_labels.insert(vec![*label]) {
                     panic!("Duplicate label ({}) appeared in parameters", label)
                 }
             }
+            Parameter::Nested { path, .. } => {
+                if !existing_labels.insert(path.clone()) {
+                    panic!(
+                        "Duplicate label ({}) appeared in parameters",
+                        path.join(".")
+                    )
+                }
+            }
         }
     }
 }
@@ -48,6 +57,22 @@ mod parameter_weeder_tests {
         weed(&parameters)
     }
 
+    #[test]
+    #[should_panic]
+    fn test_weed_nested_parameters_duplicated() {
+        let parameters = [
+            Parameter::Nested {
+                path: vec!["size", "width"],
+                content: expression(),
+            },
+            Parameter::Nested {
+                path: vec!["size", "width"],
+                content: expression(),
+            },
+        ];
+        weed(&parameters)
+    }
+
     #[test]
     #[should_panic]
     fn test_weed_labelled_parameters_duplicated() {
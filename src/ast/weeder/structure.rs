@@ -2,6 +2,8 @@ use crate::ast::StructDeclaration;
 use std::collections::HashSet;
 
 pub fn weed(structure: &StructDeclaration) {
+    // panics when default values depend on each other in a cycle
+    structure.dependent_fields();
     if structure.body.is_none() {
         return;
     }
@@ -29,16 +31,19 @@ pub fn weed(structure: &StructDeclaration) {
 #[cfg(test)]
 mod struct_weeder_tests {
     use super::weed;
-    use crate::ast::{ConstantDeclaration, Expression, Field, Name, StructDeclaration, Type};
+    use crate::ast::{
+        ConstantDeclaration, Documentation, Expression, Field, Name, StructDeclaration, Type,
+    };
 
-    fn field(name: &str) -> Field {
+    fn field(name: &str) -> Field<'_> {
         Field {
             name,
-            field_type: Type {
+            field_type: Type::Named {
                 name: Name::simple("type"),
                 is_collection: false,
             },
             default_value: None,
+            documentation: Documentation::default(),
         }
     }
 
@@ -52,10 +57,15 @@ mod struct_weeder_tests {
                 [ConstantDeclaration {
                     name: "test",
                     value: Expression::ConstUse(Name::simple("test")),
+                    documentation: Documentation::default(),
+                    is_public: false,
                 }]
                 .into_iter()
                 .collect(),
             ),
+            documentation: Documentation::default(),
+            is_public: false,
+            scope: None,
         };
         weed(&structure)
     }
@@ -69,10 +79,15 @@ mod struct_weeder_tests {
                 [ConstantDeclaration {
                     name: "test1",
                     value: Expression::ConstUse(Name::simple("test")),
+                    documentation: Documentation::default(),
+                    is_public: false,
                 }]
                 .into_iter()
                 .collect(),
             ),
+            documentation: Documentation::default(),
+            is_public: false,
+            scope: None,
         };
         weed(&structure)
     }
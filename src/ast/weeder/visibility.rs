@@ -0,0 +1,23 @@
+/// Only module level declarations can be exported,
+/// constants in blocks and struct bodies are never visible from other modules
+pub fn weed(name: &str, is_public: bool) {
+    if is_public {
+        panic!("`{name}` cannot be public, only module level declarations can use `pub`")
+    }
+}
+
+#[cfg(test)]
+mod visibility_weeder_tests {
+    use super::weed;
+
+    #[test]
+    #[should_panic]
+    fn test_public_local_declaration() {
+        weed("constant", true)
+    }
+
+    #[test]
+    fn test_private_local_declaration() {
+        weed("constant", false)
+    }
+}
@@ -1,12 +1,34 @@
 use crate::env::Environment;
+use std::fmt::{Display, Formatter};
 
 mod value;
 mod value_evaluator;
 mod value_writer;
 
-pub fn generate_code(environment: &Environment) -> Vec<u8> {
+/// An assertion whose condition does not hold
+pub struct AssertionFailure<'a> {
+    /// The `assert` keyword, locating the assertion in its source
+    pub keyword: &'a str,
+    pub message: String,
+}
+
+impl Display for AssertionFailure<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Assertion failed: {}", self.message)
+    }
+}
+
+pub fn generate_code<'a>(
+    environment: &Environment<'_, 'a>,
+) -> Result<Vec<u8>, Vec<AssertionFailure<'a>>> {
     let (evaluator, evaluated_value) = resolve_value(environment);
-    write_value_to_buffer(evaluator, evaluated_value)
+    let (evaluator, buffer) = write_value_to_buffer(evaluator, evaluated_value);
+    let failed_assertions = evaluator.into_failed_assertions();
+    if failed_assertions.is_empty() {
+        Ok(buffer)
+    } else {
+        Err(failed_assertions)
+    }
 }
 
 fn resolve_value<'ast, 'a, 'env>(
@@ -17,15 +39,21 @@ fn resolve_value<'ast, 'a, 'env>(
 ) {
     let entry = environment.entry().expect("Entry cannot be found");
     let mut evaluator = value_evaluator::ExpressionEvaluator::with_environment(environment);
+    for assertion in &environment.assertions {
+        evaluator.check_assertion(assertion, None);
+    }
     let evaluated_value = value_evaluator::evaluate(&mut evaluator, entry);
     (evaluator, evaluated_value)
 }
 
-fn write_value_to_buffer<'ast, 'a>(
-    evaluator: value_evaluator::ExpressionEvaluator<'ast, 'a, '_>,
+fn write_value_to_buffer<'ast, 'a, 'env>(
+    evaluator: value_evaluator::ExpressionEvaluator<'ast, 'a, 'env>,
     evaluated_value: value::Value<'ast, 'a>,
-) -> Vec<u8> {
+) -> (
+    value_evaluator::ExpressionEvaluator<'ast, 'a, 'env>,
+    Vec<u8>,
+) {
     let mut buffer = Vec::new();
-    value_writer::write(evaluator, evaluated_value, &mut buffer);
-    buffer
+    let evaluator = value_writer::write(evaluator, evaluated_value, &mut buffer);
+    (evaluator, buffer)
 }
@@ -1,28 +1,236 @@
 use super::value_evaluator::ExpressionEvaluator;
-use crate::ast::Expression;
+use crate::ast::{Assertion, Expression};
+use crate::tokenizer::float_value;
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 type Str<'a> = Cow<'a, str>;
 
+/// Field values of an instance, a persistent map so that copying an instance is O(1)
+/// and updating one of its fields is O(log n)
+pub type Fields<'ast, 'a> = rpds::HashTrieMap<&'a str, Value<'ast, 'a>>;
+
+/// Names of the fields holding default values computed from other fields
+pub type DerivedFields<'a> = rpds::HashTrieSet<&'a str>;
+
 #[cfg_attr(test, derive(Debug, PartialEq))]
 #[derive(Clone)]
 pub enum Value<'ast, 'a> {
     Int(isize),
-    Float(f32),
+    Float(f64),
+    Length(Length),
+    Color(Color),
     Bool(bool),
     String(Str<'a>),
     Instance(Rc<Instance<'ast, 'a>>),
-    Array(Vec<Value<'ast, 'a>>),
+    /// Elements are shared between the copies of the collection
+    Array(Rc<[Value<'ast, 'a>]>),
+    Tuple(Rc<[Value<'ast, 'a>]>),
     Void,
 }
 
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Copy, Clone)]
+pub struct Length {
+    pub value: f64,
+    pub unit: LengthUnit,
+}
+
+#[cfg_attr(test, derive(Debug))]
+#[derive(Copy, Clone, PartialEq)]
+pub enum LengthUnit {
+    Point,
+    Millimetre,
+    Centimetre,
+    Inch,
+    Percent,
+}
+
+impl Length {
+    /// Parses a length literal, e.g. `2.5cm`
+    pub fn parse(lexeme: &str) -> Self {
+        let number = lexeme.trim_end_matches(|c: char| !c.is_ascii_digit());
+        let unit = match &lexeme[number.len()..] {
+            "pt" => LengthUnit::Point,
+            "mm" => LengthUnit::Millimetre,
+            "cm" => LengthUnit::Centimetre,
+            "in" => LengthUnit::Inch,
+            "%" => LengthUnit::Percent,
+            unit => unreachable!("Unexpected length unit: {unit}"),
+        };
+        let value = float_value(number);
+        Length { value, unit }
+    }
+
+    /// The sum in the unit of this length, `None` when only one of them is relative
+    pub fn add(self, other: Length) -> Option<Length> {
+        self.combine(other, |left, right| left + right)
+    }
+
+    /// The difference in the unit of this length, `None` when only one of them is relative
+    pub fn subtract(self, other: Length) -> Option<Length> {
+        self.combine(other, |left, right| left - right)
+    }
+
+    /// Compares the lengths converted to the unit of this length,
+    /// `None` when only one of them is relative
+    pub fn compare(self, other: Length) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value_in(self.unit)?)
+    }
+
+    /// Combines two lengths in the unit of this length
+    fn combine(self, other: Length, operation: impl Fn(f64, f64) -> f64) -> Option<Length> {
+        Some(Length {
+            value: operation(self.value, other.value_in(self.unit)?),
+            unit: self.unit,
+        })
+    }
+
+    /// The length converted to the given unit,
+    /// `None` when only one of the units is relative
+    fn value_in(self, unit: LengthUnit) -> Option<f64> {
+        if self.unit == unit {
+            return Some(self.value);
+        }
+        let from = self.unit.size()?;
+        let to = unit.size()?;
+        // whole sizes keep the ratios exact, e.g. 10mm is exactly 1cm
+        Some(self.value * from / to)
+    }
+}
+
+impl LengthUnit {
+    /// Size of one unit in 1/18288 of an inch, the largest unit all absolute units are
+    /// whole multiples of. `None` for relative units
+    fn size(self) -> Option<f64> {
+        match self {
+            LengthUnit::Point => Some(254.0),
+            LengthUnit::Millimetre => Some(720.0),
+            LengthUnit::Centimetre => Some(7200.0),
+            LengthUnit::Inch => Some(18288.0),
+            LengthUnit::Percent => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+impl std::fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let unit = match self {
+            LengthUnit::Point => "pt",
+            LengthUnit::Millimetre => "mm",
+            LengthUnit::Centimetre => "cm",
+            LengthUnit::Inch => "in",
+            LengthUnit::Percent => "%",
+        };
+        write!(f, "{unit}")
+    }
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[derive(Copy, Clone)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+impl Color {
+    const WHITE: Color = Color {
+        red: 255,
+        green: 255,
+        blue: 255,
+        alpha: 255,
+    };
+    const BLACK: Color = Color {
+        red: 0,
+        green: 0,
+        blue: 0,
+        alpha: 255,
+    };
+
+    /// Parses a color literal, e.g. `#1e90ff`.
+    /// Each digit of the short forms `#rgb` and `#rgba` is repeated, so `#fff` is `#ffffff`
+    pub fn parse(lexeme: &str) -> Self {
+        let digits = &lexeme[1..];
+        let channels = match digits.len() {
+            3 | 4 => digits
+                .chars()
+                .map(|digit| digit.to_digit(16).expect("hex digit") as u8 * 17)
+                .collect::<Vec<_>>(),
+            6 | 8 => (0..digits.len())
+                .step_by(2)
+                .map(|start| u8::from_str_radix(&digits[start..start + 2], 16).expect("hex"))
+                .collect(),
+            length => unreachable!("Unexpected color literal length: {length}"),
+        };
+        Color {
+            red: channels[0],
+            green: channels[1],
+            blue: channels[2],
+            alpha: channels.get(3).copied().unwrap_or(255),
+        }
+    }
+
+    /// Mixes the color with white, `amount` is between 0 and 1
+    pub fn lighten(self, amount: f64) -> Color {
+        Color {
+            alpha: self.alpha,
+            ..Self::WHITE.mix(self, amount)
+        }
+    }
+
+    /// Mixes the color with black, `amount` is between 0 and 1
+    pub fn darken(self, amount: f64) -> Color {
+        Color {
+            alpha: self.alpha,
+            ..Self::BLACK.mix(self, amount)
+        }
+    }
+
+    /// Mixes the two colors, where `weight` is the share of this color between 0 and 1
+    pub fn mix(self, other: Color, weight: f64) -> Color {
+        assert!(
+            (0.0..=1.0).contains(&weight),
+            "Color weight should be between 0 and 1, found {weight}"
+        );
+        let blend = |this: u8, other: u8| {
+            (this as f64 * weight + other as f64 * (1.0 - weight)).round() as u8
+        };
+        Color {
+            red: blend(self.red, other.red),
+            green: blend(self.green, other.green),
+            blue: blend(self.blue, other.blue),
+            alpha: blend(self.alpha, other.alpha),
+        }
+    }
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)?;
+        if self.alpha != 255 {
+            write!(f, "{:02x}", self.alpha)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg_attr(test, derive(Debug, PartialEq))]
 #[derive(Clone)]
 pub struct Instance<'ast, 'a> {
     pub structure: Rc<Struct<'ast, 'a>>,
-    pub fields: HashMap<&'a str, Value<'ast, 'a>>,
+    pub fields: Fields<'ast, 'a>,
+    /// Fields computed from other fields, recomputed whenever the instance is updated
+    pub derived_fields: DerivedFields<'a>,
 }
 
 impl<'ast, 'a> Instance<'ast, 'a> {
@@ -34,11 +242,19 @@ impl<'ast, 'a> Instance<'ast, 'a> {
     }
 
     pub fn set_field(&mut self, name: &'a str, value: Value<'ast, 'a>) {
-        self.fields.insert(name, value);
+        self.fields.insert_mut(name, value);
+        self.derived_fields.remove_mut(name);
+    }
+
+    /// Sets a field to its default value computed from other fields
+    pub fn set_derived_field(&mut self, name: &'a str, value: Value<'ast, 'a>) {
+        self.fields.insert_mut(name, value);
+        self.derived_fields.insert_mut(name);
     }
 
     pub fn reset_field(&mut self, name: &str) {
-        self.fields.remove(name);
+        self.fields.remove_mut(name);
+        self.derived_fields.remove_mut(name);
     }
 
     pub fn attribute<'env>(
@@ -48,72 +264,61 @@ impl<'ast, 'a> Instance<'ast, 'a> {
     ) -> Option<Value<'ast, 'a>> {
         self.structure
             .attributes
-            .get(name)
-            .copied()
-            .map(|attr| expression_resolver.evaluate(attr, Some(Value::Instance(Rc::clone(self)))))
+            .iter()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, attr)| expression_resolver.evaluate_attribute(attr, self))
     }
 
+    /// Values of the attributes, in the order they are declared
     pub fn attributes<'env>(
         self: &Rc<Self>,
         expression_resolver: &mut ExpressionEvaluator<'ast, 'a, 'env>,
     ) -> Vec<(&'a str, Value<'ast, 'a>)> {
-        let attributes = self
-            .structure
+        self.structure
             .attributes
             .iter()
-            .map(|(name, attr)| {
-                (
-                    *name,
-                    expression_resolver.evaluate(attr, Some(Value::Instance(Rc::clone(self)))),
-                )
-            })
-            .collect::<Vec<_>>();
-        #[cfg(test)]
-        let attributes = {
-            let mut attributes = attributes;
-            attributes.sort_by_key(|(name, _)| *name);
-            attributes
-        };
-        attributes
+            .map(|(name, attr)| (*name, expression_resolver.evaluate_attribute(attr, self)))
+            .collect()
     }
 
+    /// Values of the fields in the order they are declared,
+    /// followed by the values not declared as fields, sorted by name
     pub fn fields(self: &Rc<Self>) -> Vec<(&'a str, Value<'ast, 'a>)> {
-        let mut appeared_field = HashSet::new();
-        let mut output =
-            Vec::with_capacity(self.fields.len() + self.structure.default_fields.len());
-        for (field_name, value) in self
+        let declared = &self.structure.field_names;
+        let mut undeclared = self
             .fields
             .iter()
-            .chain(self.structure.default_fields.iter())
-        {
-            if !appeared_field.insert(*field_name) {
-                continue;
-            }
-            output.push((*field_name, value.clone()))
-        }
-        #[cfg(test)]
-        output.sort_by_key(|(name, _)| *name);
-        output
+            .filter(|(name, _)| !declared.contains(name))
+            .map(|(name, value)| (*name, value.clone()))
+            .collect::<Vec<_>>();
+        undeclared.sort_by_key(|(name, _)| *name);
+        declared
+            .iter()
+            .filter_map(|name| Some((*name, self.field(name)?)))
+            .chain(undeclared)
+            .collect()
     }
 }
 
 #[cfg_attr(test, derive(Debug, PartialEq, Default))]
 pub struct Struct<'ast, 'a> {
     pub name: &'a str,
+    /// Names of the fields, in the order they are declared
+    pub field_names: Vec<&'a str>,
     pub default_fields: HashMap<&'a str, Value<'ast, 'a>>,
-    pub attributes: HashMap<&'a str, &'ast Expression<'a>>,
+    /// Default values referring to other fields, in the order they need to be evaluated
+    pub dependent_fields: Vec<(&'a str, &'ast Expression<'a>)>,
+    /// Own attributes in the order they are declared, followed by the inherited ones
+    pub attributes: Vec<(&'a str, &'ast Expression<'a>)>,
+    /// Assertions of the struct body, checked on every created instance
+    pub assertions: &'ast [Assertion<'a>],
     pub package_state: PackageState,
 }
 
 #[cfg_attr(test, derive(Debug, PartialEq))]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Default)]
 pub enum PackageState {
     Render,
+    #[default]
     Normal,
 }
-
-impl Default for PackageState {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
@@ -0,0 +1,147 @@
+use super::value::Value;
+
+/// Evaluates a method built into the receiver.
+/// The receiver and the argument are already type checked
+pub fn evaluate<'ast, 'a>(
+    receiver: Value<'ast, 'a>,
+    method: &str,
+    argument: Option<Value<'ast, 'a>>,
+) -> Value<'ast, 'a> {
+    match (receiver, method, argument) {
+        (Value::String(string), "upper", None) => Value::String(string.to_uppercase().into()),
+        (Value::String(string), "lower", None) => Value::String(string.to_lowercase().into()),
+        (Value::String(string), "title", None) => Value::String(title_case(&string).into()),
+        (Value::String(string), "trim", None) => Value::String(string.trim().to_owned().into()),
+        (Value::String(string), "len", None) => Value::Int(string.chars().count() as isize),
+        (Value::String(string), "split", Some(Value::String(separator))) => Value::Array(
+            string
+                .split(separator.as_ref())
+                .map(|part| Value::String(part.to_owned().into()))
+                .collect(),
+        ),
+        (Value::String(string), "replace", Some(Value::Tuple(arguments))) => {
+            match arguments.as_ref() {
+                [Value::String(from), Value::String(to)] => {
+                    Value::String(string.replace(from.as_ref(), to).into())
+                }
+                _ => unreachable!("replace takes two strings"),
+            }
+        }
+        (Value::String(string), "starts_with", Some(Value::String(prefix))) => {
+            Value::Bool(string.starts_with(prefix.as_ref()))
+        }
+        (Value::String(string), "ends_with", Some(Value::String(suffix))) => {
+            Value::Bool(string.ends_with(suffix.as_ref()))
+        }
+        (Value::Int(int), "to_string", None) => Value::String(int.to_string().into()),
+        (Value::Int(int), "abs", None) => Value::Int(
+            int.checked_abs()
+                .unwrap_or_else(|| panic!("Integer overflow in `{int}.abs()`")),
+        ),
+        (Value::Int(int), "roman", None) => Value::String(roman(int).into()),
+        (Value::Float(float), "to_string", None) => Value::String(float.to_string().into()),
+        (Value::Float(float), "abs", None) => Value::Float(float.abs()),
+        (Value::Float(float), "round", Some(Value::Int(decimals))) => {
+            let scale = 10f64.powi(decimals as i32);
+            Value::Float((float * scale).round() / scale)
+        }
+        (Value::Float(float), "format", Some(Value::Int(decimals))) => {
+            let decimals = usize::try_from(decimals)
+                .unwrap_or_else(|_| panic!("Number of decimals ({decimals}) cannot be negative"));
+            Value::String(format!("{float:.decimals$}").into())
+        }
+        (Value::Color(color), "lighten", Some(Value::Float(amount))) => {
+            Value::Color(color.lighten(amount))
+        }
+        (Value::Color(color), "darken", Some(Value::Float(amount))) => {
+            Value::Color(color.darken(amount))
+        }
+        (Value::Color(color), "mix", Some(Value::Color(other))) => {
+            Value::Color(color.mix(other, 0.5))
+        }
+        (Value::Color(color), "mix", Some(Value::Tuple(arguments))) => match arguments.as_ref() {
+            [Value::Color(other), Value::Float(weight)] => Value::Color(color.mix(*other, *weight)),
+            _ => unreachable!("Arguments of `mix` are checked to be a color and a weight"),
+        },
+        (Value::Array(elements), "len", None) => Value::Int(elements.len() as isize),
+        (Value::Array(elements), "first", None) => elements
+            .first()
+            .cloned()
+            .expect("Cannot take the first element of an empty collection"),
+        (Value::Array(elements), "last", None) => elements
+            .last()
+            .cloned()
+            .expect("Cannot take the last element of an empty collection"),
+        (Value::Array(elements), "reverse", None) => {
+            Value::Array(elements.iter().rev().cloned().collect())
+        }
+        (Value::Array(elements), "flatten", None) => Value::Array(
+            elements
+                .iter()
+                .flat_map(|element| match element {
+                    Value::Array(nested) => nested.to_vec(),
+                    element => vec![element.clone()],
+                })
+                .collect(),
+        ),
+        (Value::Array(elements), "join", Some(Value::String(separator))) => {
+            let strings = elements
+                .iter()
+                .map(|element| match element {
+                    Value::String(string) => string.as_ref(),
+                    _ => unreachable!("Only strings can be joined"),
+                })
+                .collect::<Vec<_>>();
+            Value::String(strings.join(&separator).into())
+        }
+        (_, method, _) => unreachable!("Unexpected built-in method: {method}"),
+    }
+}
+
+/// Capitalizes the first letter of every word
+fn title_case(string: &str) -> String {
+    let mut is_word_start = true;
+    string
+        .chars()
+        .flat_map(|c| {
+            let converted = if is_word_start {
+                c.to_uppercase().collect::<Vec<_>>()
+            } else {
+                vec![c]
+            };
+            is_word_start = c.is_whitespace();
+            converted
+        })
+        .collect()
+}
+
+fn roman(number: isize) -> String {
+    const NUMERALS: [(isize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    assert!(
+        (1..4000).contains(&number),
+        "Only numbers from 1 to 3999 can be written in roman numerals, found {number}"
+    );
+    let mut remaining = number;
+    let mut numeral = String::new();
+    for (value, symbol) in NUMERALS {
+        while remaining >= value {
+            numeral.push_str(symbol);
+            remaining -= value;
+        }
+    }
+    numeral
+}
@@ -1,21 +1,40 @@
+use super::builtin_method_evaluator;
 use super::instance_access_evaluator::InstanceAccessEvaluator;
 use super::instance_evaluator::InstanceEvaluator;
 use super::literal_evaluator;
+use super::string_evaluator;
 use super::struct_evaluator::StructEvaluator;
 use super::value::Value;
 use crate::ast::{
-    Accessor, Block, Expression, Name, Parameter, Statement, StructDeclaration, StructInitContent,
+    Accessor, ArithmeticOperator, Assertion, Block, ComparisonOperator, DestructuringDeclaration,
+    Expression, Match, Name, Parameter, Pattern, Statement, StringPart, StructDeclaration,
+    StructInitContent,
 };
-use crate::code_generation::value::Struct;
+use crate::code_generation::value::{Color, Instance, Length, Struct};
+use crate::code_generation::AssertionFailure;
+use crate::env::scope::DeclaredElement;
 use crate::env::{Environment, Resolved};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 pub struct ExpressionEvaluator<'ast, 'a, 'env> {
     pub env: &'env Environment<'ast, 'a>,
     resolved_struct: HashMap<&'ast StructDeclaration<'a>, Rc<Struct<'ast, 'a>>>,
+    /// Structs whose default values are being evaluated, so they are not resolved yet
+    pub(super) resolving_struct: HashSet<&'ast StructDeclaration<'a>>,
+    /// Values of the constants and destructurings declared in modules, which never use `self`
+    module_values: HashSet<*const Expression<'a>>,
+    /// Evaluated values of declarations, keyed by the value expression and the instance `self`
+    /// refers to. The instance is kept alive, so that its address is not reused by another one
+    memoized: HashMap<MemoKey<'ast, 'a>, (Option<Rc<Instance<'ast, 'a>>>, Value<'ast, 'a>)>,
+    failed_assertions: Vec<AssertionFailure<'a>>,
+    /// Subjects of the matches whose arms are being evaluated, the innermost one last
+    match_subjects: Vec<(&'ast Match<'a>, Value<'ast, 'a>)>,
 }
 
+type MemoKey<'ast, 'a> = (*const Expression<'a>, Option<*const Instance<'ast, 'a>>);
+
 macro_rules! cached {
     ($map: expr, $key: expr, $loader: expr) => {
         match $map.get($key) {
@@ -31,12 +50,64 @@ macro_rules! cached {
 
 impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
     pub fn with_environment(env: &'env Environment<'ast, 'a>) -> Self {
+        let module_values = env
+            .module_declarations()
+            .into_iter()
+            .filter_map(|declared| match declared {
+                DeclaredElement::Constant(constant) => Some(&constant.value as *const _),
+                DeclaredElement::Binding(destructuring, _) => {
+                    Some(&destructuring.value as *const _)
+                }
+                _ => None,
+            })
+            .collect();
         Self {
             env,
             resolved_struct: HashMap::new(),
+            resolving_struct: HashSet::new(),
+            module_values,
+            memoized: HashMap::new(),
+            failed_assertions: vec![],
+            match_subjects: vec![],
+        }
+    }
+
+    /// Records the assertion as failed when its condition does not hold
+    pub fn check_assertion(
+        &mut self,
+        assertion: &'ast Assertion<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) {
+        if let Value::Bool(true) = self.evaluate(&assertion.condition, self_ref.clone()) {
+            return;
+        }
+        let message = match self.evaluate(&assertion.message, self_ref) {
+            Value::String(message) => message.into_owned(),
+            _ => unreachable!("Assertion message is checked to be a string"),
+        };
+        // instances with the same content fail the same assertion once
+        let is_reported = self.failed_assertions.iter().any(|failure| {
+            std::ptr::eq(failure.keyword, assertion.keyword) && failure.message == message
+        });
+        if !is_reported {
+            self.failed_assertions.push(AssertionFailure {
+                keyword: assertion.keyword,
+                message,
+            })
         }
     }
 
+    /// Checks the assertions declared in the struct of the instance, where `self` is the instance
+    pub fn check_instance_assertions(&mut self, instance: &Rc<Instance<'ast, 'a>>) {
+        for assertion in instance.structure.assertions {
+            self.check_assertion(assertion, Some(Value::Instance(instance.clone())))
+        }
+    }
+
+    pub fn into_failed_assertions(self) -> Vec<AssertionFailure<'a>> {
+        self.failed_assertions
+    }
+
     pub fn evaluate(
         &mut self,
         expression: &'ast Expression<'a>,
@@ -64,6 +135,40 @@ impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
             Expression::Collection(elements) => {
                 self.evaluate_collection_literal(elements, self_ref)
             }
+            Expression::Tuple(elements) => self.evaluate_tuple_literal(elements, self_ref),
+            Expression::Arithmetic {
+                operator,
+                symbol,
+                left,
+                right,
+            } => self.evaluate_arithmetic(*operator, symbol, left, right, self_ref),
+            Expression::Comparison {
+                operator,
+                symbol,
+                left,
+                right,
+            } => self.evaluate_comparison(*operator, symbol, left, right, self_ref),
+            Expression::InterpolatedString(parts) => {
+                self.evaluate_interpolated_string(parts, self_ref)
+            }
+            Expression::Index {
+                receiver,
+                index,
+                bracket,
+            } => self.evaluate_index(receiver, index, bracket, self_ref),
+            Expression::Slice {
+                receiver,
+                start,
+                end,
+                bracket,
+            } => self.evaluate_slice(
+                receiver,
+                start.as_deref(),
+                end.as_deref(),
+                bracket,
+                self_ref,
+            ),
+            Expression::Match(r#match) => self.evaluate_match(r#match, self_ref),
         }
     }
 
@@ -78,14 +183,66 @@ impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
             .get(name)
             .unwrap_or_else(|| panic!("name `{}` is not resolved", name));
         match resolved {
-            Resolved::Constant(constant) => self.evaluate(&constant.value, self_ref),
+            Resolved::Constant(constant) => self.evaluate_declared(&constant.value, self_ref),
             Resolved::InstanceAccess(receiver, accesses) => {
                 InstanceAccessEvaluator::new(self, self_ref).evaluate(receiver, accesses)
             }
+            Resolved::Binding(destructuring, index) => {
+                self.evaluate_binding(destructuring, *index, self_ref)
+            }
+            Resolved::BindingAccess(destructuring, index, accesses) => {
+                let binding = self.evaluate_binding(destructuring, *index, self_ref);
+                accesses
+                    .iter()
+                    .fold(binding, |value, name| self.evaluate_access(value, name))
+            }
+            // the bound instance is the subject of the match
+            Resolved::PatternBinding(r#match, _) => self.match_subject(r#match),
+            Resolved::PatternBindingAccess(r#match, _, accesses) => {
+                let binding = self.match_subject(r#match);
+                accesses
+                    .iter()
+                    .fold(binding, |value, name| self.evaluate_access(value, name))
+            }
             _ => unreachable!("name `{}` is not resolved to constant or field", name),
         }
     }
 
+    /// Evaluates the value of a constant or a destructuring once for each instance `self`
+    /// refers to, the ones declared in modules are evaluated once
+    pub(super) fn evaluate_declared(
+        &mut self,
+        value: &'ast Expression<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let self_ref = if self.module_values.contains(&(value as *const _)) {
+            None
+        } else {
+            self_ref
+        };
+        let instance = match &self_ref {
+            Some(Value::Instance(instance)) => Some(Rc::clone(instance)),
+            Some(_) => return self.evaluate(value, self_ref),
+            None => None,
+        };
+        let key = (value as *const _, instance.as_ref().map(Rc::as_ptr));
+        if let Some((_, memoized)) = self.memoized.get(&key) {
+            return memoized.clone();
+        }
+        let evaluated = self.evaluate(value, self_ref);
+        self.memoized.insert(key, (instance, evaluated.clone()));
+        evaluated
+    }
+
+    /// Evaluates the attribute of the instance, once for each instance
+    pub fn evaluate_attribute(
+        &mut self,
+        attribute: &'ast Expression<'a>,
+        instance: &Rc<Instance<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        self.evaluate_declared(attribute, Some(Value::Instance(Rc::clone(instance))))
+    }
+
     fn evaluate_struct_initialization(
         &mut self,
         name: &'ast Name<'a>,
@@ -102,9 +259,14 @@ impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
             Resolved::Struct(definition) => *definition,
             _ => unreachable!("name `{}` is not resolved to struct", name),
         };
-        let structure = cached!(self.resolved_struct, struct_declaration, |declaration| {
-            StructEvaluator(self).evaluate(declaration)
-        });
+        let structure = if self.resolving_struct.contains(struct_declaration) {
+            // a default value creates the struct, the instance evaluates the defaults it needs
+            StructEvaluator(self).evaluate_without_defaults(struct_declaration)
+        } else {
+            cached!(self.resolved_struct, struct_declaration, |declaration| {
+                StructEvaluator(self).evaluate(declaration)
+            })
+        };
         let instance = InstanceEvaluator::new(self, self_ref).evaluate(
             structure,
             &struct_declaration.fields,
@@ -122,17 +284,49 @@ impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
     ) -> Value<'ast, 'a> {
         let mut value = self.evaluate(receiver, self_ref);
         for name in access_names {
-            value = match value {
-                Value::Instance(instance) => instance
-                    .field(name)
-                    .or_else(|| instance.attribute(self, name))
-                    .unwrap_or_else(|| panic!("field `{}` does not exist", name)),
-                _ => unreachable!("Field access can only happen on struct instance"),
-            }
+            value = self.evaluate_access(value, name);
         }
         value
     }
 
+    /// Accesses a field or an attribute of an instance, or an element of a tuple
+    pub(super) fn evaluate_access(
+        &mut self,
+        value: Value<'ast, 'a>,
+        name: &str,
+    ) -> Value<'ast, 'a> {
+        match value {
+            Value::Instance(instance) => instance
+                .field(name)
+                .or_else(|| instance.attribute(self, name))
+                .unwrap_or_else(|| panic!("field `{}` does not exist", name)),
+            Value::Tuple(elements) => {
+                let index = name
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| panic!("`{}` is not a tuple index", name));
+                assert!(
+                    index < elements.len(),
+                    "tuple index `{}` is out of range",
+                    name
+                );
+                elements[index].clone()
+            }
+            _ => unreachable!("Field access can only happen on struct instance or tuple"),
+        }
+    }
+
+    fn evaluate_binding(
+        &mut self,
+        destructuring: &'ast DestructuringDeclaration<'a>,
+        index: usize,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        match self.evaluate_declared(&destructuring.value, self_ref) {
+            Value::Tuple(elements) => elements[index].clone(),
+            _ => unreachable!("Only tuples can be destructured"),
+        }
+    }
+
     fn evaluate_chaining_methods(
         &mut self,
         receiver: &'ast Expression<'a>,
@@ -140,27 +334,82 @@ impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
         self_ref: Option<Value<'ast, 'a>>,
     ) -> Value<'ast, 'a> {
         let mut value = self.evaluate(receiver, self_ref.clone());
+        if !matches!(value, Value::Instance(_)) {
+            return self.evaluate_builtin_methods(value, accessors, self_ref);
+        }
         let instance = match &mut value {
             Value::Instance(instance) => Rc::make_mut(instance),
             _ => unreachable!("Chaining methods can only happen on structure"),
         };
-        for Accessor { identifier, value } in accessors {
-            if let Some(expression) = value {
-                instance.set_field(identifier, self.evaluate(expression, self_ref.clone()))
-            } else {
-                instance.reset_field(identifier)
-            }
+        for Accessor {
+            path,
+            identifier,
+            value,
+        } in accessors
+        {
+            let value = value
+                .as_ref()
+                .map(|expression| self.evaluate(expression, self_ref.clone()));
+            self.update_field(instance, path, identifier, value);
+        }
+        // fields reset to default values referring to other fields
+        if let Value::Instance(instance) = &mut value {
+            InstanceEvaluator::new(self, None).bind_dependent_fields(instance);
         }
         value
     }
 
+    fn evaluate_builtin_methods(
+        &mut self,
+        receiver: Value<'ast, 'a>,
+        accessors: &'ast [Accessor<'a>],
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        accessors.iter().fold(receiver, |value, accessor| {
+            let argument = accessor
+                .value
+                .as_ref()
+                .map(|expression| self.evaluate(expression, self_ref.clone()));
+            builtin_method_evaluator::evaluate(value, accessor.identifier, argument)
+        })
+    }
+
+    /// Updates the field reached by the path, rebuilding every instance along the way
+    fn update_field(
+        &mut self,
+        instance: &mut Instance<'ast, 'a>,
+        path: &[&'a str],
+        identifier: &'a str,
+        value: Option<Value<'ast, 'a>>,
+    ) {
+        match (path.split_first(), value) {
+            (Some((field, rest)), value) => {
+                let mut nested = instance
+                    .field(field)
+                    .expect("Nested field should have a value");
+                match &mut nested {
+                    Value::Instance(nested_instance) => {
+                        self.update_field(Rc::make_mut(nested_instance), rest, identifier, value);
+                        InstanceEvaluator::new(self, None).bind_dependent_fields(nested_instance);
+                    }
+                    _ => unreachable!("Only instances have nested fields"),
+                }
+                instance.set_field(field, nested)
+            }
+            (None, Some(value)) => instance.set_field(identifier, value),
+            (None, None) => instance.reset_field(identifier),
+        }
+    }
+
     fn evaluate_block(
         &mut self,
         block: &'ast Block<'a>,
         self_ref: Option<Value<'ast, 'a>>,
     ) -> Value<'ast, 'a> {
         match block.statements.last() {
-            Some(Statement::ConstantDeclaration(_)) | None => Value::Void,
+            Some(Statement::ConstantDeclaration(_) | Statement::Destructuring(_)) | None => {
+                Value::Void
+            }
             Some(Statement::Expression(expr)) => self.evaluate(expr, self_ref),
         }
     }
@@ -179,7 +428,275 @@ impl<'ast, 'a, 'env> ExpressionEvaluator<'ast, 'a, 'env> {
         let elements = elements
             .iter()
             .map(|element| self.evaluate(element, self_ref.clone()))
-            .collect::<Vec<_>>();
+            .collect();
         Value::Array(elements)
     }
+
+    fn evaluate_tuple_literal(
+        &mut self,
+        elements: &'ast [Expression<'a>],
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let elements = elements
+            .iter()
+            .map(|element| self.evaluate(element, self_ref.clone()))
+            .collect();
+        Value::Tuple(elements)
+    }
+
+    fn evaluate_interpolated_string(
+        &mut self,
+        parts: &'ast [StringPart<'a>],
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let mut string = String::new();
+        for part in parts {
+            match part {
+                StringPart::Text(lexeme) => {
+                    string.push_str(&string_evaluator::evaluate_part(lexeme))
+                }
+                StringPart::Interpolation(expression) => {
+                    match self.evaluate(expression, self_ref.clone()) {
+                        Value::String(text) => string.push_str(&text),
+                        Value::Int(int) => string.push_str(&int.to_string()),
+                        Value::Float(float) => string.push_str(&float.to_string()),
+                        Value::Bool(bool) => string.push_str(&bool.to_string()),
+                        _ => unreachable!("Interpolated value is not a string, number or boolean"),
+                    }
+                }
+            }
+        }
+        Value::String(string.into())
+    }
+
+    fn evaluate_index(
+        &mut self,
+        receiver: &'ast Expression<'a>,
+        index: &'ast Expression<'a>,
+        bracket: &'a str,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let elements = self.evaluate_indexed_collection(receiver, self_ref.clone());
+        let index = self.evaluate_position(index, self_ref);
+        let length = elements.len();
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| elements.get(index).cloned())
+            .unwrap_or_else(|| {
+                let location = self.locate(bracket);
+                panic!(
+                    "{location}: Index {index} is out of bounds for collection of length {length}"
+                )
+            })
+    }
+
+    fn evaluate_slice(
+        &mut self,
+        receiver: &'ast Expression<'a>,
+        start: Option<&'ast Expression<'a>>,
+        end: Option<&'ast Expression<'a>>,
+        bracket: &'a str,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let elements = self.evaluate_indexed_collection(receiver, self_ref.clone());
+        let length = elements.len();
+        let start = start.map_or(0, |start| self.evaluate_position(start, self_ref.clone()));
+        let end = end.map_or(length as isize, |end| self.evaluate_position(end, self_ref));
+        if !(0 <= start && start <= end && end <= length as isize) {
+            let location = self.locate(bracket);
+            panic!("{location}: Slice {start}..{end} is out of bounds for collection of length {length}")
+        }
+        Value::Array(elements[start as usize..end as usize].into())
+    }
+
+    fn evaluate_indexed_collection(
+        &mut self,
+        receiver: &'ast Expression<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Rc<[Value<'ast, 'a>]> {
+        match self.evaluate(receiver, self_ref) {
+            Value::Array(elements) => elements,
+            _ => unreachable!("Only collections can be indexed"),
+        }
+    }
+
+    fn evaluate_position(
+        &mut self,
+        position: &'ast Expression<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> isize {
+        match self.evaluate(position, self_ref) {
+            Value::Int(position) => position,
+            _ => unreachable!("Collection index is not an integer"),
+        }
+    }
+
+    fn evaluate_match(
+        &mut self,
+        r#match: &'ast Match<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let subject = self.evaluate(&r#match.subject, self_ref.clone());
+        for arm in &r#match.arms {
+            if self.matches(&arm.pattern, &subject, self_ref.clone()) {
+                self.match_subjects.push((r#match, subject));
+                let value = self.evaluate(&arm.value, self_ref);
+                self.match_subjects.pop();
+                return value;
+            }
+        }
+        unreachable!("Match is checked to be exhaustive")
+    }
+
+    /// The subject bound by the pattern of the arm being evaluated
+    fn match_subject(&self, r#match: &'ast Match<'a>) -> Value<'ast, 'a> {
+        self.match_subjects
+            .iter()
+            .rev()
+            .find(|(evaluating, _)| std::ptr::eq(*evaluating, r#match))
+            .map(|(_, subject)| subject.clone())
+            .expect("Pattern bindings are only used in the arm of their match")
+    }
+
+    fn matches(
+        &mut self,
+        pattern: &'ast Pattern<'a>,
+        subject: &Value<'ast, 'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> bool {
+        let name = match pattern {
+            Pattern::Wildcard => return true,
+            Pattern::Literal { kind, lexeme } => {
+                return is_same_primitive(&literal_evaluator::evaluate(kind, lexeme), subject)
+            }
+            Pattern::Name(name)
+            | Pattern::Binding {
+                structure: name, ..
+            } => name,
+        };
+        match self.env.resolved_names.get(name) {
+            // an instance is created from the cached struct, so an uncached struct has no instance
+            Some(Resolved::Struct(r#struct)) => match (subject, self.resolved_struct.get(r#struct))
+            {
+                (Value::Instance(instance), Some(structure)) => {
+                    Rc::ptr_eq(&instance.structure, structure)
+                }
+                _ => false,
+            },
+            _ => is_same_primitive(&self.evaluate_name(name, self_ref), subject),
+        }
+    }
+
+    fn evaluate_arithmetic(
+        &mut self,
+        operator: ArithmeticOperator,
+        symbol: &'a str,
+        left: &'ast Expression<'a>,
+        right: &'ast Expression<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let left = self.evaluate(left, self_ref.clone());
+        let right = self.evaluate(right, self_ref);
+        match (operator, left, right) {
+            (ArithmeticOperator::Add, Value::Int(left), Value::Int(right)) => Value::Int(
+                left.checked_add(right)
+                    .unwrap_or_else(|| self.integer_overflow(symbol, left, right)),
+            ),
+            (ArithmeticOperator::Add, Value::Float(left), Value::Float(right)) => {
+                Value::Float(left + right)
+            }
+            (ArithmeticOperator::Add, Value::Length(left), Value::Length(right)) => Value::Length(
+                left.add(right)
+                    .unwrap_or_else(|| self.length_mismatch("combine", symbol, &left, &right)),
+            ),
+            (ArithmeticOperator::Subtract, Value::Int(left), Value::Int(right)) => Value::Int(
+                left.checked_sub(right)
+                    .unwrap_or_else(|| self.integer_overflow(symbol, left, right)),
+            ),
+            (ArithmeticOperator::Subtract, Value::Float(left), Value::Float(right)) => {
+                Value::Float(left - right)
+            }
+            (ArithmeticOperator::Subtract, Value::Length(left), Value::Length(right)) => {
+                Value::Length(
+                    left.subtract(right)
+                        .unwrap_or_else(|| self.length_mismatch("combine", symbol, &left, &right)),
+                )
+            }
+            _ => unreachable!("Operands of `{operator}` are not numbers of the same type"),
+        }
+    }
+
+    /// Reports an arithmetic expression whose result does not fit in an `Int`,
+    /// along with where its operator is written
+    fn integer_overflow(&self, symbol: &'a str, left: isize, right: isize) -> ! {
+        let location = self.locate(symbol);
+        panic!("{location}: Integer overflow in `{left} {symbol} {right}`")
+    }
+
+    /// Reports an operation mixing a relative and an absolute length,
+    /// along with where its operator is written
+    fn length_mismatch(&self, action: &str, symbol: &'a str, left: &Length, right: &Length) -> ! {
+        let location = self.locate(symbol);
+        panic!("{location}: Cannot {action} relative and absolute lengths: `{left}` and `{right}`")
+    }
+
+    fn locate(&self, lexeme: &'a str) -> String {
+        self.env
+            .sources
+            .locate(lexeme)
+            .map_or_else(|| "<unknown>".to_string(), |location| location.to_string())
+    }
+
+    fn evaluate_comparison(
+        &mut self,
+        operator: ComparisonOperator,
+        symbol: &'a str,
+        left: &'ast Expression<'a>,
+        right: &'ast Expression<'a>,
+        self_ref: Option<Value<'ast, 'a>>,
+    ) -> Value<'ast, 'a> {
+        let left = self.evaluate(left, self_ref.clone());
+        let right = self.evaluate(right, self_ref);
+        if !operator.is_ordering() {
+            let is_equal = is_same_primitive(&left, &right);
+            return Value::Bool(is_equal == (operator == ComparisonOperator::Equal));
+        }
+        let ordering = match (&left, &right) {
+            (Value::Int(left), Value::Int(right)) => left.partial_cmp(right),
+            (Value::Float(left), Value::Float(right)) => left.partial_cmp(right),
+            (Value::Length(left), Value::Length(right)) => Some(
+                left.compare(*right)
+                    .unwrap_or_else(|| self.length_mismatch("compare", symbol, left, right)),
+            ),
+            _ => unreachable!("Operands of `{operator}` are not numbers of the same type"),
+        };
+        let is_true = match operator {
+            ComparisonOperator::Less => ordering.is_some_and(Ordering::is_lt),
+            ComparisonOperator::LessOrEqual => ordering.is_some_and(Ordering::is_le),
+            ComparisonOperator::Greater => ordering.is_some_and(Ordering::is_gt),
+            ComparisonOperator::GreaterOrEqual => ordering.is_some_and(Ordering::is_ge),
+            ComparisonOperator::Equal | ComparisonOperator::NotEqual => {
+                unreachable!("Equality is not an ordering")
+            }
+        };
+        Value::Bool(is_true)
+    }
+}
+
+/// Equality of primitive values, compared by patterns and `==`
+fn is_same_primitive(pattern: &Value, subject: &Value) -> bool {
+    match (pattern, subject) {
+        (Value::Int(pattern), Value::Int(subject)) => pattern == subject,
+        (Value::Float(pattern), Value::Float(subject)) => pattern == subject,
+        (Value::Bool(pattern), Value::Bool(subject)) => pattern == subject,
+        (Value::String(pattern), Value::String(subject)) => pattern == subject,
+        (Value::Length(pattern), Value::Length(subject)) => {
+            pattern.compare(*subject) == Some(Ordering::Equal)
+        }
+        (Value::Color(pattern), Value::Color(subject)) => {
+            let channels = |color: &Color| (color.red, color.green, color.blue, color.alpha);
+            channels(pattern) == channels(subject)
+        }
+        _ => unreachable!("Only primitive values of the same type are compared"),
+    }
 }
@@ -25,15 +25,9 @@ impl<'ast, 'a, 'env, 'res> InstanceAccessEvaluator<'ast, 'a, 'env, 'res> {
     ) -> Value<'ast, 'a> {
         let mut value = self
             .expr_evaluator
-            .evaluate(&receiver.value, self.self_ref);
+            .evaluate_declared(&receiver.value, self.self_ref);
         for name in instance_access {
-            value = match value {
-                Value::Instance(instance) => instance
-                    .field(name)
-                    .or_else(|| instance.attribute(self.expr_evaluator, name))
-                    .unwrap_or_else(|| panic!("field `{}` does not exist", name)),
-                _ => unreachable!("Field access can only happen on struct instance"),
-            }
+            value = self.expr_evaluator.evaluate_access(value, name);
         }
         value
     }
@@ -1,8 +1,7 @@
 use super::expression_evaluator::ExpressionEvaluator;
 use super::value::Value;
-use super::value::{Instance, Struct};
-use crate::ast::{Field, Parameter, StructInitContent};
-use std::collections::HashMap;
+use super::value::{DerivedFields, Fields, Instance, Struct};
+use crate::ast::{Expression, Field, Parameter, StructInitContent};
 use std::rc::Rc;
 
 pub struct InstanceEvaluator<'ast, 'a, 'env, 'res> {
@@ -30,44 +29,114 @@ impl<'ast, 'a, 'env, 'res> InstanceEvaluator<'ast, 'a, 'env, 'res> {
         parameters: &'ast [Parameter<'a>],
         init_content: &'ast Option<StructInitContent<'a>>,
     ) -> Instance<'ast, 'a> {
-        let is_labelled_parameter = parameters
-            .first()
-            .map(Parameter::is_labelled)
-            .unwrap_or(false);
-        let mut instance_fields = match is_labelled_parameter {
-            true => self.add_labelled_parameters(parameters),
-            false => self.add_positional_parameters(parameters, fields),
+        let (mut instance_fields, derived_fields) = match parameters.split_first() {
+            Some((Parameter::Spread(base), overrides)) => self.add_base_parameters(base, overrides),
+            Some((Parameter::Labelled { .. }, _)) => (
+                self.add_labelled_parameters(Fields::new(), parameters),
+                DerivedFields::new(),
+            ),
+            _ => (
+                self.add_positional_parameters(parameters, fields),
+                DerivedFields::new(),
+            ),
         };
         if let Some(init_content) = init_content {
-            instance_fields.insert(INIT_CONTENT, self.resolve_init_content(init_content));
+            instance_fields.insert_mut(INIT_CONTENT, self.resolve_init_content(init_content));
         }
-        Instance {
+        self.add_unresolved_defaults(&mut instance_fields, &structure, fields);
+        let mut instance = Rc::new(Instance {
             structure,
             fields: instance_fields,
+            derived_fields,
+        });
+        self.bind_dependent_fields(&mut instance);
+        Rc::unwrap_or_clone(instance)
+    }
+
+    /// Evaluates the default values referring to other fields, for the fields without a value
+    /// or whose value was computed before the fields it depends on were updated
+    pub fn bind_dependent_fields(&mut self, instance: &mut Rc<Instance<'ast, 'a>>) {
+        if !instance.derived_fields.is_empty() {
+            let instance = Rc::make_mut(instance);
+            for name in std::mem::take(&mut instance.derived_fields).iter() {
+                instance.fields.remove_mut(name);
+            }
+        }
+        let structure = Rc::clone(&instance.structure);
+        for (name, default_value) in &structure.dependent_fields {
+            if instance.fields.contains_key(name) {
+                continue;
+            }
+            let value = self
+                .expr_resolver
+                .evaluate(default_value, Some(Value::Instance(Rc::clone(instance))));
+            Rc::make_mut(instance).set_derived_field(name, value);
         }
     }
 
+    /// Adds the default values the struct does not hold yet, because they are being evaluated
+    fn add_unresolved_defaults(
+        &mut self,
+        instance_fields: &mut Fields<'ast, 'a>,
+        structure: &Struct<'ast, 'a>,
+        fields: &'ast [Field<'a>],
+    ) {
+        for field in fields {
+            let is_missing = !instance_fields.contains_key(field.name)
+                && !structure.default_fields.contains_key(field.name);
+            match &field.default_value {
+                Some(default_value) if is_missing && field.self_dependencies().is_empty() => {
+                    let value = self.expr_resolver.evaluate_declared(default_value, None);
+                    instance_fields.insert_mut(field.name, value);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Starts from the fields of the base instance, then applies the labelled overrides
+    fn add_base_parameters(
+        &mut self,
+        base: &'ast Expression<'a>,
+        overrides: &'ast [Parameter<'a>],
+    ) -> (Fields<'ast, 'a>, DerivedFields<'a>) {
+        let base = match self.expr_resolver.evaluate(base, self.self_ref.clone()) {
+            Value::Instance(instance) => instance,
+            _ => unreachable!("Base instance is checked to be a struct instance"),
+        };
+        let mut derived_fields = base.derived_fields.clone();
+        for parameter in overrides {
+            if let Parameter::Labelled { label, .. } = parameter {
+                derived_fields.remove_mut(label);
+            }
+        }
+        let fields = self.add_labelled_parameters(base.fields.clone(), overrides);
+        (fields, derived_fields)
+    }
+
+    /// Adds the labelled parameters to the fields, replacing the existing values
     fn add_labelled_parameters(
         &mut self,
+        mut fields: Fields<'ast, 'a>,
         parameter: &'ast [Parameter<'a>],
-    ) -> HashMap<&'a str, Value<'ast, 'a>> {
-        parameter
-            .iter()
-            .map(|parameter| match parameter {
-                Parameter::Labelled { label, content } => (
-                    *label,
+    ) -> Fields<'ast, 'a> {
+        for parameter in parameter {
+            match parameter {
+                Parameter::Labelled { label, content } => fields.insert_mut(
+                    label,
                     self.expr_resolver.evaluate(content, self.self_ref.clone()),
                 ),
                 _ => unreachable!("Checked before"),
-            })
-            .collect()
+            }
+        }
+        fields
     }
 
     fn add_positional_parameters(
         &mut self,
         parameter: &'ast [Parameter<'a>],
         fields: &'ast [Field<'a>],
-    ) -> HashMap<&'a str, Value<'ast, 'a>> {
+    ) -> Fields<'ast, 'a> {
         fields
             .iter()
             .map(|field| field.name)
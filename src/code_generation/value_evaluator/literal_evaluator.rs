@@ -1,14 +1,19 @@
 use super::string_evaluator;
-use super::value::Value;
-use crate::tokenizer::LiteralKind;
+use super::value::{Color, Length, Value};
+use crate::tokenizer::{float_value, integer_value, LiteralKind};
 
 pub fn evaluate<'ast, 'a>(kind: &LiteralKind, lexeme: &'a str) -> Value<'ast, 'a> {
     match kind {
-        LiteralKind::Integer => Value::Int(lexeme.parse().expect("integer")),
-        LiteralKind::Binary => Value::Int(isize::from_str_radix(&lexeme[2..], 2).expect("binary")),
-        LiteralKind::Hex => Value::Int(isize::from_str_radix(&lexeme[2..], 16).expect("hex")),
+        LiteralKind::Integer | LiteralKind::Binary | LiteralKind::Octal | LiteralKind::Hex => {
+            Value::Int(integer_value(*kind, lexeme).expect("integer"))
+        }
         LiteralKind::Boolean => Value::Bool(lexeme.parse().expect("bool")),
-        LiteralKind::Floating => Value::Float(lexeme.parse().expect("float")),
+        LiteralKind::Floating => Value::Float(float_value(lexeme)),
         LiteralKind::String => Value::String(string_evaluator::evaluate(lexeme)),
+        LiteralKind::Length => Value::Length(Length::parse(lexeme)),
+        LiteralKind::Color => Value::Color(Color::parse(lexeme)),
+        LiteralKind::StringHead | LiteralKind::StringMiddle | LiteralKind::StringTail => {
+            unreachable!("String parts are evaluated within interpolated strings")
+        }
     }
 }
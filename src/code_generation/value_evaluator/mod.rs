@@ -1,6 +1,7 @@
 use super::value;
 use crate::ast::ConstantDeclaration;
 
+mod builtin_method_evaluator;
 mod expression_evaluator;
 mod instance_access_evaluator;
 mod instance_evaluator;
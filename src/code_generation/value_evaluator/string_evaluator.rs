@@ -1,7 +1,15 @@
-use crate::tokenizer::{is_whitespace_or_newline, Cursor};
+use crate::tokenizer::{is_whitespace, is_whitespace_or_newline, Cursor};
 use std::borrow::Cow;
 
-pub fn evaluate(literal: &str) -> Cow<str> {
+const TEXT_BLOCK_QUOTES: &str = r#"""""#;
+
+pub fn evaluate(literal: &str) -> Cow<'_, str> {
+    if let Some(content) = literal
+        .strip_prefix(TEXT_BLOCK_QUOTES)
+        .and_then(|content| content.strip_suffix(TEXT_BLOCK_QUOTES))
+    {
+        return Cow::Owned(resolve_text_block(content));
+    }
     let is_raw = literal.starts_with("r#");
     let content = strip_surroundings(literal);
     if is_raw || !content.contains('\\') {
@@ -11,20 +19,60 @@ pub fn evaluate(literal: &str) -> Cow<str> {
     }
 }
 
+/// Evaluates the text of an interpolated string between its quotes and braces
+pub fn evaluate_part(lexeme: &str) -> Cow<'_, str> {
+    let content = &lexeme[1..lexeme.len() - 1];
+    if content.contains('\\') {
+        Cow::Owned(resolve_simple_literal(content))
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
 fn strip_surroundings(lexeme: &str) -> &str {
     let first_quote = lexeme.find('"').expect("First quote");
     let last_quote = lexeme.rfind('"').expect("Last quote");
     &lexeme[first_quote + 1..last_quote]
 }
 
+/// Normalizes line breaks to `\n` and strips the indentation shared by all lines.
+/// Blank lines do not count for the shared indentation,
+/// except the line of the closing quotes, which is removed afterwards
+fn resolve_text_block(content: &str) -> String {
+    let content = content.replace("\r\n", "\n");
+    let (_opening_line, text) = content
+        .split_once('\n')
+        .expect("Text block starts with a line break");
+    let mut lines = text.split('\n').collect::<Vec<_>>();
+    let closing_line = match lines.last() {
+        Some(last) if last.chars().all(is_whitespace) => lines.pop(),
+        _ => None,
+    };
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.chars().all(is_whitespace))
+        .chain(closing_line.as_ref())
+        .map(|line| line.len() - line.trim_start_matches(is_whitespace).len())
+        .min()
+        .unwrap_or_default();
+    let stripped = lines
+        .into_iter()
+        .map(|line| line.get(indentation..).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if stripped.contains('\\') {
+        resolve_simple_literal(&stripped)
+    } else {
+        stripped
+    }
+}
+
 fn resolve_simple_literal(literal: &str) -> String {
     let mut resolved = String::with_capacity(literal.len());
     let mut cursor = Cursor::from_iter(literal.chars());
     while let Some(leading) = cursor.bump() {
         if leading == '\\' {
-            if let Some(escaped) =
-                resolve_escaped(cursor.bump().expect("At least one char after \\"))
-            {
+            if let Some(escaped) = resolve_escaped(&mut cursor) {
                 resolved.push(escaped);
             } else {
                 cursor.eat_while(is_whitespace_or_newline);
@@ -36,14 +84,37 @@ fn resolve_simple_literal(literal: &str) -> String {
     resolved
 }
 
-fn resolve_escaped(c: char) -> Option<char> {
-    match c {
+/// Escapes are already validated by the tokenizer
+fn resolve_escaped(cursor: &mut Cursor) -> Option<char> {
+    match cursor.bump().expect("At least one char after \\") {
+        '0' => Some('\0'),
+        'x' => char::from_u32(hex_value(cursor, 2)),
+        'u' => {
+            let _open_brace = cursor.bump();
+            let escaped = char::from_u32(hex_value(cursor, 6));
+            let _close_brace = cursor.bump();
+            escaped
+        }
         't' => Some('\t'),
         'n' => Some('\n'),
         'r' => Some('\r'),
         '"' => Some('"'),
         '\'' => Some('\''),
         '\\' => Some('\\'),
-        _ => None,
+        '{' => Some('{'),
+        '}' => Some('}'),
+        _ => None, // line continuation
+    }
+}
+
+fn hex_value(cursor: &mut Cursor, digits: usize) -> u32 {
+    let mut value = 0;
+    for _ in 0..digits {
+        match cursor.first().and_then(|c| c.to_digit(16)) {
+            Some(digit) => value = value * 16 + digit,
+            None => break,
+        }
+        cursor.bump();
     }
+    value
 }
@@ -13,21 +13,66 @@ impl<'ast, 'a, 'env, 'res> StructEvaluator<'ast, 'a, 'env, 'res> {
         mut self,
         struct_definition: &'ast StructDeclaration<'a>,
     ) -> Rc<Struct<'ast, 'a>> {
+        self.0.resolving_struct.insert(struct_definition);
         let default_fields = self.resolve_default_fields(&struct_definition.fields);
-        let attributes = struct_definition
+        self.0.resolving_struct.remove(struct_definition);
+        self.build(struct_definition, default_fields)
+    }
+
+    /// Evaluates the struct while its default values are being evaluated,
+    /// leaving them to the instances created by these default values
+    pub fn evaluate_without_defaults(
+        self,
+        struct_definition: &'ast StructDeclaration<'a>,
+    ) -> Rc<Struct<'ast, 'a>> {
+        self.build(struct_definition, HashMap::new())
+    }
+
+    fn build(
+        self,
+        struct_definition: &'ast StructDeclaration<'a>,
+        default_fields: HashMap<&'a str, Value<'ast, 'a>>,
+    ) -> Rc<Struct<'ast, 'a>> {
+        let dependent_fields = struct_definition
+            .dependent_fields()
+            .into_iter()
+            .filter_map(|field| Some((field.name, field.default_value.as_ref()?)))
+            .collect::<Vec<_>>();
+        let field_names = struct_definition
+            .fields
+            .iter()
+            .map(|field| field.name)
+            .collect();
+        let mut attributes = struct_definition
             .body
             .as_ref()
             .map(Self::resolve_attributes)
             .unwrap_or_default();
+        let assertions = struct_definition
+            .body
+            .as_ref()
+            .map(|body| body.assertions.as_slice())
+            .unwrap_or_default();
+        if let Some(inherited) = self.0.env.inherited_attributes.get(struct_definition) {
+            attributes.extend(
+                inherited
+                    .iter()
+                    .map(|attribute| (attribute.name, &attribute.value)),
+            );
+        }
         let module_verifier = ModuleVerifier::with_environment(self.0.env);
-        let package_state = module_verifier
-            .in_render(struct_definition)
-            .then(|| PackageState::Render)
-            .unwrap_or(PackageState::Normal);
+        let package_state = if module_verifier.in_render(struct_definition) {
+            PackageState::Render
+        } else {
+            PackageState::Normal
+        };
         Rc::new(Struct {
             name: struct_definition.name,
+            field_names,
             default_fields,
+            dependent_fields,
             attributes,
+            assertions,
             package_state,
         })
     }
@@ -38,13 +83,14 @@ impl<'ast, 'a, 'env, 'res> StructEvaluator<'ast, 'a, 'env, 'res> {
     ) -> HashMap<&'a str, Value<'ast, 'a>> {
         fields
             .iter()
+            .filter(|field| field.self_dependencies().is_empty())
             .filter_map(|field| {
                 Some((
                     field.name,
                     field
                         .default_value
                         .as_ref()
-                        .map(|expr| self.0.evaluate(expr, None))?,
+                        .map(|expr| self.0.evaluate_declared(expr, None))?,
                 ))
             })
             .collect()
@@ -52,7 +98,7 @@ impl<'ast, 'a, 'env, 'res> StructEvaluator<'ast, 'a, 'env, 'res> {
 
     fn resolve_attributes(
         struct_body: &'ast StructBody<'a>,
-    ) -> HashMap<&'a str, &'ast Expression<'a>> {
+    ) -> Vec<(&'a str, &'ast Expression<'a>)> {
         struct_body
             .attributes
             .iter()
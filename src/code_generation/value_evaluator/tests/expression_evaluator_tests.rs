@@ -1,11 +1,13 @@
 use super::super::expression_evaluator::ExpressionEvaluator;
-use super::super::value::Value;
+use super::super::value::{Color, Length, LengthUnit, Value};
 use super::get_constant;
 use crate::ast::abstract_tree;
 use crate::env::Environment;
 use crate::formula_suppress::FormulaSuppress;
 use crate::parser::parse;
 use crate::tokenizer::tokenize;
+use std::path::Path;
+use std::rc::Rc;
 
 #[test]
 fn test_evaluate_to_instance() {
@@ -110,6 +112,621 @@ fn test_block_returns_void() {
     test_expression(program, Value::Void)
 }
 
+#[test]
+fn test_tuple_literal() {
+    test_expression(
+        "const pair = (1, true)\n",
+        Value::Tuple(vec![Value::Int(1), Value::Bool(true)].into()),
+    )
+}
+
+#[test]
+fn test_tuple_element_access() {
+    let program = r#"
+    const pair = (1, (2, 3))
+    const target = pair.1.0
+    "#;
+    test_expression(program, Value::Int(2))
+}
+
+#[test]
+fn test_destructuring() {
+    let program = r#"
+    const (width, height) = (595, 842)
+    const target = height
+    "#;
+    test_expression(program, Value::Int(842))
+}
+
+#[test]
+fn test_destructuring_field_access() {
+    let program = r#"
+    struct A(val: Int)
+    const (_unused, a) = (1, A(42))
+    const target = a.val
+    "#;
+    test_expression(program, Value::Int(42))
+}
+
+#[test]
+fn test_destructuring_in_block() {
+    let program = r#"
+    const a = {
+        const (first, second) = (4, 2)
+        second
+    }
+    "#;
+    test_expression(program, Value::Int(2))
+}
+
+#[test]
+fn test_int_arithmetic() {
+    test_expression("const a = 40 - 3 + 5\n", Value::Int(42))
+}
+
+#[test]
+fn test_negative_literal_arithmetic() {
+    test_expression("const a = -3 + 10 -2\n", Value::Int(5))
+}
+
+#[test]
+#[should_panic(expected = "<unknown>: Integer overflow in `9223372036854775807 + 1`")]
+fn test_int_addition_overflow() {
+    test_expression("const a = 9223372036854775807 + 1\n", Value::Void)
+}
+
+#[test]
+#[should_panic(expected = "main.ds:2:36: Integer overflow in `-9223372036854775808 - 1`")]
+fn test_int_subtraction_overflow_location() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = "\nconst a = -9223372036854775807 - 1 - 1\n";
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules_from_sources([(Path::new("main.ds"), program)].into_iter())
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None);
+}
+
+#[test]
+fn test_length_arithmetic_converts_to_left_unit() {
+    let expected = Value::Length(Length {
+        value: 1.5,
+        unit: LengthUnit::Inch,
+    });
+    test_expression("const a = 1in + 36pt\n", expected)
+}
+
+#[test]
+fn test_relative_length_arithmetic() {
+    let expected = Value::Length(Length {
+        value: 25.0,
+        unit: LengthUnit::Percent,
+    });
+    test_expression("const a = 50% - 25%\n", expected)
+}
+
+#[test]
+#[should_panic(expected = "Cannot combine relative and absolute lengths: `50%` and `1cm`")]
+fn test_relative_and_absolute_length_arithmetic() {
+    test_expression("const a = 50% + 1cm\n", Value::Void)
+}
+
+#[test]
+fn test_length_arithmetic_exact_between_units() {
+    let length = |value, unit| Value::Length(Length { value, unit });
+    let expected = Value::Tuple(
+        vec![
+            length(0.0, LengthUnit::Millimetre),
+            length(0.0, LengthUnit::Centimetre),
+            length(0.0, LengthUnit::Centimetre),
+        ]
+        .into(),
+    );
+    test_expression(
+        "const a = (10mm - 1cm, 1cm - 10mm, 2.54cm - 1in)\n",
+        expected,
+    )
+}
+
+#[test]
+#[should_panic(
+    expected = "main.ds:2:15: Cannot combine relative and absolute lengths: `50%` and `1pt`"
+)]
+fn test_relative_and_absolute_length_arithmetic_location() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = "\nconst a = 50% + 1pt\n";
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules_from_sources([(Path::new("main.ds"), program)].into_iter())
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None);
+}
+
+#[test]
+#[should_panic(
+    expected = "main.ds:2:15: Cannot compare relative and absolute lengths: `50%` and `1pt`"
+)]
+fn test_relative_and_absolute_length_comparison_location() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = "\nconst a = 50% < 1pt\n";
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules_from_sources([(Path::new("main.ds"), program)].into_iter())
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None);
+}
+
+#[test]
+fn test_interpolated_string() {
+    let program = r#"
+    const TOTAL = 12
+    const a = "Page {-3 + 5} of {TOTAL}: {"done"} {true} {0.5}"
+    "#;
+    test_expression(program, Value::String("Page 2 of 12: done true 0.5".into()))
+}
+
+#[test]
+fn test_index() {
+    let program = r#"
+    const AUTHORS = ["Ada", "Grace", "Edsger"]
+    const a = AUTHORS[1]
+    "#;
+    test_expression(program, Value::String("Grace".into()))
+}
+
+#[test]
+fn test_slice() {
+    let program = r#"
+    const AUTHORS = ["Ada", "Grace", "Edsger"]
+    const a = (AUTHORS[1..], AUTHORS[..1], AUTHORS[1..2])
+    "#;
+    let string = |s: &'static str| Value::String(s.into());
+    let expected = Value::Tuple(
+        vec![
+            Value::Array(vec![string("Grace"), string("Edsger")].into()),
+            Value::Array(vec![string("Ada")].into()),
+            Value::Array(vec![string("Grace")].into()),
+        ]
+        .into(),
+    );
+    test_expression(program, expected)
+}
+
+#[test]
+#[should_panic(expected = "main.ds:4:14: Index 3 is out of bounds for collection of length 3")]
+fn test_computed_index_out_of_bounds_location() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = "\nconst a = {\n    const last = 1 + 2\n    [1, 2, 3][last]\n}\n";
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules_from_sources([(Path::new("main.ds"), program)].into_iter())
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None);
+}
+
+#[test]
+#[should_panic(expected = "<unknown>: Slice 1..4 is out of bounds for collection of length 3")]
+fn test_computed_slice_out_of_bounds() {
+    let program = r#"
+    const a = {
+        const end = 2 + 2
+        [1, 2, 3][1..end]
+    }
+    "#;
+    test_expression(program, Value::Void)
+}
+
+#[test]
+#[should_panic(expected = "Index 3 is out of bounds for collection of length 3")]
+fn test_index_out_of_bounds() {
+    test_expression("const a = [1, 2, 3][3]\n", Value::Void)
+}
+
+#[test]
+#[should_panic(expected = "Slice 2..1 is out of bounds for collection of length 3")]
+fn test_slice_out_of_bounds() {
+    test_expression("const a = [1, 2, 3][2..1]\n", Value::Void)
+}
+
+#[test]
+fn test_collection_methods() {
+    let program = r#"
+    const AUTHORS = ["Ada", "Grace", "Edsger"]
+    const a = (AUTHORS.len(), AUTHORS.first(), AUTHORS.last(), AUTHORS.reverse().join(", "))
+    "#;
+    let expected = Value::Tuple(
+        vec![
+            Value::Int(3),
+            Value::String("Ada".into()),
+            Value::String("Edsger".into()),
+            Value::String("Edsger, Grace, Ada".into()),
+        ]
+        .into(),
+    );
+    test_expression(program, expected)
+}
+
+#[test]
+fn test_index_nested_collection() {
+    let expected = Value::Array(vec![Value::Int(1), Value::Int(2)].into());
+    test_expression("const a = [[1, 2], [3]][0]\n", expected)
+}
+
+#[test]
+fn test_nested_collection_methods() {
+    test_expression(
+        "const a = [[1, 2], [3]].last().first() + 1\n",
+        Value::Int(4),
+    )
+}
+
+#[test]
+fn test_flatten() {
+    let expected = Value::Array(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into());
+    test_expression("const a = [[1, 2], [3]].flatten()\n", expected)
+}
+
+#[test]
+#[should_panic(expected = "Cannot take the first element of an empty collection")]
+fn test_first_of_empty_collection() {
+    let program = r#"
+    const AUTHORS = ["Ada"]
+    const a = AUTHORS[1..].first()
+    "#;
+    test_expression(program, Value::Void)
+}
+
+#[test]
+fn test_string_methods() {
+    let program = r#"
+    const TITLE = "  the art of computer programming "
+    const a = (
+        TITLE.trim().title(),
+        TITLE.upper().trim(),
+        TITLE.trim().len(),
+        TITLE.trim().split(" "),
+        TITLE.replace("computer", "document"),
+        TITLE.trim().starts_with("the"),
+    )
+    "#;
+    let expected = Value::Tuple(
+        vec![
+            Value::String("The Art Of Computer Programming".into()),
+            Value::String("THE ART OF COMPUTER PROGRAMMING".into()),
+            Value::Int(31),
+            Value::Array(
+                ["the", "art", "of", "computer", "programming"]
+                    .map(|word| Value::String(word.into()))
+                    .into(),
+            ),
+            Value::String("  the art of document programming ".into()),
+            Value::Bool(true),
+        ]
+        .into(),
+    );
+    test_expression(program, expected)
+}
+
+#[test]
+fn test_number_methods() {
+    let program = r#"
+    const a = (
+        (-42).abs(),
+        1994.roman(),
+        12.to_string(),
+        2.71828.round(2),
+        2.5.format(3),
+    )
+    "#;
+    let expected = Value::Tuple(
+        vec![
+            Value::Int(42),
+            Value::String("MCMXCIV".into()),
+            Value::String("12".into()),
+            Value::Float(2.72),
+            Value::String("2.500".into()),
+        ]
+        .into(),
+    );
+    test_expression(program, expected)
+}
+
+#[test]
+#[should_panic(expected = "Integer overflow in `-9223372036854775808.abs()`")]
+fn test_int_abs_overflow() {
+    test_expression("const a = (-9223372036854775807 - 1).abs()\n", Value::Void)
+}
+
+#[test]
+#[should_panic(expected = "Only numbers from 1 to 3999 can be written in roman numerals, found 0")]
+fn test_roman_zero() {
+    test_expression("const a = 0.roman()\n", Value::Void)
+}
+
+#[test]
+fn test_color_lighten() {
+    let expected = Value::Color(Color {
+        red: 0x80,
+        green: 0x80,
+        blue: 0x80,
+        alpha: 0xff,
+    });
+    test_expression("const a = #000.lighten(0.5)\n", expected)
+}
+
+#[test]
+fn test_color_lighten_keeps_alpha() {
+    let expected = Value::Color(Color {
+        red: 0x80,
+        green: 0x80,
+        blue: 0x80,
+        alpha: 0x00,
+    });
+    test_expression("const a = #0000.lighten(0.5)\n", expected)
+}
+
+#[test]
+fn test_color_methods_on_constant() {
+    let program = r#"
+    const base = #ffffff
+    const hover = base.darken(0.25).mix(#0000ff)
+    "#;
+    let expected = Value::Color(Color {
+        red: 0x60,
+        green: 0x60,
+        blue: 0xdf,
+        alpha: 0xff,
+    });
+    test_expression(program, expected)
+}
+
+#[test]
+fn test_color_mix_with_weight() {
+    let expected = Value::Color(Color {
+        red: 0x40,
+        green: 0x40,
+        blue: 0xff,
+        alpha: 0xff,
+    });
+    test_expression("const a = #0000ff.mix(#ffffff, 0.75)\n", expected)
+}
+
+#[test]
+fn test_match_value() {
+    let program = r#"
+    const LIMIT = 3
+    const level = 3
+    const a = match level { 1 => "one", LIMIT => "limit", _ => "other" }
+    "#;
+    test_expression(program, Value::String("limit".into()))
+}
+
+#[test]
+fn test_match_struct() {
+    let program = r#"
+    struct Figure(caption: String)
+    struct Table(caption: String, rows: Int)
+    const child = Table("Results", 3)
+    const a = match child { Figure => 0, table: Table => table.rows, _ => -1 }
+    "#;
+    test_expression(program, Value::Int(3))
+}
+
+#[test]
+fn test_match_subject_evaluated_once() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = r#"
+    struct Table(caption: String, rows: Int)
+    const results = Table("Results", 3)
+    const a = match (results.with(rows: 4)) { table: Table => (table, table), _ => (results, results) }
+    "#;
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules(&[vec![]])
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    let instances =
+        match ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None) {
+            Value::Tuple(elements) => elements
+                .iter()
+                .map(|element| match element {
+                    Value::Instance(instance) => Rc::clone(instance),
+                    _ => panic!("Not instance"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Not tuple"),
+        };
+    // the binding refers to the subject evaluated before matching
+    assert!(Rc::ptr_eq(&instances[0], &instances[1]));
+}
+
+#[test]
+fn test_comparison() {
+    test_expression("const a = 1in > 2cm\n", Value::Bool(true));
+    test_expression("const a = 1.5 <= 1.25\n", Value::Bool(false));
+    test_expression("const a = \"left\" != \"right\"\n", Value::Bool(true));
+    test_expression("const a = 10pt == 10pt\n", Value::Bool(true))
+}
+
+#[test]
+fn test_failed_assertions() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = r#"
+    struct Title(text: String) {
+        assert(self.text != "", "Title should not be empty")
+    }
+    assert(1 > 2, "One should be greater than two")
+    const a = Title("")
+    "#;
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules(&[vec![]])
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let mut evaluator = ExpressionEvaluator::with_environment(&env);
+    for assertion in &env.assertions {
+        evaluator.check_assertion(assertion, None);
+    }
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    let instance = match evaluator.evaluate(&constant.value, None) {
+        Value::Instance(instance) => instance,
+        _ => panic!("Not instance"),
+    };
+    // checking the same instance again does not report the assertion twice
+    evaluator.check_instance_assertions(&instance);
+    evaluator.check_instance_assertions(&instance);
+    let messages = evaluator
+        .into_failed_assertions()
+        .into_iter()
+        .map(|failure| failure.message)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "One should be greater than two",
+            "Title should not be empty"
+        ]
+    )
+}
+
+#[test]
+fn test_destructured_value_evaluated_once() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = r#"
+    struct Header(text: String)
+    const (first, second) = (Header("A"), Header("B"))
+    const a = (first, second, first, second)
+    "#;
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules(&[vec![]])
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    let instances =
+        match ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None) {
+            Value::Tuple(elements) => elements
+                .iter()
+                .map(|element| match element {
+                    Value::Instance(instance) => Rc::clone(instance),
+                    _ => panic!("Not instance"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Not tuple"),
+        };
+    // every name bound by the destructuring refers to the same evaluated tuple
+    assert!(Rc::ptr_eq(&instances[0], &instances[2]));
+    assert!(Rc::ptr_eq(&instances[1], &instances[3]));
+}
+
+#[test]
+fn test_memoized_constant_and_attribute() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = r#"
+    struct Header(text: String)
+    const header = Header("Handbook")
+    struct Page(number: Int) {
+        const title = Header("Page {self.number}")
+    }
+    const page = Page(1)
+    const a = (header, header, page.title, page.title, Page(2).title)
+    "#;
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules(&[vec![]])
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    let instances =
+        match ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None) {
+            Value::Tuple(elements) => elements
+                .iter()
+                .map(|element| match element {
+                    Value::Instance(instance) => Rc::clone(instance),
+                    _ => panic!("Not instance"),
+                })
+                .collect::<Vec<_>>(),
+            _ => panic!("Not tuple"),
+        };
+    // constants and attributes of the same instance are evaluated once
+    assert!(Rc::ptr_eq(&instances[0], &instances[1]));
+    assert!(Rc::ptr_eq(&instances[2], &instances[3]));
+    assert!(!Rc::ptr_eq(&instances[3], &instances[4]));
+    assert_eq!(
+        instances[4].field("text"),
+        Some(Value::String("Page 2".into()))
+    );
+}
+
+#[test]
+fn test_copy_shares_unchanged_fields() {
+    let checkers = FormulaSuppress::all();
+    checkers.suppress();
+
+    let program = r#"
+    struct Book(title: String, chapters: [String])
+    const book = Book("Draft", ["Intro", "Body"])
+    const a = (book, book.title("Final"))
+    "#;
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules(&[vec![]])
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+    let constant = get_constant(&syntax_trees[0]).expect("Constant not found");
+    let chapters = match ExpressionEvaluator::with_environment(&env).evaluate(&constant.value, None)
+    {
+        Value::Tuple(elements) => elements
+            .iter()
+            .map(|element| match element {
+                Value::Instance(instance) => instance.field("chapters"),
+                _ => panic!("Not instance"),
+            })
+            .collect::<Vec<_>>(),
+        _ => panic!("Not tuple"),
+    };
+    match chapters.as_slice() {
+        [Some(Value::Array(original)), Some(Value::Array(copied))] => {
+            assert!(Rc::ptr_eq(original, copied))
+        }
+        _ => panic!("Chapters not found"),
+    }
+}
+
 fn test_expression(program: &str, expected: Value) {
     let checkers = FormulaSuppress::all();
     checkers.suppress();
@@ -61,6 +61,213 @@ fn test_access_attr_refers_to_field() {
     )
 }
 
+#[test]
+fn test_access_attr_inherited_from_trait() {
+    test_evaluated_value(
+        r#"
+    trait Titled(title: String) {
+        const heading = self.title
+    }
+    struct Page(title: String)
+    const a = Page("Intro")
+    "#,
+        &["heading"],
+        Value::String("Intro".into()),
+    )
+}
+
+#[test]
+fn test_access_attr_overriding_trait() {
+    test_evaluated_value(
+        r#"
+    trait Titled(title: String) {
+        const heading = self.title
+    }
+    struct Page(title: String) {
+        const heading = "Page"
+    }
+    const a = Page("Intro")
+    "#,
+        &["heading"],
+        Value::String("Page".into()),
+    )
+}
+
+#[test]
+fn test_access_field_copied_from_base() {
+    test_evaluated_value(
+        r#"
+    struct Essay(title: String, year: Int, draft: Bool = true)
+    const base = Essay(title: "Intro", year: 2020, draft: false)
+    const a = Essay(..base, year: 2024)
+    "#,
+        &["title"],
+        Value::String("Intro".into()),
+    );
+}
+
+#[test]
+fn test_access_field_overriding_base() {
+    test_evaluated_value(
+        r#"
+    struct Essay(title: String, year: Int, draft: Bool = true)
+    const base = Essay(title: "Intro", year: 2020, draft: false)
+    const a = Essay(..base, year: 2024)
+    "#,
+        &["year"],
+        Value::Int(2024),
+    );
+}
+
+#[test]
+fn test_access_nested_field_updated() {
+    test_evaluated_value(
+        r#"
+    struct Size(width: Int, height: Int)
+    struct Page(title: String, size: Size)
+    const page = Page("Intro", Size(100, 200))
+    const wide = page.with(size.width: 600, title: "Wide")
+    "#,
+        &["size", "width"],
+        Value::Int(600),
+    );
+}
+
+#[test]
+fn test_access_nested_field_kept() {
+    test_evaluated_value(
+        r#"
+    struct Size(width: Int, height: Int)
+    struct Page(title: String, size: Size)
+    const wide = Page("Intro", Size(100, 200)).with(size.width: 600)
+    "#,
+        &["size", "height"],
+        Value::Int(200),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_field() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    const figure = Figure("A cat")
+    "#,
+        &["alt"],
+        Value::String("A cat".into()),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_field_overridden() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    const figure = Figure("A cat", "Cat")
+    "#,
+        &["alt"],
+        Value::String("Cat".into()),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_other_default() {
+    test_evaluated_value(
+        r#"
+    struct Frame(base: Int, outer: Int = self.inner, inner: Int = self.base)
+    const frame = Frame(base: 3)
+    "#,
+        &["outer"],
+        Value::Int(3),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_field_after_reset() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    const figure = Figure("A cat", "Cat").alt()
+    "#,
+        &["alt"],
+        Value::String("A cat".into()),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_field_after_chaining() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    const figure = Figure("A cat").caption("Dog")
+    "#,
+        &["alt"],
+        Value::String("Dog".into()),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_field_after_spread() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    const cat = Figure("A cat")
+    const figure = Figure(..cat, caption: "Dog")
+    "#,
+        &["alt"],
+        Value::String("Dog".into()),
+    );
+}
+
+#[test]
+fn test_access_default_referring_to_field_after_nested_update() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    struct Page(figure: Figure)
+    const page = Page(Figure("A cat")).with(figure.caption: "Dog")
+    "#,
+        &["figure", "alt"],
+        Value::String("Dog".into()),
+    );
+}
+
+#[test]
+fn test_access_explicit_field_kept_after_chaining() {
+    test_evaluated_value(
+        r#"
+    struct Figure(caption: String, alt: String = self.caption)
+    const figure = Figure("A cat").alt("Cat").caption("Dog")
+    "#,
+        &["alt"],
+        Value::String("Cat".into()),
+    );
+}
+
+#[test]
+fn test_access_recursive_struct() {
+    test_evaluated_value(
+        r#"
+    struct Section(title: String, subsections: [Section] = [])
+    const outline = Section("Guide", [Section("Install"), Section("Usage", [Section("CLI")])])
+    "#,
+        &["title"],
+        Value::String("Guide".into()),
+    );
+}
+
+#[test]
+fn test_access_default_creating_own_struct() {
+    test_evaluated_value(
+        r#"
+    struct Section(title: String, subsections: [Section] = [Section("Empty", [])])
+    const outline = Section("Guide")
+    "#,
+        &["title"],
+        Value::String("Guide".into()),
+    );
+}
+
 fn test_evaluated_value(program: &str, accesses: &[&str], expected: Value) {
     let checkers = FormulaSuppress::all();
     checkers.suppress();
@@ -70,6 +277,7 @@ fn test_evaluated_value(program: &str, accesses: &[&str], expected: Value) {
         .add_modules(&[vec![]])
         .generate_scopes(&mut syntax_trees)
         .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
         .build();
     let mut expr_evaluator = ExpressionEvaluator::with_environment(&env);
     let instance_access_evaluator = InstanceAccessEvaluator::new(&mut expr_evaluator, None);
@@ -1,5 +1,5 @@
 use super::super::literal_evaluator::evaluate;
-use super::super::value::Value;
+use super::super::value::{Color, Length, LengthUnit, Value};
 use crate::tokenizer::LiteralKind;
 use quickcheck_macros::quickcheck;
 use std::str::FromStr;
@@ -33,11 +33,39 @@ fn test_resolve_hex() {
     assert_eq!(actual, expected)
 }
 
+#[test]
+fn test_resolve_octal() {
+    let actual = evaluate(&LiteralKind::Octal, "0o755");
+    let expected = Value::Int(493);
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_resolve_negative_integer_with_underscores() {
+    let actual = evaluate(&LiteralKind::Integer, "-1_000_000");
+    let expected = Value::Int(-1_000_000);
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_float_precision() {
+    let actual = evaluate(&LiteralKind::Floating, "12345678.91");
+    let expected = Value::Float(12345678.91);
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_float_exponent() {
+    let actual = evaluate(&LiteralKind::Floating, "-1.5e-3");
+    let expected = Value::Float(-0.0015);
+    assert_eq!(actual, expected)
+}
+
 #[quickcheck]
 fn test_float(num: u16) -> bool {
     let string = format!("{num}.{num}", num = num);
     let actual = evaluate(&LiteralKind::Floating, &string);
-    let expected = Value::Float(f32::from_str(&string).expect("Float failed to parse"));
+    let expected = Value::Float(f64::from_str(&string).expect("Float failed to parse"));
     expected == actual
 }
 
@@ -54,3 +82,47 @@ fn test_boolean_false() {
     let expected = Value::Bool(false);
     assert_eq!(actual, expected)
 }
+
+#[test]
+fn test_length() {
+    let actual = evaluate(&LiteralKind::Length, "2.5cm");
+    let expected = Value::Length(Length {
+        value: 2.5,
+        unit: LengthUnit::Centimetre,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_percentage() {
+    let actual = evaluate(&LiteralKind::Length, "50%");
+    let expected = Value::Length(Length {
+        value: 50.0,
+        unit: LengthUnit::Percent,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_color() {
+    let actual = evaluate(&LiteralKind::Color, "#1e90ff");
+    let expected = Value::Color(Color {
+        red: 0x1e,
+        green: 0x90,
+        blue: 0xff,
+        alpha: 0xff,
+    });
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_short_color_with_alpha() {
+    let actual = evaluate(&LiteralKind::Color, "#f808");
+    let expected = Value::Color(Color {
+        red: 0xff,
+        green: 0x88,
+        blue: 0x00,
+        alpha: 0x88,
+    });
+    assert_eq!(actual, expected)
+}
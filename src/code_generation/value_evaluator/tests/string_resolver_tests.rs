@@ -1,4 +1,4 @@
-use super::super::string_evaluator::evaluate;
+use super::super::string_evaluator::{evaluate, evaluate_part};
 use std::borrow::Cow;
 
 #[test]
@@ -16,6 +16,13 @@ fn test_simple_string_with_escape() {
     assert_eq!(actual, expected)
 }
 
+#[test]
+fn test_simple_string_with_char_code_escape() {
+    let actual = evaluate(r#""\x41BC\0\u{a0}\u{2014}\u{1F600}""#);
+    let expected = "ABC\0\u{a0}\u{2014}\u{1F600}";
+    assert_eq!(actual, expected)
+}
+
 #[test]
 fn test_simple_string_escape_spaces() {
     let actual = evaluate(r#""hello \   world""#);
@@ -61,3 +68,33 @@ fn test_raw_string_multiple_lines() {
     let expected = "can still\n        go multiple\n        lines\n        ";
     assert_eq!(actual, expected)
 }
+
+#[test]
+fn test_interpolated_string_part() {
+    let actual = evaluate_part(r#"} of \{{"#);
+    let expected = " of {";
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_text_block_strips_indentation() {
+    let actual = evaluate(
+        "\"\"\"\r\n        first\r\n\r\n          indented\r\n        last\r\n        \"\"\"",
+    );
+    let expected = "first\n\n  indented\nlast";
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_text_block_closing_quotes_set_indentation() {
+    let actual = evaluate("\"\"\"\n        {braces}\n      \"\"\"");
+    let expected = "  {braces}";
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_text_block_closing_quotes_on_last_line() {
+    let actual = evaluate("\"\"\"\n    a \\\"quote\\\"\n    and continued \\\n    line\"\"\"");
+    let expected = "a \"quote\"\nand continued line";
+    assert_eq!(actual, expected)
+}
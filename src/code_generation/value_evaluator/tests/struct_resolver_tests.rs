@@ -69,12 +69,16 @@ fn test_resolve_struct_with_default_fields_and_attributes() {
     assert_eq!(structure.default_fields, expected_fields);
     let expected_attribute_keys = vec!["attr"];
     assert_eq!(
-        structure.attributes.keys().copied().collect::<Vec<_>>(),
+        structure
+            .attributes
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>(),
         expected_attribute_keys
     );
 }
 
-fn get_struct(program: &str) -> Option<StructDeclaration> {
+fn get_struct(program: &str) -> Option<StructDeclaration<'_>> {
     let mut syntax_tree = abstract_tree(parse(tokenize(program)));
     syntax_tree
         .compilation_unit
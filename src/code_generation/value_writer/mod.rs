@@ -16,15 +16,17 @@ type Result<T, E = Error> = std::result::Result<T, E>;
 const RENDERED: &str = "rendered";
 const RENDER_TAG: &str = "__RENDER_TAG";
 
-pub fn write<'ast, 'a, Out: Output>(
-    evaluator: ExpressionEvaluator<'ast, 'a, '_>,
+/// Writes the value, and gives back the evaluator holding the failed assertions
+pub fn write<'ast, 'a, 'env, Out: Output>(
+    evaluator: ExpressionEvaluator<'ast, 'a, 'env>,
     value: Value<'ast, 'a>,
     output: &mut Out,
-) {
+) -> ExpressionEvaluator<'ast, 'a, 'env> {
     let mut formatter = ValueFormatter::new(evaluator, output);
     if let Err(error @ Error::IoError(_)) = formatter.format(&value) {
         panic!("{}", error)
     }
+    formatter.evaluator
 }
 
 struct ValueFormatter<'ast, 'a, 'env, 'out, Out: Output> {
@@ -41,19 +43,27 @@ impl<'ast, 'a, 'env, 'out, Out: Output> ValueFormatter<'ast, 'a, 'env, 'out, Out
         match value {
             Value::Int(int) => write!(self.output, "{int}").map_err(Error::from),
             Value::Float(float) => write!(self.output, "{float}").map_err(Error::from),
+            Value::Length(length) => write!(self.output, "{length}").map_err(Error::from),
+            Value::Color(color) => write!(self.output, "{color}").map_err(Error::from),
             Value::Bool(bool) => write!(self.output, "{bool}").map_err(Error::from),
             Value::String(string) => write!(self.output, "{string:?}").map_err(Error::from),
             Value::Void => Err(Error::EmptyContent),
-            Value::Array(values) => self.format_array(values),
+            Value::Array(values) => self.format_sequence(values, "[", "]"),
+            Value::Tuple(values) => self.format_sequence(values, "(", ")"),
             Value::Instance(instance) => self.format_instance(instance),
         }
     }
 
-    fn format_array(&mut self, array: &[Value<'ast, 'a>]) -> Result<()> {
+    fn format_sequence(
+        &mut self,
+        values: &[Value<'ast, 'a>],
+        open: &str,
+        close: &str,
+    ) -> Result<()> {
         let init_len = self.output.position();
-        write!(self.output, "[")?;
+        write!(self.output, "{open}")?;
         let pre_format_len = self.output.position();
-        for value in array {
+        for value in values {
             match self.format(value) {
                 Err(Error::EmptyContent) => (),
                 error @ Err(_) => error?,
@@ -64,11 +74,13 @@ impl<'ast, 'a, 'env, 'out, Out: Output> ValueFormatter<'ast, 'a, 'env, 'out, Out
             self.output.truncate(init_len);
             Err(Error::EmptyContent)
         } else {
-            Ok(write!(self.output, "]")?)
+            Ok(write!(self.output, "{close}")?)
         }
     }
 
     fn format_instance(&mut self, instance: &Rc<Instance<'ast, 'a>>) -> Result<()> {
+        // only the instances making up the document are checked
+        self.evaluator.check_instance_assertions(instance);
         match instance.structure.package_state {
             PackageState::Render => self.format_render_instance(instance),
             PackageState::Normal => self.format_normal_instance(instance),
@@ -87,7 +99,7 @@ impl<'ast, 'a, 'env, 'out, Out: Output> ValueFormatter<'ast, 'a, 'env, 'out, Out
         let fields_attrs = instance
             .fields()
             .into_iter()
-            .chain(instance.attributes(&mut self.evaluator).into_iter())
+            .chain(instance.attributes(&mut self.evaluator))
             .filter(|(name, _)| *name != RENDER_TAG);
         for (name, value) in fields_attrs {
             match self.format_key_value(name, &value) {
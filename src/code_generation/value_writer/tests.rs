@@ -1,8 +1,9 @@
-use super::super::value::{Instance, PackageState, Struct, Value};
+use super::super::value::{
+    Color, DerivedFields, Fields, Instance, Length, LengthUnit, PackageState, Struct, Value,
+};
 use super::super::value_evaluator::ExpressionEvaluator;
 use super::RENDER_TAG;
 use crate::env::Environment;
-use std::collections::HashMap;
 use std::rc::Rc;
 
 fn evaluator<'ast, 'a, 'env>(
@@ -41,21 +42,83 @@ fn test_primitive_values() {
 
 #[test]
 fn test_primitive_arrays() {
-    let primitive_array = Value::Array(vec![
-        Value::Int(42),
-        Value::Float(10.48),
-        Value::Void,
-        Value::Bool(true),
-        Value::String("Hello World".into()),
-    ]);
+    let primitive_array = Value::Array(
+        vec![
+            Value::Int(42),
+            Value::Float(10.48),
+            Value::Void,
+            Value::Bool(true),
+            Value::String("Hello World".into()),
+        ]
+        .into(),
+    );
     let expected = r#"[42,10.48,true,"Hello World",]"#;
     let env = Environment::default();
     assert_eq!(write_to_string(evaluator(&env), primitive_array), expected)
 }
 
+#[test]
+fn test_tuples() {
+    let tuple = Value::Tuple(
+        vec![
+            Value::Int(595),
+            Value::String("A4".into()),
+            Value::Array(vec![Value::Bool(false)].into()),
+        ]
+        .into(),
+    );
+    let expected = r#"(595,"A4",[false,],)"#;
+    let env = Environment::default();
+    assert_eq!(write_to_string(evaluator(&env), tuple), expected)
+}
+
+#[test]
+fn test_lengths() {
+    let lengths = Value::Array(
+        vec![
+            Value::Length(Length {
+                value: 2.5,
+                unit: LengthUnit::Centimetre,
+            }),
+            Value::Length(Length {
+                value: 50.0,
+                unit: LengthUnit::Percent,
+            }),
+        ]
+        .into(),
+    );
+    let expected = "[2.5cm,50%,]";
+    let env = Environment::default();
+    assert_eq!(write_to_string(evaluator(&env), lengths), expected)
+}
+
+#[test]
+fn test_colors() {
+    let colors = Value::Array(
+        vec![
+            Value::Color(Color {
+                red: 0x1e,
+                green: 0x90,
+                blue: 0xff,
+                alpha: 0xff,
+            }),
+            Value::Color(Color {
+                red: 0,
+                green: 0,
+                blue: 0,
+                alpha: 0x80,
+            }),
+        ]
+        .into(),
+    );
+    let expected = "[#1e90ff,#00000080,]";
+    let env = Environment::default();
+    assert_eq!(write_to_string(evaluator(&env), colors), expected)
+}
+
 #[test]
 fn test_empty_arrays() {
-    let array = Value::Array(vec![Value::Void, Value::Void]);
+    let array = Value::Array(vec![Value::Void, Value::Void].into());
     let expected = "";
     let env = Environment::default();
     assert_eq!(write_to_string(evaluator(&env), array), expected)
@@ -73,13 +136,17 @@ fn test_empty_array_nested() {
     ]
     .into_iter()
     .collect();
-    let array = Value::Array(vec![
-        Value::Void,
-        Value::Instance(Rc::new(Instance {
-            structure: Rc::new(structure),
-            fields,
-        })),
-    ]);
+    let array = Value::Array(
+        vec![
+            Value::Void,
+            Value::Instance(Rc::new(Instance {
+                structure: Rc::new(structure),
+                fields,
+                derived_fields: DerivedFields::new(),
+            })),
+        ]
+        .into(),
+    );
     let expected = "";
     let env = Environment::default();
     assert_eq!(write_to_string(evaluator(&env), array), expected)
@@ -108,6 +175,7 @@ fn test_instance_render_element_with_default_fields() {
         .collect();
     let structure = Struct {
         package_state: PackageState::Render,
+        field_names: vec!["font", "size"],
         default_fields,
         ..Default::default()
     };
@@ -121,6 +189,26 @@ fn test_instance_render_element_with_default_fields() {
     test_instance_to_value(structure, fields, expected)
 }
 
+#[test]
+fn test_instance_render_fields_in_declaration_order() {
+    let structure = Struct {
+        package_state: PackageState::Render,
+        field_names: vec!["width", "height", "align"],
+        ..Default::default()
+    };
+    let fields = [
+        ("children", Value::Array(vec![Value::Int(1)].into())),
+        ("align", Value::String("left".into())),
+        ("height", Value::Int(2)),
+        ("width", Value::Int(3)),
+        (RENDER_TAG, Value::String("Tag".into())),
+    ]
+    .into_iter()
+    .collect();
+    let expected = r#"Tag: {width: 3,height: 2,align: "left",children: [1,],}"#;
+    test_instance_to_value(structure, fields, expected)
+}
+
 #[test]
 fn test_instance_render_element_with_void_field() {
     let structure = Struct {
@@ -160,6 +248,7 @@ fn test_instance_normal() {
     let end_point = Value::Instance(Rc::new(Instance {
         structure: Rc::new(end_structure),
         fields: [("size", Value::Int(42))].into_iter().collect(),
+        derived_fields: DerivedFields::new(),
     }));
 
     let fields = [("rendered", end_point), ("irrelevant", Value::Float(3.0))]
@@ -193,7 +282,10 @@ fn test_nested_empty_instance() {
     };
     let fields = [
         ("size", Value::Void),
-        ("content", Value::Array(vec![Value::Void, Value::Void])),
+        (
+            "content",
+            Value::Array(vec![Value::Void, Value::Void].into()),
+        ),
         (RENDER_TAG, Value::String("Tag".into())),
     ]
     .into_iter()
@@ -202,12 +294,13 @@ fn test_nested_empty_instance() {
     test_instance_to_value(structure, fields, expected)
 }
 
-fn test_instance_to_value(structure: Struct, fields: HashMap<&str, Value>, expected: &str) {
+fn test_instance_to_value(structure: Struct, fields: Fields, expected: &str) {
     let env = Environment::default();
     let evaluator = evaluator(&env);
     let value = Value::Instance(Rc::new(Instance {
         structure: Rc::new(structure),
         fields,
+        derived_fields: DerivedFields::new(),
     }));
     let actual = write_to_string(evaluator, value);
     assert_eq!(actual, expected)
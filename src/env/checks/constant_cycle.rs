@@ -0,0 +1,385 @@
+use super::type_checking::{trait_hierarchy, type_resolver};
+use super::{Error, Types};
+use crate::ast::{
+    AbstractSyntaxTree, ConstantDeclaration, Declaration, DestructuringDeclaration, Expression,
+    Name, Parameter, Pattern, Statement, StringPart, StructDeclaration, TraitDeclaration,
+};
+use crate::env::{Environment, Resolved};
+use std::collections::{HashMap, HashSet};
+
+/// A declaration whose value is evaluated when it is referred to
+#[derive(Clone, Copy)]
+enum Dependency<'ast, 'a> {
+    Constant(&'ast ConstantDeclaration<'a>),
+    Destructuring(&'ast DestructuringDeclaration<'a>),
+}
+
+/// Struct or trait declaring an attribute, where `self` in the attribute refers to the instance
+#[derive(Clone, Copy)]
+enum Owner<'ast, 'a> {
+    Struct(&'ast StructDeclaration<'a>),
+    Trait(&'ast TraitDeclaration<'a>),
+}
+
+/// Checks that no constant, destructuring or attribute depends on itself,
+/// either directly or through other declarations
+pub struct ConstantCycleChecker<'ast, 'a, 'env> {
+    owners: HashMap<*const ConstantDeclaration<'a>, Owner<'ast, 'a>>,
+    checked: HashSet<*const ()>,
+    environment: &'env Environment<'ast, 'a>,
+}
+
+impl<'ast, 'a> Dependency<'ast, 'a> {
+    fn address(self) -> *const () {
+        match self {
+            Dependency::Constant(constant) => constant as *const ConstantDeclaration as *const (),
+            Dependency::Destructuring(destructuring) => {
+                destructuring as *const DestructuringDeclaration as *const ()
+            }
+        }
+    }
+
+    fn value(self) -> &'ast Expression<'a> {
+        match self {
+            Dependency::Constant(constant) => &constant.value,
+            Dependency::Destructuring(destructuring) => &destructuring.value,
+        }
+    }
+}
+
+impl<'ast, 'a> Owner<'ast, 'a> {
+    fn name(self) -> &'a str {
+        match self {
+            Owner::Struct(r#struct) => r#struct.name,
+            Owner::Trait(r#trait) => r#trait.name,
+        }
+    }
+}
+
+impl<'ast, 'a, 'env> ConstantCycleChecker<'ast, 'a, 'env> {
+    pub fn with_environment(environment: &'env Environment<'ast, 'a>) -> Self {
+        Self {
+            owners: HashMap::new(),
+            checked: HashSet::new(),
+            environment,
+        }
+    }
+
+    pub fn check(&mut self, syntax_trees: &'ast [AbstractSyntaxTree<'a>]) {
+        let declarations = syntax_trees
+            .iter()
+            .flat_map(|syntax_tree| &syntax_tree.compilation_unit.declarations);
+        let mut dependencies = vec![];
+        for declaration in declarations {
+            let (owner, body) = match declaration {
+                Declaration::Constant(constant) => {
+                    dependencies.push(Dependency::Constant(constant));
+                    continue;
+                }
+                Declaration::Destructuring(destructuring) => {
+                    dependencies.push(Dependency::Destructuring(destructuring));
+                    continue;
+                }
+                Declaration::Struct(r#struct) => (Owner::Struct(r#struct), &r#struct.body),
+                Declaration::Trait(r#trait) => (Owner::Trait(r#trait), &r#trait.body),
+                _ => continue,
+            };
+            for attribute in body.iter().flat_map(|body| &body.attributes) {
+                self.owners.insert(attribute, owner);
+                dependencies.push(Dependency::Constant(attribute));
+            }
+        }
+        for dependency in dependencies {
+            self.visit(dependency, &mut vec![]);
+        }
+    }
+
+    fn visit(&mut self, dependency: Dependency<'ast, 'a>, path: &mut Vec<Dependency<'ast, 'a>>) {
+        if self.checked.contains(&dependency.address()) {
+            return;
+        }
+        if let Some(start) = path
+            .iter()
+            .position(|visiting| visiting.address() == dependency.address())
+        {
+            let cycle = path[start..]
+                .iter()
+                .chain([&dependency])
+                .map(|dependency| self.describe(*dependency))
+                .collect::<Vec<_>>();
+            panic!("{}", Error::ConstantCycle(cycle.join(" -> ")))
+        }
+        let owner = match dependency {
+            Dependency::Constant(constant) => self.owners.get(&(constant as *const _)).copied(),
+            Dependency::Destructuring(_) => None,
+        };
+        let mut dependencies = vec![];
+        self.collect_dependencies(dependency.value(), owner, &mut dependencies);
+        path.push(dependency);
+        for next in dependencies {
+            self.visit(next, path);
+        }
+        path.pop();
+        self.checked.insert(dependency.address());
+    }
+
+    /// Collects the declarations evaluated along with the expression
+    fn collect_dependencies(
+        &self,
+        expression: &'ast Expression<'a>,
+        owner: Option<Owner<'ast, 'a>>,
+        dependencies: &mut Vec<Dependency<'ast, 'a>>,
+    ) {
+        match expression {
+            Expression::ConstUse(name) => self.refers_to(name, dependencies),
+            Expression::FieldAccess {
+                receiver,
+                field_names,
+            } => {
+                self.collect_dependencies(receiver, owner, dependencies);
+                let accessed = self.owner_of(receiver, owner, &mut vec![]);
+                self.collect_accessed(accessed, field_names, dependencies)
+            }
+            Expression::ChainingMethodInvocation {
+                receiver,
+                accessors,
+            } => {
+                self.collect_dependencies(receiver, owner, dependencies);
+                for value in accessors
+                    .iter()
+                    .filter_map(|accessor| accessor.value.as_ref())
+                {
+                    self.collect_dependencies(value, owner, dependencies)
+                }
+            }
+            Expression::StructInit {
+                parameters,
+                init_content,
+                ..
+            } => {
+                for parameter in parameters {
+                    self.collect_dependencies(Parameter::expression(parameter), owner, dependencies)
+                }
+                for expression in init_content.iter().flat_map(|content| &content.0) {
+                    self.collect_dependencies(expression, owner, dependencies)
+                }
+            }
+            Expression::Block(block) => {
+                for statement in &block.statements {
+                    match statement {
+                        Statement::Expression(expression) => {
+                            self.collect_dependencies(expression, owner, dependencies)
+                        }
+                        Statement::ConstantDeclaration(constant) => {
+                            self.collect_dependencies(&constant.value, owner, dependencies)
+                        }
+                        Statement::Destructuring(destructuring) => {
+                            self.collect_dependencies(&destructuring.value, owner, dependencies)
+                        }
+                    }
+                }
+            }
+            Expression::Collection(elements) | Expression::Tuple(elements) => {
+                for element in elements {
+                    self.collect_dependencies(element, owner, dependencies)
+                }
+            }
+            Expression::Arithmetic { left, right, .. }
+            | Expression::Comparison { left, right, .. } => {
+                self.collect_dependencies(left, owner, dependencies);
+                self.collect_dependencies(right, owner, dependencies);
+            }
+            Expression::InterpolatedString(parts) => {
+                for expression in parts.iter().filter_map(StringPart::expression) {
+                    self.collect_dependencies(expression, owner, dependencies)
+                }
+            }
+            Expression::Index {
+                receiver, index, ..
+            } => {
+                self.collect_dependencies(receiver, owner, dependencies);
+                self.collect_dependencies(index, owner, dependencies);
+            }
+            Expression::Slice {
+                receiver,
+                start,
+                end,
+                ..
+            } => {
+                self.collect_dependencies(receiver, owner, dependencies);
+                for bound in start.iter().chain(end) {
+                    self.collect_dependencies(bound, owner, dependencies)
+                }
+            }
+            Expression::Match(r#match) => {
+                self.collect_dependencies(&r#match.subject, owner, dependencies);
+                for arm in &r#match.arms {
+                    if let Pattern::Name(name) = &arm.pattern {
+                        self.refers_to(name, dependencies)
+                    }
+                    self.collect_dependencies(&arm.value, owner, dependencies)
+                }
+            }
+            // the instance as a whole does not evaluate its attributes
+            Expression::SelfRef(_) | Expression::Literal { .. } | Expression::Void => (),
+        }
+    }
+
+    fn refers_to(&self, name: &Name<'a>, dependencies: &mut Vec<Dependency<'ast, 'a>>) {
+        match self.environment.resolved_names.get(name) {
+            Some(Resolved::Constant(constant)) => dependencies.push(Dependency::Constant(constant)),
+            Some(Resolved::InstanceAccess(constant, field_names)) => {
+                dependencies.push(Dependency::Constant(constant));
+                let accessed = self.owner_of(&constant.value, None, &mut vec![constant]);
+                self.collect_accessed(accessed, field_names, dependencies)
+            }
+            Some(
+                Resolved::Binding(destructuring, _) | Resolved::BindingAccess(destructuring, ..),
+            ) => dependencies.push(Dependency::Destructuring(destructuring)),
+            _ => (),
+        }
+    }
+
+    /// Collects the attributes evaluated by accessing the names in turn from an instance of the
+    /// owner, until a name does not lead to a struct or a trait
+    fn collect_accessed(
+        &self,
+        mut accessed: Option<Owner<'ast, 'a>>,
+        field_names: &[&'a str],
+        dependencies: &mut Vec<Dependency<'ast, 'a>>,
+    ) {
+        for name in field_names {
+            let Some(owner) = accessed else {
+                return;
+            };
+            let attribute = self.attribute(owner, name);
+            dependencies.extend(attribute.map(Dependency::Constant));
+            accessed = match attribute {
+                Some(attribute) => self.owner_of(&attribute.value, Some(owner), &mut vec![]),
+                None => self.field_owner(owner, name),
+            };
+        }
+    }
+
+    /// Finds the struct or trait the expression is an instance of when it is known without type
+    /// checking, where `visiting` holds the constants being looked through to stop at cycles
+    fn owner_of(
+        &self,
+        expression: &'ast Expression<'a>,
+        owner: Option<Owner<'ast, 'a>>,
+        visiting: &mut Vec<&'ast ConstantDeclaration<'a>>,
+    ) -> Option<Owner<'ast, 'a>> {
+        match expression {
+            Expression::SelfRef(_) => owner,
+            Expression::StructInit { name, .. } => {
+                match self.environment.resolved_names.get(name)? {
+                    Resolved::Struct(r#struct) => Some(Owner::Struct(r#struct)),
+                    _ => None,
+                }
+            }
+            Expression::ConstUse(name) => {
+                let (constant, field_names) = match self.environment.resolved_names.get(name)? {
+                    Resolved::Constant(constant) => (*constant, &[][..]),
+                    Resolved::InstanceAccess(constant, field_names) => {
+                        (*constant, field_names.as_slice())
+                    }
+                    _ => return None,
+                };
+                if visiting
+                    .iter()
+                    .any(|visited| std::ptr::eq(*visited, constant))
+                {
+                    return None;
+                }
+                visiting.push(constant);
+                let instance = self.owner_of(&constant.value, None, visiting);
+                self.access_owner(instance, field_names, visiting)
+            }
+            Expression::FieldAccess {
+                receiver,
+                field_names,
+            } => {
+                let instance = self.owner_of(receiver, owner, visiting);
+                self.access_owner(instance, field_names, visiting)
+            }
+            _ => None,
+        }
+    }
+
+    /// Finds the struct or trait reached by accessing the names in turn from an instance
+    fn access_owner(
+        &self,
+        mut accessed: Option<Owner<'ast, 'a>>,
+        field_names: &[&'a str],
+        visiting: &mut Vec<&'ast ConstantDeclaration<'a>>,
+    ) -> Option<Owner<'ast, 'a>> {
+        for name in field_names {
+            let owner = accessed?;
+            accessed = match self.attribute(owner, name) {
+                Some(attribute) => {
+                    if visiting
+                        .iter()
+                        .any(|visited| std::ptr::eq(*visited, attribute))
+                    {
+                        return None;
+                    }
+                    visiting.push(attribute);
+                    self.owner_of(&attribute.value, Some(owner), visiting)
+                }
+                None => self.field_owner(owner, name),
+            };
+        }
+        accessed
+    }
+
+    /// Finds the struct or trait a field is declared with
+    fn field_owner(&self, owner: Owner<'ast, 'a>, name: &str) -> Option<Owner<'ast, 'a>> {
+        let field = match owner {
+            Owner::Struct(r#struct) => r#struct.fields.iter().find(|field| field.name == name),
+            Owner::Trait(r#trait) => trait_hierarchy::required_fields(self.environment, r#trait)
+                .into_iter()
+                .find(|field| field.name == name),
+        }?;
+        match type_resolver::resolve_type(self.environment, &field.field_type)? {
+            Types::Struct(r#struct) => Some(Owner::Struct(r#struct)),
+            Types::Trait(r#trait) => Some(Owner::Trait(r#trait)),
+            _ => None,
+        }
+    }
+
+    /// Finds the attribute accessed from an instance of the owner, fields do not depend on anything
+    fn attribute(
+        &self,
+        owner: Owner<'ast, 'a>,
+        name: &str,
+    ) -> Option<&'ast ConstantDeclaration<'a>> {
+        match owner {
+            Owner::Struct(r#struct) => r#struct
+                .body
+                .iter()
+                .flat_map(|body| &body.attributes)
+                .find(|attribute| attribute.name == name),
+            Owner::Trait(r#trait) => trait_hierarchy::attributes(self.environment, r#trait)
+                .into_iter()
+                .find(|attribute| attribute.name == name),
+        }
+    }
+
+    /// Formats the dependency as its name followed by where it is declared, if known
+    fn describe(&self, dependency: Dependency<'ast, 'a>) -> String {
+        let (name, lexeme) = match dependency {
+            Dependency::Constant(constant) => match self.owners.get(&(constant as *const _)) {
+                Some(owner) => (format!("{}.{}", owner.name(), constant.name), constant.name),
+                None => (constant.name.to_owned(), constant.name),
+            },
+            Dependency::Destructuring(destructuring) => (
+                format!("({})", destructuring.names.join(", ")),
+                destructuring.names[0],
+            ),
+        };
+        match self.environment.sources.locate(lexeme) {
+            Some(location) => format!("`{name}` at {location}"),
+            None => format!("`{name}`"),
+        }
+    }
+}
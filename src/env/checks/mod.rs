@@ -1,3 +1,4 @@
+mod constant_cycle;
 mod struct_hierarchy;
 #[cfg(test)]
 mod tests;
@@ -7,7 +8,9 @@ use super::address_hash::hash;
 use type_checking::types::Types;
 
 use crate::ast::AbstractSyntaxTree;
+use crate::env::environment::InheritedAttributes;
 use crate::env::Environment;
+use constant_cycle::ConstantCycleChecker;
 use struct_hierarchy::StructHierarchyChecker;
 use type_checking::TypeChecker;
 
@@ -15,12 +18,16 @@ use type_checking::TypeChecker;
 pub enum Error {
     #[error("Struct cycle dependency detected at {0}")]
     StructCycleDependency(String),
+    #[error("Constant cycle detected: {0}")]
+    ConstantCycle(String),
 }
 
+/// Validates the syntax trees, and returns the attributes structs inherit from traits
 pub fn check<'ast, 'a>(
     environment: &Environment<'ast, 'a>,
-    syntax_trees: &[AbstractSyntaxTree<'a>],
-) {
+    syntax_trees: &'ast [AbstractSyntaxTree<'a>],
+) -> InheritedAttributes<'ast, 'a> {
+    ConstantCycleChecker::with_environment(environment).check(syntax_trees);
     let mut type_checker = TypeChecker::with_environment(environment);
     #[cfg(test)]
     if !crate::formula_suppress::FormulaSuppress::entry_check_suppressed() {
@@ -28,6 +35,7 @@ pub fn check<'ast, 'a>(
     }
     #[cfg(not(test))]
     type_checker.entry_check();
-    type_checker.check(syntax_trees);
+    let inherited_attributes = type_checker.check(syntax_trees);
     StructHierarchyChecker::with_environment(environment).check(syntax_trees);
+    inherited_attributes
 }
@@ -49,23 +49,36 @@ impl<'ast, 'a, 'env> StructHierarchyChecker<'ast, 'a, 'env> {
             Ok(())
         } else {
             for field in &declaration.fields {
-                match type_resolver::resolve_type_name(
-                    self.environment,
-                    &field.field_type.name,
-                    field.field_type.is_collection,
-                ) {
-                    Some(Types::Struct(struct_declaration)) => {
-                        self.recursively_check(struct_declaration, white_list)?;
-                    }
-                    Some(_primitive_types) => (),
-                    None => panic!("Name `{}` cannot be resolved", field.field_type.name),
-                }
+                let field_type = type_resolver::resolve_type(self.environment, &field.field_type)
+                    .unwrap_or_else(|| panic!("Type `{}` cannot be resolved", field.field_type));
+                self.check_type(&field_type, white_list)?;
             }
             self.declaring.remove(&declaration);
             white_list.insert(declaration);
             Ok(())
         }
     }
+
+    fn check_type(
+        &mut self,
+        field_type: &Types<'ast, 'a>,
+        white_list: &mut HashSet<&'ast StructDeclaration<'a>>,
+    ) -> Result<(), Error> {
+        match field_type {
+            Types::Struct(struct_declaration) => {
+                self.recursively_check(struct_declaration, white_list)
+            }
+            Types::Tuple(elements) => elements
+                .iter()
+                .try_for_each(|element| self.check_type(element, white_list)),
+            // collections can be empty, so recursive structs can still be created through them
+            Types::PrimitiveCollection(_)
+            | Types::StructCollection(_)
+            | Types::TraitCollection(_)
+            | Types::NestedCollection(_) => Ok(()),
+            Types::Primitive(_) | Types::Trait(_) => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
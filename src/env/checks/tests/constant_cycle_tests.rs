@@ -0,0 +1,134 @@
+use crate::ast::abstract_tree;
+use crate::formula_suppress::FormulaSuppress;
+use crate::parser::parse;
+use crate::tokenizer::tokenize;
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `A` -> `B` -> `A`")]
+fn test_mutually_recursive_constants() {
+    test_constant_cycle_check(
+        r#"
+    const A = B
+    const B = A
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `A` -> `A`")]
+fn test_constant_refers_to_itself() {
+    test_constant_cycle_check(
+        r#"
+    const A = { const b = A + 1
+        b }
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `(A, B)` -> `C` -> `(A, B)`")]
+fn test_cycle_through_destructuring() {
+    test_constant_cycle_check(
+        r#"
+    const (A, B) = (C, 1)
+    const C = B + 1
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `Page.first` -> `Page.second` -> `Page.first`")]
+fn test_attributes_depend_on_each_other_through_self() {
+    test_constant_cycle_check(
+        r#"
+    struct Page(number: Int) {
+        const first = self.second + 1
+        const second = self.first + self.number
+    }
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(
+    expected = "Constant cycle detected: `Named.title` -> `Named.label` -> `Named.title`"
+)]
+fn test_trait_attributes_depend_on_each_other_through_self() {
+    test_constant_cycle_check(
+        r#"
+    trait Named(name: String) {
+        const title = self.label
+        const label = "{self.title}: {self.name}"
+    }
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `Box.x` -> `Box.x`")]
+fn test_attribute_depends_on_itself_through_new_instance() {
+    test_constant_cycle_check(
+        r#"
+    struct Box(n: Int) {
+        const x = Box(self.n).x
+        const rendered = "{self.x}"
+    }
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `Box.x` -> `Box.x`")]
+fn test_attribute_depends_on_itself_through_field() {
+    test_constant_cycle_check(
+        r#"
+    struct Box(n: Int, inner: Box) {
+        const x = self.inner.x + 1
+    }
+    "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Constant cycle detected: `Box.x` -> `Box.x`")]
+fn test_attribute_depends_on_itself_through_instance_constant() {
+    test_constant_cycle_check(
+        r#"
+    struct Box(n: Int) {
+        const x = A.x + 1
+    }
+    const A = Box(1)
+    "#,
+    )
+}
+
+#[test]
+fn test_constants_without_cycle() {
+    test_constant_cycle_check(
+        r#"
+    const A = 1
+    const B = A + A
+    struct Page(number: Int) {
+        const first = self.number + B
+        const second = self.first + A
+    }
+    const C = Page(B).second
+    const page = Page(C)
+    const D = page.second + page.first
+    "#,
+    )
+}
+
+fn test_constant_cycle_check(program: &str) {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = vec![abstract_tree(parse(tokenize(program)))];
+    let module_paths = vec![vec![]];
+    crate::env::Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
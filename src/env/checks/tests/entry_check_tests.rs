@@ -37,7 +37,7 @@ fn test_entry_follows_render() {
         .validate(&syntax_trees);
 }
 
-fn build_syntax_trees(source: &str) -> (Vec<AbstractSyntaxTree>, Vec<Vec<&str>>) {
+fn build_syntax_trees(source: &str) -> (Vec<AbstractSyntaxTree<'_>>, Vec<Vec<&str>>) {
     let syntax_trees = stdlib::compiled_content()
         .into_iter()
         .chain(std::iter::once(abstract_tree(parse(tokenize(source)))))
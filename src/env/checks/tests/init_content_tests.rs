@@ -15,7 +15,7 @@ fn get_syntax_trees<const N: usize>(source: [&'static str; N]) -> Vec<AbstractSy
 fn get_modules<const N: usize>(module_paths: [Vec<&'static str>; N]) -> Vec<Vec<&'static str>> {
     stdlib::module_paths()
         .into_iter()
-        .chain(module_paths.into_iter())
+        .chain(module_paths)
         .collect()
 }
 
@@ -1,9 +1,11 @@
+mod constant_cycle_tests;
 mod entry_check_tests;
 mod init_content_tests;
 mod render_tests;
 mod resolve_types_tests;
 mod struct_hierarchy_tests;
 mod test_type_check_chaining_method;
+mod trait_inheritance_tests;
 
 macro_rules! try_block {
     ($kind: ty, $block: expr) => {{
@@ -8,6 +8,7 @@ use crate::env::Environment;
 use crate::formula_suppress::FormulaSuppress;
 use crate::parser::parse;
 use crate::tokenizer::{tokenize, LiteralKind};
+use std::path::Path;
 
 #[test]
 fn test_int() {
@@ -34,13 +35,189 @@ fn test_bool() {
     test_literals(LiteralKind::Boolean, Types::BOOL)
 }
 
+#[test]
+fn test_length() {
+    test_literals(LiteralKind::Length, Types::LENGTH)
+}
+
+#[test]
+fn test_color() {
+    test_literals(LiteralKind::Color, Types::COLOR)
+}
+
 fn test_literals(kind: LiteralKind, expected: Types) {
-    let expression = Expression::Literal { kind, lexeme: "" };
+    let lexeme = match kind {
+        LiteralKind::Integer => "1",
+        LiteralKind::Binary => "0b1",
+        LiteralKind::Octal => "0o1",
+        LiteralKind::Hex => "0x1",
+        LiteralKind::Floating => "1.5",
+        _ => "",
+    };
+    let expression = Expression::Literal { kind, lexeme };
     let env = Environment::default();
     let actual = TypeChecker::with_environment(&env).test_resolve_expression(&expression);
     assert_eq!(actual, expected)
 }
 
+#[test]
+fn test_tuple_literal() {
+    let expression = Expression::Tuple(vec![
+        Expression::Literal {
+            kind: LiteralKind::Integer,
+            lexeme: "1",
+        },
+        Expression::Literal {
+            kind: LiteralKind::String,
+            lexeme: "",
+        },
+    ]);
+    let env = Environment::default();
+    let actual = TypeChecker::with_environment(&env).test_resolve_expression(&expression);
+    assert_eq!(actual, Types::Tuple([Types::INT, Types::STRING].into()))
+}
+
+#[test]
+fn test_destructuring_types() {
+    test_validated(
+        r#"
+        struct Size(width: Int, height: Float)
+        const (width, height) = (3, 4.5)
+        const size = Size(width, height)
+        "#,
+    )
+}
+
+#[test]
+fn test_tuple_field_type() {
+    test_validated(
+        r#"
+        struct Sized(size: (Int, String))
+        const sized = Sized((3, "A4"))
+        const width = sized.size.0
+        const other = Sized((width, sized.size.1))
+        "#,
+    )
+}
+
+#[test]
+#[should_panic]
+fn test_tuple_field_type_mismatch() {
+    test_validated(
+        r#"
+        struct Sized(size: (Int, String))
+        const sized = Sized(("A4", 3))
+        "#,
+    )
+}
+
+#[test]
+#[should_panic]
+fn test_destructuring_size_mismatch() {
+    test_validated("const (width, height) = (3, 4, 5)\n")
+}
+
+#[test]
+#[should_panic]
+fn test_destructuring_non_tuple() {
+    test_validated("const (width, height) = 3\n")
+}
+
+#[test]
+#[should_panic(expected = "Collections of tuples are not supported, found `(Int, Int)`")]
+fn test_collection_of_tuples() {
+    test_validated("const pairs = [(1, 2), (3, 4)]\n")
+}
+
+#[test]
+fn test_nested_collection() {
+    test_validated(
+        r#"
+        const rows = [[1, 2], [3]]
+        const first = rows[0].len() + rows.flatten()[2]
+        "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Cannot apply `+` to `[Int]` and `Int`")]
+fn test_nested_collection_element_type() {
+    test_validated("const first = [[1, 2], [3]].first() + 1\n")
+}
+
+#[test]
+#[should_panic(expected = "Method `flatten` could not be found in type `[Int]`")]
+fn test_flatten_flat_collection() {
+    test_validated("const numbers = [1, 2].flatten()\n")
+}
+
+#[test]
+#[should_panic]
+fn test_tuple_index_out_of_range() {
+    test_validated(
+        r#"
+        const pair = (3, 4)
+        const third = pair.2
+        "#,
+    )
+}
+
+fn test_validated(program: &str) {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = vec![abstract_tree(parse(tokenize(program)))];
+    let module_paths = vec![vec![]];
+    Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_literals_in_range() {
+    test_validated(
+        "const a = (9_223_372_036_854_775_807, -9223372036854775808, -0x7FFF_FFFF_FFFF_FFFF)\n",
+    )
+}
+
+#[test]
+#[should_panic(expected = "<unknown>: Integer literal (9223372036854775808) is out of range")]
+fn test_integer_literal_overflow() {
+    test_validated("const a = 9223372036854775808\n")
+}
+
+#[test]
+#[should_panic(expected = "Integer literal (0b1")]
+fn test_binary_literal_overflow() {
+    test_validated(&format!("const a = 0b1{}\n", "0".repeat(64)))
+}
+
+#[test]
+#[should_panic(expected = "Floating literal (1e400) is out of range")]
+fn test_floating_literal_overflow() {
+    test_validated("const a = 1e400\n")
+}
+
+#[test]
+#[should_panic(expected = "main.ds:3:13: Integer literal (0xFFFF_FFFF_FFFF_FFFF) is out of range")]
+fn test_literal_overflow_location() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program =
+        "const a = 1\nconst b = match a {\n    1 => 2, 0xFFFF_FFFF_FFFF_FFFF => 3, _ => 4 }\n";
+    let mut syntax_trees = vec![abstract_tree(parse(tokenize(program)))];
+    Environment::builder()
+        .add_modules_from_sources([(Path::new("main.ds"), program)].into_iter())
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
 #[test]
 fn test_resolve_struct() {
     let formula = FormulaSuppress::all();
@@ -321,13 +498,16 @@ fn test_access_field_with_trait_type() {
 }
 
 #[test]
-#[should_panic]
-fn access_undeclared_field_from_trait() {
+#[should_panic(expected = "Failed struct base type check")]
+fn test_base_instance_of_different_struct() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
     let program = r#"
-    trait Trait(value: Int)
-    struct Impl(value: Int, hidden: String)
-    struct TestType(field: Trait)
-    const test = TestType(Impl(42)).hidden
+    struct Essay(title: String)
+    struct Letter(title: String)
+    const letter = Letter("Hi")
+    const essay = Essay(..letter, title: "Hello")
     "#;
     let module_paths = vec![vec![]];
     let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
@@ -340,70 +520,95 @@ fn access_undeclared_field_from_trait() {
 }
 
 #[test]
-fn test_void() {
-    let void_expr = Expression::Void;
-    let env = Environment::default();
-    let actual = TypeChecker::with_environment(&env).test_resolve_expression(&void_expr);
-    assert_eq!(actual, Types::VOID)
+#[should_panic(expected = "Field `depth` could not be found")]
+fn test_update_unknown_nested_field() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Size(width: Int, height: Int)
+    struct Page(size: Size)
+    const page = Page(Size(100, 200))
+    const deep = page.with(size.depth: 1)
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
 }
 
 #[test]
-fn test_void_type_full() {
-    test_void_type_in_const(
-        r#"
-    const marker = ()
-    "#,
-    );
+#[should_panic(
+    expected = "Default value of field `subsections` cannot create `Section` recursively"
+)]
+fn test_default_value_creates_own_struct() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Section(title: String, subsections: [Section] = [Section("Empty")])
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
 }
 
-fn test_void_type_in_const(program: &str) {
+#[test]
+fn test_default_value_creates_own_struct_with_field_given() {
     let formula = FormulaSuppress::all();
     formula.suppress();
 
+    let program = r#"
+    struct Section(title: String, subsections: [Section] = [Section("Empty", [])])
+    struct Chapter(title: String, sections: [Chapter] = [Chapter(title: "Empty", sections: [])])
+    const section = Section("Introduction")
+    "#;
     let module_paths = vec![vec![]];
     let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
-    let env = Environment::builder()
+    let _env = Environment::builder()
         .add_modules(&module_paths)
         .generate_scopes(&mut syntax_trees)
         .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
         .build();
+}
 
-    let target_constant = try_block!(
-        &ConstantDeclaration,
-        syntax_trees
-            .first()?
-            .compilation_unit
-            .declarations
-            .first()?
-            .as_constant()
-    );
-    let actual =
-        TypeChecker::with_environment(&env).test_resolve_expression(&target_constant.value);
-    let expected = Types::VOID;
-    assert_eq!(actual, expected)
+#[test]
+fn test_length_arithmetic() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Page(margin: Length)
+    const page = Page(1in - 2cm + 50%)
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
 }
 
 #[test]
-fn test_void_type_impl_trait() {
+#[should_panic(expected = "Cannot apply `+` to `Length` and `Int`")]
+fn test_length_plus_int() {
     let formula = FormulaSuppress::all();
     formula.suppress();
 
-    let mut syntax_trees = [
-        abstract_tree(parse(tokenize("trait Test(marker: Void)\n"))),
-        abstract_tree(parse(tokenize("struct Collection(test: Test)\n"))),
-        abstract_tree(parse(tokenize(
-            r#"struct Impl(data: String) { 
-            const marker = () 
-            }
-            "#,
-        ))),
-        abstract_tree(parse(tokenize(
-            r#"
-            const collection = Collection(Impl("test"))
-            "#,
-        ))),
-    ];
-    let module_paths = [vec![], vec![], vec![], vec![]];
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize("const width = 12pt + 3\n")))];
     let _env = Environment::builder()
         .add_modules(&module_paths)
         .generate_scopes(&mut syntax_trees)
@@ -414,15 +619,55 @@ fn test_void_type_impl_trait() {
 
 #[test]
 #[should_panic]
-fn test_collection_with_different_types() {
+fn test_length_for_int_field() {
     let formula = FormulaSuppress::all();
     formula.suppress();
 
+    let program = r#"
+    struct Size(width: Int, height: Int)
+    const a4 = Size(210mm, 297mm)
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_color_methods() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Theme(accent: Color, hover: Color = self.accent.lighten(0.2))
+    const accent = #1e90ff
+    const theme = Theme(accent.mix(#fff).darken(0.1))
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Method `brighten` could not be found in type `Color`")]
+fn test_unknown_color_method() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
     let mut syntax_trees = [abstract_tree(parse(tokenize(
-        "const arr = [1, true, ()]\n",
+        "const hover = #1e90ff.brighten(0.2)\n",
     )))];
-    let module_paths = [vec![]];
-    Environment::builder()
+    let _env = Environment::builder()
         .add_modules(&module_paths)
         .generate_scopes(&mut syntax_trees)
         .resolve_names(&syntax_trees)
@@ -431,22 +676,906 @@ fn test_collection_with_different_types() {
 }
 
 #[test]
-fn test_assign_empty_collection_to_multiple_collections() {
+#[should_panic(expected = "Expect type: `Float`\nFound type: `String`, on method .lighten")]
+fn test_color_method_argument_type() {
     let formula = FormulaSuppress::all();
     formula.suppress();
 
+    let module_paths = vec![vec![]];
     let mut syntax_trees = [abstract_tree(parse(tokenize(
-        r#"
-    struct IntArray(elements: [Int])
-    struct Text;
-    struct TextArray(elements: [Text])
-    const empty = []
-    const int_array = IntArray(empty)
-    const text_array = TextArray(empty)
-    "#,
+        "const hover = #1e90ff.lighten(\"20%\")\n",
     )))];
-    let module_paths = [vec![]];
-    Environment::builder()
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_color_mix_weight() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const muted = #1e90ff.mix(#ffffff, 0.25).lighten(1.0)\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expect type: `Color` or `(Color, Float)`\nFound type: `(Color, Int)`, on method .mix"
+)]
+fn test_color_mix_argument_type() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const muted = #1e90ff.mix(#ffffff, 1)\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Argument of method `lighten` should be between 0 and 1, found 1.5")]
+fn test_color_lighten_literal_out_of_range() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const hover = #1e90ff.lighten(1.5)\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Argument of method `mix` should be between 0 and 1, found 2.0")]
+fn test_color_mix_weight_literal_out_of_range() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const muted = #1e90ff.mix(#ffffff, 2.0)\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic]
+fn access_undeclared_field_from_trait() {
+    let program = r#"
+    trait Trait(value: Int)
+    struct Impl(value: Int, hidden: String)
+    struct TestType(field: Trait)
+    const test = TestType(Impl(42)).hidden
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_void() {
+    let void_expr = Expression::Void;
+    let env = Environment::default();
+    let actual = TypeChecker::with_environment(&env).test_resolve_expression(&void_expr);
+    assert_eq!(actual, Types::VOID)
+}
+
+#[test]
+fn test_void_type_full() {
+    test_void_type_in_const(
+        r#"
+    const marker = ()
+    "#,
+    );
+}
+
+fn test_void_type_in_const(program: &str) {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+
+    let target_constant = try_block!(
+        &ConstantDeclaration,
+        syntax_trees
+            .first()?
+            .compilation_unit
+            .declarations
+            .first()?
+            .as_constant()
+    );
+    let actual =
+        TypeChecker::with_environment(&env).test_resolve_expression(&target_constant.value);
+    let expected = Types::VOID;
+    assert_eq!(actual, expected)
+}
+
+#[test]
+fn test_void_type_impl_trait() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = [
+        abstract_tree(parse(tokenize("trait Test(marker: Void)\n"))),
+        abstract_tree(parse(tokenize("struct Collection(test: Test)\n"))),
+        abstract_tree(parse(tokenize(
+            r#"struct Impl(data: String) { 
+            const marker = () 
+            }
+            "#,
+        ))),
+        abstract_tree(parse(tokenize(
+            r#"
+            const collection = Collection(Impl("test"))
+            "#,
+        ))),
+    ];
+    let module_paths = [vec![], vec![], vec![], vec![]];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic]
+fn test_collection_with_different_types() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const arr = [1, true, ()]\n",
+    )))];
+    let module_paths = [vec![]];
+    Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_assign_empty_collection_to_multiple_collections() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        r#"
+    struct IntArray(elements: [Int])
+    struct Text;
+    struct TextArray(elements: [Text])
+    const empty = []
+    const int_array = IntArray(empty)
+    const text_array = TextArray(empty)
+    "#,
+    )))];
+    let module_paths = [vec![]];
+    Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_interpolated_string() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const TOTAL = 12
+    struct Footer(number: Int, draft: Bool) {
+        const text = "Page {self.number} of {TOTAL}, draft: {self.draft}"
+    }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "Only `String`, `Int`, `Float` and `Bool` can be interpolated in strings, found `Length`"
+)]
+fn test_interpolated_length() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const a = \"width: {12pt}\"\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_collection_index_and_slice() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Essay(authors: [String]) {
+        const credit = "{self.authors[0]} et al., {self.authors[1..].len()} more"
+    }
+    const AUTHORS = ["Ada", "Grace"]
+    const last = AUTHORS[AUTHORS.len() - 1]
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Index 2 is out of bounds for collection of length 2")]
+fn test_static_index_out_of_bounds() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const AUTHORS = ["Ada", "Grace"]
+    const third = AUTHORS[2]
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Slice 1..3 is out of bounds for collection of length 2")]
+fn test_static_slice_out_of_bounds() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize("const a = [1, 2][1..3]\n")))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Only collections can be indexed, found `String`")]
+fn test_index_string() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize("const a = \"text\"[0]\n")))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Method `join` could not be found in type `[Int]`")]
+fn test_join_numbers() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const a = [1, 2].join(\", \")\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_primitive_methods() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Chapter(number: Int, title: String, ratio: Float) {
+        const heading = "{self.number.roman()}. {self.title.title().replace("And", "and")}"
+        const progress = self.ratio.format(1)
+        const draft = self.title.lower().starts_with("draft")
+    }
+    const chapter = Chapter(4, "tools and methods", 0.25)
+    const words = chapter.title.split(" ").len()
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "Expect type: `(String, String)`\nFound type: `String`, on method .replace"
+)]
+fn test_replace_with_one_argument() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(
+        "const TITLE = \"Title\"\nconst a = TITLE.replace(\"T\")\n",
+    )))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Method `roman` could not be found in type `Float`")]
+fn test_roman_float() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize("const a = 2.0.roman()\n")))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_match() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    trait Captioned(caption: String)
+    struct Figure(caption: String)
+    struct Table(caption: String, rows: Int)
+    struct Labelled(child: Captioned, level: Int) {
+        const label = match self.child {
+            figure: Figure => figure.caption,
+            Table => "table",
+            _ => "other",
+        }
+        const size = match self.level {
+            1 => 24,
+            LARGE => 18,
+            _ => 12,
+        }
+    }
+    const LARGE = 2
+    const WIDE = true
+    const columns = match WIDE { true => 2, false => 1 }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Match on `Int` is not exhaustive, add a `_` arm")]
+fn test_match_not_exhaustive() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const level = 1
+    const size = match level { 1 => 24, 2 => 18 }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Match arms should have a common type, found `Int`, `String`")]
+fn test_match_arms_with_different_types() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const level = 1
+    const size = match level { 1 => 24, _ => "small" }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_match_arms_with_common_trait() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    trait Named(caption: String)
+    trait Captioned: Named
+    struct Figure(caption: String)
+    struct Table(caption: String, rows: Int)
+    struct Labelled(child: Captioned)
+    const WIDE = true
+    const child = match WIDE { true => Table("Results", 3), false => Figure("A cat") }
+    const labelled = Labelled(child)
+    const rows = match WIDE { true => [1, 2], false => [] }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "Match arms of types `Table`, `Figure` conform to several unrelated traits `Named`, `Sized`"
+)]
+fn test_match_arms_with_several_common_traits() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    trait Named(caption: String)
+    trait Sized(rows: Int)
+    struct Figure(caption: String, rows: Int)
+    struct Table(caption: String, rows: Int)
+    const WIDE = true
+    const child = match WIDE { true => Table("Results", 3), false => Figure("A cat", 1) }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Match arms should have a common type, found `Table`, `Figure`")]
+fn test_match_arms_with_common_trait_of_another_module() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let programs = [
+        r#"
+        struct Figure(caption: String)
+        struct Table(caption: String, rows: Int)
+        const WIDE = true
+        const child = match WIDE { true => Table("Results", 3), false => Figure("A cat") }
+        "#,
+        "pub trait Named(caption: String)\n",
+    ];
+    let module_paths = vec![vec![], vec!["shapes"]];
+    let mut syntax_trees = programs.map(|program| abstract_tree(parse(tokenize(program))));
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "TypeMismatch { field: \"count\", expected: \"Int\", found: \"Titled\" }"
+)]
+fn test_match_arms_with_equivalent_traits_first_declared() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let programs = [
+        r#"
+        use shapes.Named
+        trait Titled(caption: String)
+        trait Captioned(caption: String)
+        struct Figure(caption: String)
+        struct Table(caption: String, rows: Int)
+        struct Counter(count: Int)
+        const WIDE = true
+        const child = match WIDE { true => Table("Results", 3), false => Figure("A cat") }
+        const counter = Counter(child)
+        "#,
+        "pub trait Named(caption: String)\ntrait Hidden(caption: String)\n",
+    ];
+    let module_paths = vec![vec![], vec!["shapes"]];
+    let mut syntax_trees = programs.map(|program| abstract_tree(parse(tokenize(program))));
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Pattern `\"1\"` of type `String` cannot match a value of type `Int`")]
+fn test_match_pattern_with_different_type() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const level = 1
+    const size = match level { "1" => 24, _ => 12 }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Struct `Figure` can never match a value of type `Title`")]
+fn test_match_unrelated_struct() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Figure(caption: String)
+    struct Title(text: String)
+    const title = Title("title")
+    const caption = match title { figure: Figure => figure.caption, _ => "" }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Match arm is unreachable, the arms before it cover every value")]
+fn test_match_unreachable_arm() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const level = 1
+    const size = match level { _ => 12, 1 => 24 }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_assertions() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const PAGES = 12
+    const MARGIN = 2cm
+    assert(PAGES <= 40, "Document should have at most 40 pages")
+    assert(MARGIN >= 1cm, "Margin should be at least 1cm")
+    struct Title(text: String) {
+        const size = 24
+        assert(self.text.len() < 80, "Title should be shorter than 80 characters")
+        assert(self.text != "", "Title should not be empty")
+    }
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Cannot apply `<` to `String` and `String`")]
+fn test_ordering_strings() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const is_before = "a" < "b"
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Cannot apply `==` to `Int` and `Float`")]
+fn test_comparison_different_types() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const is_same = 1 == 1.0
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Assertion condition should be `Bool`, found `Int`")]
+fn test_assertion_condition_not_bool() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    const PAGES = 12
+    assert(PAGES, "Document is too long")
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn test_type_aliases() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    struct Figure(caption: String)
+    type Pt = Int
+    type Figures = [Figure]
+    type Size = (Pt, Pt)
+    type Gallery = Figures
+    struct Page(size: Size, figures: Gallery, margin: Pt = 12)
+    const page = Page((595, 842), [Figure("A cat"), Figure("A dog")])
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Type alias `Tree` refers to itself")]
+fn test_type_alias_cycle() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    type Tree = (Int, [Tree])
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(
+    expected = "Default value for field `margin` has a different type.\nExpected: Pt\nFound: String"
+)]
+fn test_type_alias_named_in_error() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    type Pt = Int
+    struct Page(margin: Pt = "12")
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "expected: \"(Pt, String)\", found: \"(String, String)\"")]
+fn test_type_alias_named_in_tuple_error() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    type Pt = Int
+    struct Label(position: (Pt, String))
+    const label = Label(("12", "Title"))
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Expect type: `Pt`\nFound type: `String`, on access .margin")]
+fn test_type_alias_named_on_access() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    type Pt = Int
+    struct Page(margin: Pt = 12)
+    const page = Page().with(margin: "24")
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Type alias `Caption` cannot create an instance, use `Label` instead")]
+fn test_type_alias_struct_init() {
+    test_validated(
+        r#"
+    struct Label(text: String)
+    type Caption = Label
+    const title = Caption("Title")
+    "#,
+    )
+}
+
+#[test]
+fn test_field_named_type() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    type Kind = String
+    struct Shape(type: Kind)
+    const type = Shape("circle").type
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Collections of collections are not supported, found `[Captions]`")]
+fn test_collection_of_collection_alias() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let program = r#"
+    trait Captioned(caption: String)
+    type Captions = [Captioned]
+    struct Gallery(pages: [Captions])
+    "#;
+    let module_paths = vec![vec![]];
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let _env = Environment::builder()
         .add_modules(&module_paths)
         .generate_scopes(&mut syntax_trees)
         .resolve_names(&syntax_trees)
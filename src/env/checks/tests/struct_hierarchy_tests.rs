@@ -38,6 +38,47 @@ fn test_reference_to_cycle() {
     assert!(res.is_err())
 }
 
+#[test]
+fn test_self_reference_through_tuple() {
+    let res = test_hierarchy_check(
+        r#"
+    struct A(pair: (Int, A))
+    "#,
+    );
+    assert!(res.is_err())
+}
+
+#[test]
+fn test_self_reference_through_collection() {
+    let res = test_hierarchy_check(
+        r#"
+    struct Section(title: String, subsections: [Section] = [])
+    "#,
+    );
+    assert!(res.is_ok())
+}
+
+#[test]
+fn test_reference_between_structs_through_collection() {
+    let res = test_hierarchy_check(
+        r#"
+    struct List(head: Item)
+    struct Item(text: String, nested: [List] = [])
+    "#,
+    );
+    assert!(res.is_ok())
+}
+
+#[test]
+fn test_self_reference_through_tuple_of_collection() {
+    let res = test_hierarchy_check(
+        r#"
+    struct Tree(branches: (Int, [Tree]))
+    "#,
+    );
+    assert!(res.is_ok())
+}
+
 #[test]
 fn test_attribute_reference_to_self() {
     let res = test_hierarchy_check(
@@ -0,0 +1,264 @@
+use crate::ast::{abstract_tree, AbstractSyntaxTree, StructDeclaration};
+use crate::env::Environment;
+use crate::formula_suppress::FormulaSuppress;
+use crate::parser::parse;
+use crate::tokenizer::tokenize;
+
+#[test]
+fn test_struct_inherits_trait_attribute() {
+    validate(
+        r#"
+        trait Titled(title: String) {
+            const heading = self.title
+        }
+        struct Page(title: String)
+        struct Chapter(title: String) {
+            const heading = "Chapter"
+        }
+        const heading = Page("Intro").heading
+        "#,
+        |env, syntax_trees| {
+            let page = struct_declaration(syntax_trees, 1);
+            let inherited = env.inherited_attributes.get(page).expect("Page inherits");
+            assert_eq!(
+                inherited
+                    .iter()
+                    .map(|attribute| attribute.name)
+                    .collect::<Vec<_>>(),
+                vec!["heading"]
+            );
+            let chapter = struct_declaration(syntax_trees, 2);
+            assert!(!env.inherited_attributes.contains_key(chapter));
+        },
+    );
+}
+
+#[test]
+fn test_access_attribute_from_trait_type() {
+    check(
+        r#"
+        trait Titled(title: String) {
+            const heading = self.title
+        }
+        struct Page(title: String)
+        struct Book(first: Titled)
+        const heading = Book(Page("Intro")).first.heading
+        "#,
+    )
+}
+
+#[test]
+fn test_sub_trait_overrides_attribute() {
+    validate(
+        r#"
+        trait Named(name: String) {
+            const label = self.name
+        }
+        trait Person(age: Int): Named {
+            const label = "person"
+        }
+        struct Student(name: String, age: Int)
+        "#,
+        |env, syntax_trees| {
+            let student = struct_declaration(syntax_trees, 2);
+            let person = syntax_trees[0].compilation_unit.declarations[1]
+                .as_trait()
+                .unwrap();
+            let inherited = &env.inherited_attributes[student];
+            let expected = &person.body.as_ref().unwrap().attributes[0];
+            assert_eq!(inherited.len(), 1);
+            assert!(std::ptr::eq(inherited[0], expected));
+        },
+    );
+}
+
+#[test]
+fn test_trait_conforms_to_super_trait() {
+    check(
+        r#"
+        trait Named(name: String)
+        trait Person(age: Int): Named
+        struct Badge(owner: Named)
+        struct Card(person: Person) {
+            const badge = Badge(self.person)
+            const name = self.person.name
+        }
+        "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Failed struct field type check")]
+fn test_super_trait_fields_are_required() {
+    check(
+        r#"
+        trait Named(name: String)
+        trait Person(age: Int): Named
+        struct Unnamed(age: Int)
+        struct Card(person: Person)
+        const card = Card(Unnamed(42))
+        "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "overrides attribute from trait `Titled` with a different type")]
+fn test_override_with_different_type() {
+    check(
+        r#"
+        trait Titled(title: String) {
+            const heading = self.title
+        }
+        struct Page(title: String) {
+            const heading = 42
+        }
+        "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Struct `Page` inherits attribute `heading` from more than one trait")]
+fn test_ambiguous_inherited_attribute() {
+    check(
+        r#"
+        trait Titled(title: String) {
+            const heading = self.title
+        }
+        trait Numbered(number: Int) {
+            const heading = "Page"
+        }
+        struct Page(title: String, number: Int)
+        "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "Cycle detected in trait inheritance")]
+fn test_cyclic_super_traits() {
+    check(
+        r#"
+        trait First: Second
+        trait Second: First
+        "#,
+    )
+}
+
+#[test]
+#[should_panic(expected = "`Plain` is not a trait, trait `Fancy` can only inherit traits")]
+fn test_inherit_struct() {
+    check(
+        r#"
+        struct Plain
+        trait Fancy: Plain
+        "#,
+    )
+}
+
+#[test]
+fn test_unrelated_traits_are_not_inherited() {
+    validate_modules(
+        [
+            r#"
+            pub struct Item(name: String)
+            "#,
+            r#"
+            trait Tagged(name: String) {
+                const tag = "shape"
+            }
+            "#,
+            r#"
+            pub trait Labelled(name: String) {
+                const tag = "label"
+            }
+            "#,
+        ],
+        |env, syntax_trees| {
+            let item = struct_declaration(syntax_trees, 0);
+            assert!(!env.inherited_attributes.contains_key(item));
+        },
+    );
+}
+
+#[test]
+fn test_imported_trait_is_inherited() {
+    validate_modules(
+        [
+            r#"
+            use labels.Labelled as Named
+            struct Item(name: String)
+            const tag = Item("pen").tag
+            "#,
+            r#"
+            trait Tagged(name: String) {
+                const tag = "shape"
+            }
+            "#,
+            r#"
+            pub trait Labelled(name: String) {
+                const tag = "label"
+            }
+            "#,
+        ],
+        |env, syntax_trees| {
+            let item = syntax_trees[0].compilation_unit.declarations[1]
+                .as_struct()
+                .unwrap();
+            let labelled = syntax_trees[2].compilation_unit.declarations[0]
+                .as_trait()
+                .unwrap();
+            let inherited = &env.inherited_attributes[item];
+            assert_eq!(inherited.len(), 1);
+            assert!(std::ptr::eq(
+                inherited[0],
+                &labelled.body.as_ref().unwrap().attributes[0]
+            ));
+        },
+    );
+}
+
+fn check(program: &str) {
+    validate(program, |_, _| ());
+}
+
+fn validate(program: &str, assertion: impl FnOnce(&Environment, &[AbstractSyntaxTree])) {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = [abstract_tree(parse(tokenize(program)))];
+    let module_paths = [vec![]];
+    let env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+    assertion(&env, &syntax_trees)
+}
+
+/// Validates the programs as the entry module, module `shapes` and module `labels`
+fn validate_modules(
+    programs: [&str; 3],
+    assertion: impl FnOnce(&Environment, &[AbstractSyntaxTree]),
+) {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = programs.map(|program| abstract_tree(parse(tokenize(program))));
+    let module_paths = [vec![], vec!["shapes"], vec!["labels"]];
+    let env = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .validate(&syntax_trees)
+        .build();
+    assertion(&env, &syntax_trees)
+}
+
+fn struct_declaration<'ast, 'a>(
+    syntax_trees: &'ast [AbstractSyntaxTree<'a>],
+    index: usize,
+) -> &'ast StructDeclaration<'a> {
+    syntax_trees[0].compilation_unit.declarations[index]
+        .as_struct()
+        .unwrap()
+}
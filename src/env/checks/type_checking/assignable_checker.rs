@@ -1,3 +1,4 @@
+use super::trait_hierarchy;
 use super::type_resolver;
 use crate::env::checks::type_checking::render_impl_checker::RenderImplChecker;
 use crate::env::checks::type_checking::types::{Primitive, Types};
@@ -20,45 +21,63 @@ impl<'ast, 'a, 'env, 'checker> AssignableChecker<'ast, 'a, 'env, 'checker> {
         source == target
             || Self::empty_assignability(source, target)
             || (matches!(target, Types::Trait(_)) && self.conforms_to_trait(source, target))
+            || self.tuple_assignability(source, target)
             || RenderImplChecker(self.0.environment).check(source, target)
     }
 
+    /// Tuples are assignable when they have the same size,
+    /// and every element is assignable to the element at the same position
+    fn tuple_assignability(&mut self, source: &Types<'ast, 'a>, target: &Types<'ast, 'a>) -> bool {
+        match (source, target) {
+            (Types::Tuple(source_elements), Types::Tuple(target_elements)) => {
+                source_elements.len() == target_elements.len()
+                    && source_elements
+                        .iter()
+                        .zip(target_elements.iter())
+                        .all(|(source, target)| self.check(source, target))
+            }
+            _ => false,
+        }
+    }
+
     fn empty_assignability(source: &Types<'ast, 'a>, target: &Types<'ast, 'a>) -> bool {
-        matches!(source, Types::Primitive(Primitive::Void))
-            && matches!(
-                target,
-                Types::PrimitiveCollection(_)
-                    | Types::StructCollection(_)
-                    | Types::TraitCollection(_)
-            )
+        matches!(source, Types::Primitive(Primitive::Void)) && target.is_collection()
     }
 
     /// To make a type conforms to a trait,
     /// the source type need to implement all the required fields
-    /// from the trait, with the same name and type
+    /// from the trait and its super traits, with the same name and type
     ///
     /// # Note
-    /// Both struct and trait can conform to another trait
+    /// Both struct and trait can conform to another trait,
+    /// and a trait always conforms to the traits it inherits with `trait B: A`
     fn conforms_to_trait(
         &mut self,
         source_type: &Types<'ast, 'a>,
         trait_type: &Types<'ast, 'a>,
     ) -> bool {
-        for field in trait_type.fields() {
-            if let Some(typed_element) = source_type.access(field.name) {
-                let expected_type = type_resolver::resolve_type_name(
-                    self.0.environment,
-                    &field.field_type.name,
-                    field.field_type.is_collection,
-                )
-                .expect("Expected trait field type not found");
+        let environment = self.0.environment;
+        let r#trait = match trait_type {
+            Types::Trait(r#trait) => *r#trait,
+            _ => return false,
+        };
+        if let Types::Trait(source_trait) = source_type {
+            if trait_hierarchy::inherits(environment, source_trait, r#trait) {
+                return true;
+            }
+        }
+        for field in trait_hierarchy::required_fields(environment, r#trait) {
+            if let Some(typed_element) =
+                trait_hierarchy::access(environment, source_type, field.name)
+            {
+                let expected_type =
+                    type_resolver::resolve_type(self.0.environment, &field.field_type)
+                        .expect("Expected trait field type not found");
                 let found_type = match typed_element {
-                    TypedElement::Field(found_field) => type_resolver::resolve_type_name(
-                        self.0.environment,
-                        &found_field.field_type.name,
-                        found_field.field_type.is_collection,
-                    )
-                    .expect("Failed to find type for field"),
+                    TypedElement::Field(found_field) => {
+                        type_resolver::resolve_type(self.0.environment, &found_field.field_type)
+                            .expect("Failed to find type for field")
+                    }
                     TypedElement::Constant(constant) => self.0.resolve_expression(&constant.value),
                 };
                 if !self.check(&found_type, &expected_type) {
@@ -227,7 +246,7 @@ mod type_conform_checker_tests {
             Types::STRING,
             Types::VOID,
             Types::BOOL,
-            trait_type,
+            trait_type.clone(),
         ] {
             assert!(conform_checker.check(&source_type, &trait_type))
         }
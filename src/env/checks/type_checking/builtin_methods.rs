@@ -0,0 +1,90 @@
+use super::types::{Primitive, Types};
+use crate::ast::Expression;
+
+/// Types of the argument and the output of a method built into a type
+pub(in crate::env) struct Signature<'ast, 'a> {
+    pub argument: Option<Types<'ast, 'a>>,
+    /// Type of an argument that can follow the argument, e.g. the weight of `mix`
+    pub optional: Option<Types<'ast, 'a>>,
+    pub output: Types<'ast, 'a>,
+}
+
+/// Finds the method built into the receiver type.
+/// Built-in methods are evaluated at compile time
+///
+/// # Example
+/// ```doc_script
+/// const hover = #1e90ff.lighten(0.2)
+/// const title = "chapter one".title().replace("One", "1")
+/// const names = authors.reverse().join(", ")
+/// ```
+pub(in crate::env) fn signature<'ast, 'a>(
+    receiver: &Types<'ast, 'a>,
+    method: &str,
+) -> Option<Signature<'ast, 'a>> {
+    let optional = match (receiver, method) {
+        (Types::Primitive(Primitive::Color), "mix") => Some(Types::FLOAT),
+        _ => None,
+    };
+    let (argument, output) = match (receiver, method) {
+        (Types::Primitive(Primitive::String), "upper" | "lower" | "title" | "trim") => {
+            (None, Types::STRING)
+        }
+        (Types::Primitive(Primitive::String), "len") => (None, Types::INT),
+        (Types::Primitive(Primitive::String), "split") => (
+            Some(Types::STRING),
+            Types::PrimitiveCollection(Primitive::String),
+        ),
+        (Types::Primitive(Primitive::String), "replace") => (
+            Some(Types::Tuple([Types::STRING, Types::STRING].into())),
+            Types::STRING,
+        ),
+        (Types::Primitive(Primitive::String), "starts_with" | "ends_with") => {
+            (Some(Types::STRING), Types::BOOL)
+        }
+        (Types::Primitive(Primitive::Int | Primitive::Float), "to_string") => (None, Types::STRING),
+        (Types::Primitive(number @ (Primitive::Int | Primitive::Float)), "abs") => {
+            (None, Types::Primitive(*number))
+        }
+        (Types::Primitive(Primitive::Int), "roman") => (None, Types::STRING),
+        (Types::Primitive(Primitive::Float), "round") => (Some(Types::INT), Types::FLOAT),
+        (Types::Primitive(Primitive::Float), "format") => (Some(Types::INT), Types::STRING),
+        (Types::Primitive(Primitive::Color), "lighten" | "darken") => {
+            (Some(Types::FLOAT), Types::COLOR)
+        }
+        (Types::Primitive(Primitive::Color), "mix") => (Some(Types::COLOR), Types::COLOR),
+        (collection, "len") if collection.is_collection() => (None, Types::INT),
+        (collection, "first" | "last") if collection.is_collection() => {
+            (None, collection.clone().element_type())
+        }
+        (collection, "reverse") if collection.is_collection() => (None, collection.clone()),
+        (Types::NestedCollection(element), "flatten") => (None, element.as_ref().clone()),
+        (Types::PrimitiveCollection(Primitive::String), "join") => {
+            (Some(Types::STRING), Types::STRING)
+        }
+        _ => return None,
+    };
+    Some(Signature {
+        argument,
+        optional,
+        output,
+    })
+}
+
+/// Finds the part of the argument of a color method that is a ratio between 0 and 1
+///
+/// # Example
+/// ```doc_script
+/// const hover = #1e90ff.lighten(0.2)
+/// const muted = #1e90ff.mix(#ffffff, 0.75)
+/// ```
+pub(in crate::env) fn ratio_argument<'b, 'a>(
+    method: &str,
+    argument: &'b Expression<'a>,
+) -> Option<&'b Expression<'a>> {
+    match (method, argument) {
+        ("lighten" | "darken", ratio) => Some(ratio),
+        ("mix", Expression::Tuple(arguments)) => arguments.get(1),
+        _ => None,
+    }
+}
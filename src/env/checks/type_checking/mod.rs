@@ -1,7 +1,9 @@
 pub(in crate::env::checks) mod assignable_checker;
+mod builtin_methods;
 pub(in crate::env::checks) mod essential_trait;
 mod render_impl_checker;
 mod struct_init_checker;
+pub(in crate::env::checks) mod trait_hierarchy;
 mod type_checker;
 pub mod type_resolver;
 pub mod types;
@@ -1,4 +1,5 @@
 use super::assignable_checker::AssignableChecker;
+use super::type_resolver;
 use crate::ast::{Field, Parameter};
 use crate::env::checks::type_checking::types::Types;
 use std::collections::{HashMap, VecDeque};
@@ -14,12 +15,18 @@ pub enum Error {
     FieldNotSupplied(String),
     #[error("Too many parameters provided.\nExpected: {expected}\nFound: {found}")]
     TooManyInputParameters { expected: usize, found: usize },
-    #[error("Type mismatch for field `{}`.\nExpected: {expected}\nFound: {found}")]
+    #[error("Type mismatch for field `{field}`.\nExpected: {expected}\nFound: {found}")]
     TypeMismatch {
         field: String,
         expected: String,
         found: String,
     },
+    #[error("Base instance must be the same struct.\nExpected: {expected}\nFound: {found}")]
+    BaseTypeMismatch { expected: String, found: String },
+    #[error("Field `{0}` does not exist")]
+    UnknownField(String),
+    #[error("Nested field `{0}` can only be updated with `with`")]
+    NestedField(String),
 }
 
 impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
@@ -29,6 +36,23 @@ impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
         Self { assignable_checker }
     }
 
+    /// The base instance in `Struct(..base)` provides values for all the fields,
+    /// so it needs to be an instance of the initialized struct
+    pub fn check_base(
+        &self,
+        struct_type: &Types<'ast, 'a>,
+        base_type: &Types<'ast, 'a>,
+    ) -> Result<(), Error> {
+        if struct_type == base_type {
+            Ok(())
+        } else {
+            Err(Error::BaseTypeMismatch {
+                expected: format!("{}", struct_type),
+                found: format!("{}", base_type),
+            })
+        }
+    }
+
     pub fn check_parameters(
         &mut self,
         parameters: &[Parameter<'a>],
@@ -36,7 +60,9 @@ impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
         fields: &'ast [Field<'a>],
         field_types: &[Types<'ast, 'a>],
     ) -> Result<(), Error> {
-        if parameters.len() > fields.len() {
+        if let Some((Parameter::Spread(_), overrides)) = parameters.split_first() {
+            self.check_overrides(overrides, &parameter_types[1..], fields, field_types)
+        } else if parameters.len() > fields.len() {
             Err(Error::TooManyInputParameters {
                 expected: fields.len(),
                 found: parameters.len(),
@@ -65,17 +91,22 @@ impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
         let parameter_types = parameters
             .iter()
             .map(|parameter| match parameter {
-                Parameter::Labelled { label, .. } => *label,
+                Parameter::Labelled { label, .. } => Ok(*label),
+                Parameter::Nested { path, .. } => Err(Error::NestedField(path.join("."))),
                 Parameter::Plain(_) => unreachable!("Cannot mix labelled and plain parameter"),
+                Parameter::Spread(_) => {
+                    unreachable!("Base instance can only be the first parameter")
+                }
             })
             .zip(parameter_types)
-            .collect::<HashMap<_, _>>();
+            .map(|(label, parameter_type)| Ok((label?, parameter_type)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
         for (field, field_type) in fields.iter().zip(field_types) {
             if let Some(parameter_type) = parameter_types.get(field.name) {
                 if !self.assignable_checker.check(parameter_type, field_type) {
                     return Err(Error::TypeMismatch {
                         field: field.name.to_owned(),
-                        expected: format!("{}", field_type),
+                        expected: self.type_name(field, field_type),
                         found: format!("{}", parameter_type),
                     });
                 }
@@ -86,6 +117,36 @@ impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
         Ok(())
     }
 
+    /// Fields not labelled after the base instance keep the values of the base instance
+    fn check_overrides(
+        &mut self,
+        overrides: &[Parameter],
+        parameter_types: &[Types<'ast, 'a>],
+        fields: &'ast [Field<'a>],
+        field_types: &[Types<'ast, 'a>],
+    ) -> Result<(), Error> {
+        for (parameter, parameter_type) in overrides.iter().zip(parameter_types) {
+            let label = match parameter {
+                Parameter::Labelled { label, .. } => *label,
+                Parameter::Nested { path, .. } => return Err(Error::NestedField(path.join("."))),
+                _ => unreachable!("Only labelled parameters can follow the base instance"),
+            };
+            let (field, field_type) = fields
+                .iter()
+                .zip(field_types)
+                .find(|(field, _)| field.name == label)
+                .ok_or_else(|| Error::UnknownField(label.to_owned()))?;
+            if !self.assignable_checker.check(parameter_type, field_type) {
+                return Err(Error::TypeMismatch {
+                    field: field.name.to_owned(),
+                    expected: self.type_name(field, field_type),
+                    found: format!("{}", parameter_type),
+                });
+            }
+        }
+        Ok(())
+    }
+
     fn check_plain_parameters(
         &mut self,
         parameter_types: Vec<Types<'ast, 'a>>,
@@ -107,7 +168,7 @@ impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
             } else if field.default_value.is_none() {
                 return Err(Error::TypeMismatch {
                     field: field.name.to_owned(),
-                    expected: format!("{}", expected_type),
+                    expected: self.type_name(field, expected_type),
                     found: format!("{}", &parameter_types[0]),
                 });
             }
@@ -118,29 +179,35 @@ impl<'ast, 'a, 'env, 'checker> StructInitChecker<'ast, 'a, 'env, 'checker> {
             unreachable!("Unable to match parameters with required fields")
         }
     }
+
+    fn type_name(&self, field: &'ast Field<'a>, field_type: &Types<'ast, 'a>) -> String {
+        let environment = self.assignable_checker.0.environment;
+        type_resolver::type_name(environment, &field.field_type, field_type)
+    }
 }
 
 #[cfg(test)]
 mod struct_init_checker_tests {
     use super::{Error, StructInitChecker};
-    use crate::ast::{Expression, Field, Name, Parameter, Type};
+    use crate::ast::{Documentation, Expression, Field, Name, Parameter, Type};
     use crate::env::checks::type_checking::assignable_checker::AssignableChecker;
     use crate::env::checks::type_checking::types::Types;
     use crate::env::checks::type_checking::TypeChecker;
     use crate::env::Environment;
 
-    fn field(name: &str, default_value: bool) -> Field {
+    fn field(name: &str, default_value: bool) -> Field<'_> {
         let default_value = match default_value {
             false => None,
             true => Some(Expression::ConstUse(Name::simple("test"))),
         };
         Field {
             name,
-            field_type: Type {
+            field_type: Type::Named {
                 name: Name::simple("not important"),
                 is_collection: false,
             },
             default_value,
+            documentation: Documentation::default(),
         }
     }
 
@@ -355,7 +422,92 @@ mod struct_init_checker_tests {
         assert_eq!(check_res, Err(Error::FieldNotSupplied("field1".into())))
     }
 
-    fn parameter(label: &str) -> Parameter {
+    #[test]
+    fn test_base_of_different_type() {
+        let env = Environment::default();
+        let mut type_checker = TypeChecker::with_environment(&env);
+        let check_res = StructInitChecker::new(AssignableChecker(&mut type_checker))
+            .check_base(&Types::STRING, &Types::INT);
+        assert_eq!(
+            check_res,
+            Err(Error::BaseTypeMismatch {
+                expected: "String".into(),
+                found: "Int".into()
+            })
+        )
+    }
+
+    #[test]
+    fn test_overrides_after_base() {
+        let check_res = check_overrides(
+            vec![field("field1", false), field("field2", false)],
+            vec![Types::INT, Types::STRING],
+            vec![parameter("field2")],
+            vec![Types::STRING],
+        );
+        assert!(check_res.is_ok())
+    }
+
+    #[test]
+    fn test_overrides_unknown_field() {
+        let check_res = check_overrides(
+            vec![field("field1", false)],
+            vec![Types::INT],
+            vec![parameter("field2")],
+            vec![Types::INT],
+        );
+        assert_eq!(check_res, Err(Error::UnknownField("field2".into())))
+    }
+
+    #[test]
+    fn test_overrides_type_mismatches() {
+        let check_res = check_overrides(
+            vec![field("field1", false)],
+            vec![Types::INT],
+            vec![parameter("field1")],
+            vec![Types::STRING],
+        );
+        assert_eq!(
+            check_res,
+            Err(Error::TypeMismatch {
+                field: "field1".into(),
+                expected: "Int".into(),
+                found: "String".into()
+            })
+        )
+    }
+
+    #[test]
+    fn test_labelled_nested_field() {
+        let check_res = check_labelled_parameters(
+            vec![field("field1", false)],
+            vec![Types::INT],
+            vec![Parameter::Nested {
+                path: vec!["field1", "inner"],
+                content: Expression::ConstUse(Name::simple("test")),
+            }],
+            vec![Types::INT],
+        );
+        assert_eq!(check_res, Err(Error::NestedField("field1.inner".into())))
+    }
+
+    fn check_overrides(
+        fields: Vec<Field>,
+        field_types: Vec<Types>,
+        overrides: Vec<Parameter>,
+        parameter_types: Vec<Types>,
+    ) -> Result<(), Error> {
+        let env = Environment::default();
+        let mut type_checker = TypeChecker::with_environment(&env);
+        StructInitChecker::new(AssignableChecker(&mut type_checker)).check_overrides(
+            &overrides,
+            &parameter_types,
+            &fields,
+            &field_types,
+        )
+    }
+
+    fn parameter(label: &str) -> Parameter<'_> {
         Parameter::Labelled {
             label,
             content: Expression::ConstUse(Name::simple("test")),
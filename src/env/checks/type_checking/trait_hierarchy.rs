@@ -0,0 +1,159 @@
+use crate::ast::{ConstantDeclaration, Field, TraitDeclaration};
+use crate::env::checks::type_checking::types::Types;
+use crate::env::name_resolution::ResolveHelper;
+use crate::env::scope::ScopeId;
+use crate::env::{Environment, Resolved, TypedElement};
+use std::collections::HashSet;
+
+/// Traits listed after `:` in the trait declaration
+pub(in crate::env) fn super_traits<'ast, 'a, 'env>(
+    environment: &'env Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+) -> impl Iterator<Item = &'ast TraitDeclaration<'a>> + 'env {
+    r#trait
+        .super_traits
+        .iter()
+        .map(move |name| match environment.resolved_names.get(name) {
+            Some(Resolved::Trait(super_trait)) => *super_trait,
+            _ => panic!(
+                "`{name}` is not a trait, trait `{}` can only inherit traits",
+                r#trait.name
+            ),
+        })
+}
+
+/// The trait itself, followed by every trait it inherits directly or indirectly
+///
+/// # Note
+/// A trait inherited through different paths appears only once
+pub(in crate::env) fn hierarchy<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+) -> Vec<&'ast TraitDeclaration<'a>> {
+    let mut hierarchy = vec![];
+    visit(
+        environment,
+        r#trait,
+        &mut vec![],
+        &mut HashSet::new(),
+        &mut hierarchy,
+    );
+    hierarchy
+}
+
+fn visit<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+    inheriting: &mut Vec<*const TraitDeclaration<'a>>,
+    visited: &mut HashSet<*const TraitDeclaration<'a>>,
+    hierarchy: &mut Vec<&'ast TraitDeclaration<'a>>,
+) {
+    let address = r#trait as *const TraitDeclaration;
+    assert!(
+        !inheriting.contains(&address),
+        "Cycle detected in trait inheritance at `{}`",
+        r#trait.name
+    );
+    if !visited.insert(address) {
+        return;
+    }
+    hierarchy.push(r#trait);
+    inheriting.push(address);
+    for super_trait in super_traits(environment, r#trait) {
+        visit(environment, super_trait, inheriting, visited, hierarchy);
+    }
+    inheriting.pop();
+}
+
+/// Checks whether the module can refer to the trait, because it declares or imports the trait.
+/// Modules of the standard library cannot see traits declared outside of it
+pub(in crate::env) fn is_visible<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+    module_scope: ScopeId,
+) -> bool {
+    if is_std(environment, module_scope) {
+        return false;
+    }
+    let by_name = match ResolveHelper(environment).resolve(module_scope, r#trait.name) {
+        Some(Resolved::Trait(resolved)) => std::ptr::eq(resolved, r#trait),
+        _ => false,
+    };
+    // imported under a different name with `as`
+    by_name
+        || environment
+            .get_scope(module_scope)
+            .name_spaces
+            .declared
+            .values()
+            .any(|declared| declared == r#trait)
+}
+
+/// Checks whether the module is `std` or one of its sub modules
+fn is_std(environment: &Environment, module_scope: ScopeId) -> bool {
+    let mut modules = environment
+        .find_module(&["std"])
+        .into_iter()
+        .collect::<Vec<_>>();
+    while let Some(scope_id) = modules.pop() {
+        if scope_id == module_scope {
+            return true;
+        }
+        let name_spaces = &environment.get_scope(scope_id).name_spaces;
+        modules.extend(name_spaces.modules.values());
+    }
+    false
+}
+
+/// Checks whether the trait explicitly inherits the other trait, or is the same trait
+pub(in crate::env) fn inherits<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+    other: &'ast TraitDeclaration<'a>,
+) -> bool {
+    hierarchy(environment, r#trait)
+        .into_iter()
+        .any(|inherited| std::ptr::eq(inherited, other))
+}
+
+/// Fields required by the trait and all of its super traits
+pub(in crate::env) fn required_fields<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+) -> Vec<&'ast Field<'a>> {
+    hierarchy(environment, r#trait)
+        .into_iter()
+        .flat_map(|inherited| &inherited.required)
+        .collect()
+}
+
+/// Attributes provided by the trait and all of its super traits.
+/// An attribute declared closer to the trait overrides the inherited one with the same name
+pub(in crate::env) fn attributes<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    r#trait: &'ast TraitDeclaration<'a>,
+) -> Vec<&'ast ConstantDeclaration<'a>> {
+    let mut names = HashSet::new();
+    hierarchy(environment, r#trait)
+        .into_iter()
+        .filter_map(|inherited| inherited.body.as_ref())
+        .flat_map(|body| &body.attributes)
+        .filter(|attribute| names.insert(attribute.name))
+        .collect()
+}
+
+/// Finds a field or an attribute declared by the type,
+/// including the ones a trait inherits from its super traits
+pub(in crate::env) fn access<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    declared_type: &Types<'ast, 'a>,
+    name: &str,
+) -> Option<TypedElement<'ast, 'a>> {
+    let r#trait = match declared_type {
+        Types::Trait(r#trait) => *r#trait,
+        _ => return declared_type.access(name),
+    };
+    hierarchy(environment, r#trait)
+        .into_iter()
+        .find_map(|inherited| Types::Trait(inherited).access(name))
+}
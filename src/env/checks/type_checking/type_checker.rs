@@ -1,19 +1,24 @@
 use super::super::hash;
 use super::assignable_checker::AssignableChecker;
+use super::builtin_methods;
 use super::error::*;
 use super::essential_trait;
 use super::struct_init_checker::StructInitChecker;
+use super::trait_hierarchy;
 use super::type_resolver;
 use crate::ast::{
-    AbstractSyntaxTree, Accessor, Block, ConstantDeclaration, Declaration, Expression, Field, Name,
-    Parameter, Statement, StructDeclaration, StructInitContent, TraitDeclaration,
+    AbstractSyntaxTree, Accessor, ArithmeticOperator, Assertion, Block, ComparisonOperator,
+    ConstantDeclaration, Declaration, DestructuringDeclaration, Expression, Field, Match, Name,
+    Parameter, Pattern, Statement, StringPart, StructDeclaration, StructInitContent,
+    TraitDeclaration,
 };
-use crate::env::checks::type_checking::types::Types;
-use crate::env::environment::Resolved;
+use crate::env::checks::type_checking::types::{Primitive, Types};
+use crate::env::environment::{InheritedAttributes, Resolved};
 use crate::env::name_resolution::ResolveHelper;
 use crate::env::scope::ScopeId;
 use crate::env::Environment;
 use crate::env::TypedElement;
+use crate::tokenizer::{float_value, integer_value, LiteralKind};
 use std::collections::{HashMap, HashSet};
 
 hash!(Field);
@@ -25,6 +30,9 @@ pub(in crate::env) struct TypeChecker<'ast, 'a, 'env> {
     resolved_fields: HashMap<&'ast Field<'a>, Types<'ast, 'a>>,
     resolved_instance_fields: HashMap<Name<'a>, Types<'ast, 'a>>,
     checking_expression: HashSet<Name<'a>>,
+    inherited_attributes: InheritedAttributes<'ast, 'a>,
+    inheriting: HashSet<&'ast StructDeclaration<'a>>,
+    resolving_defaults: HashSet<&'ast Field<'a>>,
 }
 
 impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
@@ -35,15 +43,26 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
             resolved_fields: HashMap::new(),
             resolved_instance_fields: HashMap::new(),
             checking_expression: HashSet::new(),
+            inherited_attributes: HashMap::new(),
+            inheriting: HashSet::new(),
+            resolving_defaults: HashSet::new(),
         }
     }
 
-    pub fn check(mut self, syntax_trees: &'ast [AbstractSyntaxTree<'a>]) {
+    /// Checks every declaration, and returns the attributes structs inherit from traits
+    pub fn check(
+        mut self,
+        syntax_trees: &'ast [AbstractSyntaxTree<'a>],
+    ) -> InheritedAttributes<'ast, 'a> {
         for syntax_tree in syntax_trees {
             for declaration in &syntax_tree.compilation_unit.declarations {
                 self.resolve_declaration(declaration);
             }
         }
+        self.inherited_attributes
+            .into_iter()
+            .filter(|(_, attributes)| !attributes.is_empty())
+            .collect()
     }
 
     pub fn entry_check(&mut self) {
@@ -61,8 +80,16 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
             Declaration::Constant(constant) => {
                 self.resolve_expression(&constant.value);
             }
+            Declaration::Destructuring(destructuring) => {
+                self.resolve_destructuring(destructuring);
+            }
             Declaration::Struct(r#struct) => self.resolve_struct(r#struct),
             Declaration::Trait(r#trait) => self.resolve_trait(r#trait),
+            Declaration::Assertion(assertion) => self.resolve_assertion(assertion),
+            Declaration::TypeAlias(alias) => {
+                type_resolver::resolve_type(self.environment, &alias.aliased)
+                    .unwrap_or_else(|| panic!("Type `{}` is invalid", alias.aliased));
+            }
             Declaration::Import(_) => (), // do nothing for import
         }
     }
@@ -72,7 +99,7 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         expression: &'ast Expression<'a>,
     ) -> Types<'ast, 'a> {
         if let Some(resolved_type) = self.resolved_expressions.get(&expression) {
-            return *resolved_type;
+            return resolved_type.clone();
         }
         let resolve_type = match expression {
             Expression::Void => Types::VOID,
@@ -80,7 +107,7 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
             Expression::SelfRef(scope_id) => {
                 self.resolve_self(scope_id.expect("self scope not set"))
             }
-            Expression::Literal { kind, .. } => type_resolver::resolve_literal(kind),
+            Expression::Literal { kind, lexeme } => self.resolve_literal(kind, lexeme),
             Expression::Block(block) => self.resolve_block(block),
             Expression::StructInit {
                 name,
@@ -96,8 +123,41 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
                 field_names,
             } => self.resolve_field_access(receiver, field_names),
             Expression::Collection(elements) => self.resolve_collection_literal(elements),
+            Expression::Tuple(elements) => Types::Tuple(
+                elements
+                    .iter()
+                    .map(|element| self.resolve_expression(element))
+                    .collect(),
+            ),
+            Expression::Arithmetic {
+                operator,
+                left,
+                right,
+                ..
+            } => self.resolve_arithmetic(*operator, left, right),
+            Expression::Comparison {
+                operator,
+                left,
+                right,
+                ..
+            } => self.resolve_comparison(*operator, left, right),
+            Expression::InterpolatedString(parts) => self.resolve_interpolated_string(parts),
+            Expression::Index {
+                receiver,
+                index,
+                bracket,
+            } => self.resolve_index(receiver, index, bracket),
+            Expression::Slice {
+                receiver,
+                start,
+                end,
+                bracket,
+            } => self.resolve_slice(receiver, start.as_deref(), end.as_deref(), bracket),
+            Expression::Match(r#match) => self.resolve_match(r#match),
         };
-        let existing = self.resolved_expressions.insert(expression, resolve_type);
+        let existing = self
+            .resolved_expressions
+            .insert(expression, resolve_type.clone());
         debug_assert!(existing.is_none(), "Expression resolved twice");
         resolve_type
     }
@@ -116,14 +176,28 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
             Resolved::Constant(constant) => self.resolve_expression(&constant.value),
             Resolved::InstanceAccess(instance, fields) => {
                 if let Some(cached) = self.resolved_instance_fields.get(name) {
-                    *cached
+                    cached.clone()
                 } else {
-                    let resolved_type = self.resolve_from_instance_fields(instance, fields);
+                    let instance_type = self.resolve_expression(&instance.value);
+                    let resolved_type = self.resolve_accesses(instance_type, fields);
                     self.resolved_instance_fields
-                        .insert(name.clone(), resolved_type);
+                        .insert(name.clone(), resolved_type.clone());
                     resolved_type
                 }
             }
+            Resolved::Binding(destructuring, index) => self.resolve_binding(destructuring, *index),
+            Resolved::BindingAccess(destructuring, index, fields) => {
+                let binding_type = self.resolve_binding(destructuring, *index);
+                self.resolve_accesses(binding_type, fields)
+            }
+            Resolved::PatternBinding(r#match, index) => {
+                Types::Struct(self.pattern_struct(&r#match.arms[*index].pattern))
+            }
+            Resolved::PatternBindingAccess(r#match, index, fields) => {
+                let binding_type =
+                    Types::Struct(self.pattern_struct(&r#match.arms[*index].pattern));
+                self.resolve_accesses(binding_type, fields)
+            }
             Resolved::Module(_) => panic!("Cannot assign module `{}` to constant", name),
             Resolved::Struct(struct_type) => {
                 panic!("Cannot assign struct `{}` to constant", struct_type.name)
@@ -131,6 +205,9 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
             Resolved::Trait(trait_type) => {
                 panic!("Cannot assign trait `{}` to constant", trait_type.name)
             }
+            Resolved::TypeAlias(alias) => {
+                panic!("Cannot assign type `{}` to constant", alias.name)
+            }
         };
         self.checking_expression.remove(name);
         Some(resolved_type)
@@ -139,26 +216,173 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
     fn resolve_self(&self, scope: ScopeId) -> Types<'ast, 'a> {
         match ResolveHelper(self.environment).resolve(scope, "self") {
             Some(Resolved::Struct(struct_declaration)) => Types::Struct(struct_declaration),
-            _ => panic!("self can only be used in structs"),
+            Some(Resolved::Trait(trait_declaration)) => Types::Trait(trait_declaration),
+            _ => panic!("self can only be used in structs and traits"),
         }
     }
 
-    fn resolve_from_instance_fields(
+    fn resolve_binding(
         &mut self,
-        instance: &'ast ConstantDeclaration<'a>,
-        fields: &[&'a str],
+        destructuring: &'ast DestructuringDeclaration<'a>,
+        index: usize,
     ) -> Types<'ast, 'a> {
-        let mut current_type = self.resolve_expression(&instance.value);
-        for field in fields {
-            let access = current_type
-                .access(field)
-                .unwrap_or_else(|| panic!("Failed to find {}", field));
-            current_type = match access {
-                TypedElement::Field(field) => self.resolve_field(field),
-                TypedElement::Constant(constant) => self.resolve_expression(&constant.value),
-            };
+        self.resolve_destructuring(destructuring)
+            .element(index)
+            .expect("Destructuring size is checked")
+    }
+
+    fn resolve_destructuring(
+        &mut self,
+        destructuring: &'ast DestructuringDeclaration<'a>,
+    ) -> Types<'ast, 'a> {
+        let value_type = self.resolve_expression(&destructuring.value);
+        match &value_type {
+            Types::Tuple(elements) if elements.len() == destructuring.names.len() => value_type,
+            _ => panic!(
+                "Cannot destructure type `{}` into {} names: ({})",
+                value_type,
+                destructuring.names.len(),
+                destructuring.names.join(", ")
+            ),
         }
-        current_type
+    }
+
+    fn resolve_accesses(
+        &mut self,
+        receiver_type: Types<'ast, 'a>,
+        names: &[&'a str],
+    ) -> Types<'ast, 'a> {
+        let mut last_type = receiver_type;
+        for name in names {
+            last_type = self.resolve_access(&last_type, name);
+        }
+        last_type
+    }
+
+    /// Resolves the type of a field, an attribute, or a tuple element (like `pair.0`)
+    fn resolve_access(&mut self, receiver_type: &Types<'ast, 'a>, name: &str) -> Types<'ast, 'a> {
+        let tuple_element = name
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| receiver_type.element(index));
+        if let Some(element_type) = tuple_element {
+            return element_type;
+        }
+        match self.access(receiver_type, name) {
+            Some(TypedElement::Field(field)) => self.resolve_field(field),
+            Some(TypedElement::Constant(constant)) => self.resolve_expression(&constant.value),
+            None => panic!(
+                "{:?} has no field or attribute named {}",
+                receiver_type, name
+            ),
+        }
+    }
+
+    /// Finds a field or an attribute of the type, including the attributes a struct inherits
+    fn access(
+        &mut self,
+        receiver_type: &Types<'ast, 'a>,
+        name: &str,
+    ) -> Option<TypedElement<'ast, 'a>> {
+        trait_hierarchy::access(self.environment, receiver_type, name).or_else(|| {
+            match receiver_type {
+                Types::Struct(r#struct) => self
+                    .inherited_attributes(r#struct)
+                    .iter()
+                    .find(|attribute| attribute.name == name)
+                    .copied()
+                    .map(TypedElement::Constant),
+                _ => None,
+            }
+        })
+    }
+
+    fn inherited_attributes(
+        &mut self,
+        r#struct: &'ast StructDeclaration<'a>,
+    ) -> &[&'ast ConstantDeclaration<'a>] {
+        if !self.inherited_attributes.contains_key(r#struct) {
+            let inherited = self.inherit(r#struct);
+            self.inherited_attributes.insert(r#struct, inherited);
+        }
+        &self.inherited_attributes[r#struct]
+    }
+
+    /// A struct inherits the attributes of the most specific traits it conforms to among the ones
+    /// its module can refer to, unless it declares a field or an attribute with the same name.
+    /// The attributes are ordered as the traits are declared, then as declared in the trait
+    fn inherit(
+        &mut self,
+        r#struct: &'ast StructDeclaration<'a>,
+    ) -> Vec<&'ast ConstantDeclaration<'a>> {
+        assert!(
+            self.inheriting.insert(r#struct),
+            "Cycle detected when inheriting attributes for struct `{}`",
+            r#struct.name
+        );
+        let environment = self.environment;
+        let struct_type = Types::Struct(r#struct);
+        let module_scope = environment.struct_modules[r#struct];
+        let conforming = environment
+            .default_traits
+            .iter()
+            .copied()
+            .filter(|r#trait| trait_hierarchy::is_visible(environment, r#trait, module_scope))
+            .filter(|r#trait| AssignableChecker(self).check(&struct_type, &Types::Trait(r#trait)))
+            .collect::<Vec<_>>();
+        let mut inherited: Vec<&'ast ConstantDeclaration<'a>> = vec![];
+        for r#trait in conforming.iter().copied() {
+            let is_most_specific = !conforming.iter().any(|other| {
+                !std::ptr::eq(*other, r#trait)
+                    && trait_hierarchy::inherits(environment, other, r#trait)
+            });
+            if !is_most_specific {
+                continue;
+            }
+            for attribute in trait_hierarchy::attributes(environment, r#trait) {
+                if let Some(declared) = struct_type.access(attribute.name) {
+                    self.check_override(r#struct, declared, r#trait, attribute);
+                    continue;
+                }
+                match inherited
+                    .iter()
+                    .find(|existing| existing.name == attribute.name)
+                {
+                    Some(existing) => assert!(
+                        std::ptr::eq(*existing, attribute),
+                        "Struct `{}` inherits attribute `{}` from more than one trait",
+                        r#struct.name,
+                        attribute.name
+                    ),
+                    None => inherited.push(attribute),
+                }
+            }
+        }
+        self.inheriting.remove(r#struct);
+        inherited
+    }
+
+    fn check_override(
+        &mut self,
+        r#struct: &'ast StructDeclaration<'a>,
+        declared: TypedElement<'ast, 'a>,
+        r#trait: &'ast TraitDeclaration<'a>,
+        attribute: &'ast ConstantDeclaration<'a>,
+    ) {
+        let declared_type = match declared {
+            TypedElement::Field(field) => self.resolve_field(field),
+            TypedElement::Constant(constant) => self.resolve_expression(&constant.value),
+        };
+        let expected_type = self.resolve_expression(&attribute.value);
+        assert!(
+            AssignableChecker(self).check(&declared_type, &expected_type),
+            "`{}` in struct `{}` overrides attribute from trait `{}` with a different type.\nExpected: {}\nFound: {}",
+            attribute.name,
+            r#struct.name,
+            r#trait.name,
+            expected_type,
+            declared_type
+        );
     }
 
     fn resolve_block(&mut self, block: &'ast Block<'a>) -> Types<'ast, 'a> {
@@ -176,9 +400,25 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         parameters: &'ast [Parameter<'a>],
         init_content: &'ast Option<StructInitContent<'a>>,
     ) -> Types<'ast, 'a> {
+        if let Some(Resolved::TypeAlias(alias)) = self.environment.resolved_names.get(name) {
+            panic!(
+                "Type alias `{}` cannot create an instance, use `{}` instead",
+                alias.name, alias.aliased
+            )
+        }
         let struct_type = type_resolver::resolve_type_name(self.environment, name, false)
             .unwrap_or_else(|| panic!("type name `{}` not linked", name));
         let fields = struct_type.fields();
+        // a default value creating its own struct never ends if it needs the same default again
+        if let Some(field) = fields.iter().enumerate().find_map(|(index, field)| {
+            let is_provided = Self::is_provided(fields, index, parameters, init_content);
+            (self.resolving_defaults.contains(field) && !is_provided).then_some(field)
+        }) {
+            panic!(
+                "Default value of field `{}` cannot create `{}` recursively",
+                field.name, struct_type
+            )
+        }
         let field_types = fields
             .iter()
             .map(|field| self.resolve_field(field))
@@ -201,17 +441,43 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         } else {
             (fields, field_types.as_slice())
         };
-        StructInitChecker::new(AssignableChecker(self))
+        let mut struct_init_checker = StructInitChecker::new(AssignableChecker(self));
+        if let Some(Parameter::Spread(_)) = parameters.first() {
+            struct_init_checker
+                .check_base(&struct_type, &parameter_types[0])
+                .expect("Failed struct base type check");
+        }
+        struct_init_checker
             .check_parameters(parameters, parameter_types, fields, field_types)
             .expect("Failed struct field type check");
         struct_type
     }
 
+    /// Checks whether the struct init gives a value to the field at the index,
+    /// either through the parameters, the base instance or the init content
+    fn is_provided(
+        fields: &[Field<'a>],
+        index: usize,
+        parameters: &[Parameter<'a>],
+        init_content: &Option<StructInitContent<'a>>,
+    ) -> bool {
+        let by_parameters = match parameters.first() {
+            Some(Parameter::Spread(_)) => true,
+            Some(Parameter::Labelled { .. }) => parameters.iter().any(|parameter| {
+                matches!(parameter, Parameter::Labelled { label, .. } if *label == fields[index].name)
+            }),
+            _ => index < parameters.len(),
+        };
+        by_parameters || (init_content.is_some() && index + 1 == fields.len())
+    }
+
     fn check_can_have_init_content(&mut self, field_type: Option<&Types<'ast, 'a>>) -> Result<()> {
         match field_type {
             Some(declared_type @ (Types::StructCollection(_) | Types::TraitCollection(_))) => {
                 let render_trait = essential_trait::render(self.environment);
-                if !AssignableChecker(self).check(&(*declared_type).element_type(), &render_trait) {
+                if !AssignableChecker(self)
+                    .check(&declared_type.clone().element_type(), &render_trait)
+                {
                     return Err(Error::LastFieldIsNotRender);
                 }
                 Ok(())
@@ -232,26 +498,41 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         Ok(())
     }
 
+    fn chained_field(&self, owner_type: &Types<'ast, 'a>, name: &str) -> &'ast Field<'a> {
+        match trait_hierarchy::access(self.environment, owner_type, name) {
+            Some(TypedElement::Field(field)) => field,
+            _ => panic!(
+                "Field `{}` could not be found in type `{:?}`",
+                name, owner_type
+            ),
+        }
+    }
+
     fn resolve_chaining_method(
         &mut self,
         receiver: &'ast Expression<'a>,
         accessors: &'ast [Accessor<'a>],
     ) -> Types<'ast, 'a> {
         let receiver_type = self.resolve_expression(receiver);
+        if !matches!(receiver_type, Types::Struct(_) | Types::Trait(_)) {
+            return self.resolve_builtin_methods(receiver_type, accessors);
+        }
         for accessor in accessors {
-            let field = receiver_type.field(accessor.identifier).unwrap_or_else(|| {
-                panic!(
-                    "Field `{}` could not be found in type `{:?}`",
-                    accessor.identifier, receiver_type
-                )
-            });
+            let mut owner_type = receiver_type.clone();
+            for field_name in &accessor.path {
+                let field = self.chained_field(&owner_type, field_name);
+                owner_type = self.resolve_field(field);
+            }
+            let field = self.chained_field(&owner_type, accessor.identifier);
             let field_type = self.resolve_field(field);
             if let Some(value) = &accessor.value {
                 let argument_type = self.resolve_expression(value);
                 if !AssignableChecker(self).check(&argument_type, &field_type) {
                     panic!(
                         "Expect type: `{}`\nFound type: `{}`, on access .{}",
-                        field_type, argument_type, accessor.identifier
+                        type_resolver::type_name(self.environment, &field.field_type, &field_type),
+                        argument_type,
+                        accessor.identifier
                     );
                 }
             } else {
@@ -265,6 +546,70 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         receiver_type
     }
 
+    /// Each built-in method is applied to the output of the previous one
+    fn resolve_builtin_methods(
+        &mut self,
+        receiver_type: Types<'ast, 'a>,
+        accessors: &'ast [Accessor<'a>],
+    ) -> Types<'ast, 'a> {
+        let mut output_type = receiver_type;
+        for accessor in accessors {
+            let method = accessor.identifier;
+            let signature = builtin_methods::signature(&output_type, method).unwrap_or_else(|| {
+                panic!("Method `{method}` could not be found in type `{output_type}`")
+            });
+            let argument_type = accessor
+                .value
+                .as_ref()
+                .map(|value| self.resolve_expression(value));
+            match (&signature.argument, &argument_type) {
+                (Some(expected), Some(found)) => {
+                    let with_optional = signature
+                        .optional
+                        .map(|optional| Types::Tuple([expected.clone(), optional].into()));
+                    let is_accepted = AssignableChecker(self).check(found, expected)
+                        || with_optional
+                            .as_ref()
+                            .is_some_and(|expected| AssignableChecker(self).check(found, expected));
+                    let expected = match with_optional {
+                        Some(with_optional) => format!("{expected}` or `{with_optional}"),
+                        None => expected.to_string(),
+                    };
+                    assert!(
+                        is_accepted,
+                        "Expect type: `{expected}`\nFound type: `{found}`, on method .{method}"
+                    )
+                }
+                (Some(expected), None) => {
+                    panic!("Method `{method}` expects an argument of type `{expected}`")
+                }
+                (None, Some(_)) => panic!("Method `{method}` takes no argument"),
+                (None, None) => (),
+            }
+            if let (Types::Primitive(Primitive::Color), Some(argument)) =
+                (&output_type, &accessor.value)
+            {
+                Self::check_literal_ratio(method, argument);
+            }
+            output_type = signature.output;
+        }
+        output_type
+    }
+
+    /// Ratios given to color methods as literals are checked before evaluation
+    fn check_literal_ratio(method: &str, argument: &'ast Expression<'a>) {
+        if let Some(Expression::Literal {
+            kind: LiteralKind::Floating,
+            lexeme,
+        }) = builtin_methods::ratio_argument(method, argument)
+        {
+            assert!(
+                (0.0..=1.0).contains(&float_value(lexeme)),
+                "Argument of method `{method}` should be between 0 and 1, found {lexeme}"
+            )
+        }
+    }
+
     fn resolve_statement(&mut self, statement: &'ast Statement<'a>) -> Types<'ast, 'a> {
         match statement {
             Statement::Expression(expression) => self.resolve_expression(expression),
@@ -280,35 +625,63 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
             for attribute in &body.attributes {
                 self.resolve_expression(&attribute.value);
             }
+            for assertion in &body.assertions {
+                self.resolve_assertion(assertion);
+            }
         }
+        self.inherited_attributes(r#struct);
+    }
+
+    fn resolve_assertion(&mut self, assertion: &'ast Assertion<'a>) {
+        let condition_type = self.resolve_expression(&assertion.condition);
+        assert!(
+            condition_type == Types::BOOL,
+            "Assertion condition should be `Bool`, found `{condition_type}`"
+        );
+        let message_type = self.resolve_expression(&assertion.message);
+        assert!(
+            message_type == Types::STRING,
+            "Assertion message should be `String`, found `{message_type}`"
+        );
     }
 
     fn resolve_trait(&mut self, r#trait: &'ast TraitDeclaration<'a>) {
         for field in &r#trait.required {
             self.resolve_field(field);
         }
+        // super traits need to be traits without cyclic inheritance
+        trait_hierarchy::hierarchy(self.environment, r#trait);
+        if let Some(body) = &r#trait.body {
+            for attribute in &body.attributes {
+                self.resolve_expression(&attribute.value);
+            }
+        }
     }
 
     fn resolve_field(&mut self, field: &'ast Field<'a>) -> Types<'ast, 'a> {
         if let Some(resolved_type) = self.resolved_fields.get(&field) {
-            return *resolved_type;
+            return resolved_type.clone();
+        }
+        let expected_type = type_resolver::resolve_type(self.environment, &field.field_type)
+            .unwrap_or_else(|| panic!("Field type `{}` is invalid", field.field_type));
+        if self.resolving_defaults.contains(field) {
+            // the default value is being checked, and it creates the struct with this field given
+            return expected_type;
         }
-        let expected_type = type_resolver::resolve_type_name(
-            self.environment,
-            &field.field_type.name,
-            field.field_type.is_collection,
-        )
-        .unwrap_or_else(|| panic!("Field type `{}` is invalid", field.field_type.name));
         if let Some(default_value) = &field.default_value {
+            self.resolving_defaults.insert(field);
             let value_type = self.resolve_expression(default_value);
+            self.resolving_defaults.remove(field);
             if !AssignableChecker(self).check(&value_type, &expected_type) {
                 panic!(
                     "Default value for field `{}` has a different type.\nExpected: {}\nFound: {}\n",
-                    field.name, expected_type, value_type
+                    field.name,
+                    type_resolver::type_name(self.environment, &field.field_type, &expected_type),
+                    value_type
                 )
             }
         }
-        let existing = self.resolved_fields.insert(field, expected_type);
+        let existing = self.resolved_fields.insert(field, expected_type.clone());
         debug_assert!(
             existing.is_none(),
             "Duplicated field resolution: {}",
@@ -323,15 +696,7 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         name: &[&'a str],
     ) -> Types<'ast, 'a> {
         let receiver_type = self.resolve_expression(receiver);
-        let mut last_type = receiver_type;
-        for name in name {
-            last_type = match last_type.access(name) {
-                Some(TypedElement::Field(field)) => self.resolve_field(field),
-                Some(TypedElement::Constant(constant)) => self.resolve_expression(&constant.value),
-                None => panic!("{:?} has no field or attribute named {}", last_type, name),
-            };
-        }
-        last_type
+        self.resolve_accesses(receiver_type, name)
     }
 
     fn resolve_collection_literal(&mut self, elements: &'ast [Expression<'a>]) -> Types<'ast, 'a> {
@@ -345,7 +710,319 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         if let Some(unmatched_type) = element_type.find(|element| element != &expected_type) {
             panic!("Collection literal expects type {expected_type}, but found {unmatched_type}")
         }
-        expected_type.collection_type()
+        expected_type.clone().collection_type().unwrap_or_else(|| {
+            panic!("Collections of tuples are not supported, found `{expected_type}`")
+        })
+    }
+
+    fn resolve_interpolated_string(&mut self, parts: &'ast [StringPart<'a>]) -> Types<'ast, 'a> {
+        for expression in parts.iter().filter_map(StringPart::expression) {
+            let interpolated_type = self.resolve_expression(expression);
+            assert!(
+                matches!(
+                    interpolated_type,
+                    Types::Primitive(
+                        Primitive::String | Primitive::Int | Primitive::Float | Primitive::Bool
+                    )
+                ),
+                "Only `String`, `Int`, `Float` and `Bool` can be interpolated in strings, found `{interpolated_type}`"
+            );
+        }
+        Types::STRING
+    }
+
+    fn resolve_index(
+        &mut self,
+        receiver: &'ast Expression<'a>,
+        index: &'ast Expression<'a>,
+        bracket: &'a str,
+    ) -> Types<'ast, 'a> {
+        let collection_type = self.resolve_indexed_collection(receiver);
+        self.resolve_position(index);
+        if let (Some(length), Some(index)) = (self.static_length(receiver), static_position(index))
+        {
+            if !(0..length as isize).contains(&index) {
+                let location = self.locate(bracket);
+                panic!(
+                    "{location}: Index {index} is out of bounds for collection of length {length}"
+                )
+            }
+        }
+        collection_type.element_type()
+    }
+
+    fn resolve_slice(
+        &mut self,
+        receiver: &'ast Expression<'a>,
+        start: Option<&'ast Expression<'a>>,
+        end: Option<&'ast Expression<'a>>,
+        bracket: &'a str,
+    ) -> Types<'ast, 'a> {
+        let collection_type = self.resolve_indexed_collection(receiver);
+        for bound in start.iter().chain(&end) {
+            self.resolve_position(bound);
+        }
+        if let Some(length) = self.static_length(receiver) {
+            let start = start.map_or(Some(0), static_position);
+            let end = end.map_or(Some(length as isize), static_position);
+            if let (Some(start), Some(end)) = (start, end) {
+                if !(0 <= start && start <= end && end <= length as isize) {
+                    let location = self.locate(bracket);
+                    panic!("{location}: Slice {start}..{end} is out of bounds for collection of length {length}")
+                }
+            }
+        }
+        collection_type
+    }
+
+    fn resolve_indexed_collection(&mut self, receiver: &'ast Expression<'a>) -> Types<'ast, 'a> {
+        let collection_type = self.resolve_expression(receiver);
+        assert!(
+            collection_type.is_collection(),
+            "Only collections can be indexed, found `{collection_type}`"
+        );
+        collection_type
+    }
+
+    fn resolve_position(&mut self, position: &'ast Expression<'a>) {
+        let position_type = self.resolve_expression(position);
+        assert_eq!(
+            position_type,
+            Types::INT,
+            "Collection index should be `Int`, found `{position_type}`"
+        );
+    }
+
+    /// Number of elements in the collection if it is known at compile time
+    fn static_length(&self, collection: &'ast Expression<'a>) -> Option<usize> {
+        match collection {
+            Expression::Collection(elements) => Some(elements.len()),
+            Expression::ConstUse(name) => match self.environment.resolved_names.get(name)? {
+                Resolved::Constant(constant) => self.static_length(&constant.value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn resolve_comparison(
+        &mut self,
+        operator: ComparisonOperator,
+        left: &'ast Expression<'a>,
+        right: &'ast Expression<'a>,
+    ) -> Types<'ast, 'a> {
+        let left_type = self.resolve_expression(left);
+        let right_type = self.resolve_expression(right);
+        let is_comparable = match left_type {
+            Types::Primitive(Primitive::Int | Primitive::Float | Primitive::Length) => true,
+            Types::Primitive(Primitive::Bool | Primitive::String | Primitive::Color) => {
+                !operator.is_ordering()
+            }
+            _ => false,
+        };
+        assert!(
+            is_comparable && left_type == right_type,
+            "Cannot apply `{operator}` to `{left_type}` and `{right_type}`"
+        );
+        Types::BOOL
+    }
+
+    /// Every arm needs to have the same type,
+    /// and the arms together need to cover every value of the subject
+    fn resolve_match(&mut self, r#match: &'ast Match<'a>) -> Types<'ast, 'a> {
+        let subject_type = self.resolve_expression(&r#match.subject);
+        let mut arm_types = vec![];
+        let mut is_exhaustive = false;
+        let mut matched_booleans = HashSet::new();
+        for arm in &r#match.arms {
+            assert!(
+                !is_exhaustive,
+                "Match arm is unreachable, the arms before it cover every value"
+            );
+            is_exhaustive = match &arm.pattern {
+                Pattern::Wildcard => true,
+                Pattern::Literal { kind, lexeme } => {
+                    let pattern_type = self.resolve_literal(kind, lexeme);
+                    Self::check_value_pattern(lexeme, &pattern_type, &subject_type);
+                    if pattern_type == Types::BOOL {
+                        matched_booleans.insert(*lexeme);
+                    }
+                    matched_booleans.len() == 2
+                }
+                Pattern::Name(name) if self.is_struct_pattern(name) => {
+                    self.check_struct_pattern(&arm.pattern, &subject_type)
+                }
+                Pattern::Name(name) => {
+                    let pattern_type = self.resolve_from_constant_use_name(name);
+                    assert!(
+                        matches!(pattern_type, Types::Primitive(primitive) if primitive != Primitive::Void),
+                        "Only constants of primitive types can be used as patterns, `{name}` is `{pattern_type}`"
+                    );
+                    Self::check_value_pattern(name, &pattern_type, &subject_type);
+                    false
+                }
+                Pattern::Binding { .. } => self.check_struct_pattern(&arm.pattern, &subject_type),
+            };
+            let arm_type = self.resolve_expression(&arm.value);
+            if !arm_types.contains(&arm_type) {
+                arm_types.push(arm_type);
+            }
+        }
+        assert!(
+            is_exhaustive,
+            "Match on `{subject_type}` is not exhaustive, add a `_` arm"
+        );
+        self.common_type(&arm_types, self.environment.match_modules[r#match])
+    }
+
+    /// Finds the type every arm of a match can be assigned to, which is the type of one of the arms
+    /// or else a trait of the match's module all of them conform to, the most specific one when
+    /// there are several (traits with the same requirements conform to each other, so the first
+    /// declared one is kept)
+    fn common_type(
+        &mut self,
+        arm_types: &[Types<'ast, 'a>],
+        module_scope: ScopeId,
+    ) -> Types<'ast, 'a> {
+        let is_common = |checker: &mut Self, candidate: &Types<'ast, 'a>| {
+            arm_types
+                .iter()
+                .all(|arm_type| AssignableChecker(checker).check(arm_type, candidate))
+        };
+        if let Some(common) = arm_types
+            .iter()
+            .find(|candidate| is_common(self, candidate))
+        {
+            return common.clone();
+        }
+        let environment = self.environment;
+        let common_traits = environment
+            .traits
+            .iter()
+            .copied()
+            .filter(|r#trait| trait_hierarchy::is_visible(environment, r#trait, module_scope))
+            .map(Types::Trait)
+            .filter(|candidate| is_common(self, candidate))
+            .collect::<Vec<_>>();
+        let most_specific = common_traits
+            .iter()
+            .filter(|candidate| {
+                common_traits
+                    .iter()
+                    .all(|other| AssignableChecker(self).check(candidate, other))
+            })
+            .collect::<Vec<_>>();
+        let arm_types = arm_types.iter().map(Types::to_string).collect::<Vec<_>>();
+        match (most_specific.as_slice(), common_traits.as_slice()) {
+            ([r#trait, ..], _) => (*r#trait).clone(),
+            (_, []) => panic!(
+                "Match arms should have a common type, found `{}`",
+                arm_types.join("`, `")
+            ),
+            (_, common_traits) => {
+                let mut common_traits = common_traits
+                    .iter()
+                    .map(Types::to_string)
+                    .collect::<Vec<_>>();
+                common_traits.sort();
+                panic!(
+                    "Match arms of types `{}` conform to several unrelated traits `{}`",
+                    arm_types.join("`, `"),
+                    common_traits.join("`, `")
+                )
+            }
+        }
+    }
+
+    fn check_value_pattern(
+        pattern: impl std::fmt::Display,
+        pattern_type: &Types<'ast, 'a>,
+        subject_type: &Types<'ast, 'a>,
+    ) {
+        assert_eq!(
+            pattern_type, subject_type,
+            "Pattern `{pattern}` of type `{pattern_type}` cannot match a value of type `{subject_type}`"
+        );
+    }
+
+    fn is_struct_pattern(&self, name: &Name<'a>) -> bool {
+        matches!(
+            self.environment.resolved_names.get(name),
+            Some(Resolved::Struct(_) | Resolved::Trait(_))
+        )
+    }
+
+    /// Checks the struct of the pattern conforms to the subject type,
+    /// returns whether the pattern matches every value of the subject
+    fn check_struct_pattern(
+        &mut self,
+        pattern: &'ast Pattern<'a>,
+        subject_type: &Types<'ast, 'a>,
+    ) -> bool {
+        let pattern_type = Types::Struct(self.pattern_struct(pattern));
+        assert!(
+            AssignableChecker(self).check(&pattern_type, subject_type),
+            "Struct `{pattern_type}` can never match a value of type `{subject_type}`"
+        );
+        &pattern_type == subject_type
+    }
+
+    /// The struct matched by a struct pattern like `Figure` or `figure: Figure`
+    fn pattern_struct(&self, pattern: &'ast Pattern<'a>) -> &'ast StructDeclaration<'a> {
+        let name = pattern.name().expect("Struct patterns have names");
+        match self.environment.resolved_names.get(name) {
+            Some(Resolved::Struct(r#struct)) => r#struct,
+            Some(Resolved::Trait(r#trait)) => panic!(
+                "Only structs can be matched by type, `{}` is a trait",
+                r#trait.name
+            ),
+            _ => panic!("Only structs can be matched by type, found `{name}`"),
+        }
+    }
+
+    /// Literals that do not fit in their type are reported where they are written
+    fn resolve_literal(&self, kind: &LiteralKind, lexeme: &'a str) -> Types<'ast, 'a> {
+        let literal = match kind {
+            LiteralKind::Integer | LiteralKind::Binary | LiteralKind::Octal | LiteralKind::Hex
+                if integer_value(*kind, lexeme).is_err() =>
+            {
+                Some("Integer")
+            }
+            LiteralKind::Floating if !float_value(lexeme).is_finite() => Some("Floating"),
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            let location = self.locate(lexeme);
+            panic!("{location}: {literal} literal ({lexeme}) is out of range")
+        }
+        type_resolver::resolve_literal(kind)
+    }
+
+    /// Where the lexeme is written, for diagnostics
+    fn locate(&self, lexeme: &'a str) -> String {
+        self.environment
+            .sources
+            .locate(lexeme)
+            .map_or_else(|| "<unknown>".to_string(), |location| location.to_string())
+    }
+
+    fn resolve_arithmetic(
+        &mut self,
+        operator: ArithmeticOperator,
+        left: &'ast Expression<'a>,
+        right: &'ast Expression<'a>,
+    ) -> Types<'ast, 'a> {
+        let left_type = self.resolve_expression(left);
+        let right_type = self.resolve_expression(right);
+        let is_number = matches!(
+            left_type,
+            Types::Primitive(Primitive::Int | Primitive::Float | Primitive::Length)
+        );
+        assert!(
+            is_number && left_type == right_type,
+            "Cannot apply `{operator}` to `{left_type}` and `{right_type}`"
+        );
+        left_type
     }
 }
 
@@ -355,3 +1032,11 @@ impl<'ast, 'a, 'env> TypeChecker<'ast, 'a, 'env> {
         self.resolve_expression(expression)
     }
 }
+
+/// Value of the index if it is an integer literal
+fn static_position(position: &Expression) -> Option<isize> {
+    match position {
+        Expression::Literal { kind, lexeme } => integer_value(*kind, lexeme).ok(),
+        _ => None,
+    }
+}
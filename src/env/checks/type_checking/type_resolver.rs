@@ -1,30 +1,108 @@
-use crate::ast::Name;
+use crate::ast::{Name, Type, TypeAlias};
 use crate::env::checks::type_checking::types::Types;
 use crate::env::Environment;
 use crate::env::Resolved;
 use crate::tokenizer::LiteralKind;
 
+pub(in crate::env) fn resolve_type<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    declared_type: &'ast Type<'a>,
+) -> Option<Types<'ast, 'a>> {
+    expand_type(environment, declared_type, &mut vec![])
+}
+
+/// Resolves a type name, expanding type aliases to the types they stand for
 pub(in crate::env) fn resolve_type_name<'ast, 'a>(
     environment: &Environment<'ast, 'a>,
     name: &'ast Name<'a>,
     is_collection: bool,
 ) -> Option<Types<'ast, 'a>> {
-    environment
-        .resolved_names
-        .get(name)
-        .map(|resolved| match resolved {
-            Resolved::Struct(r#struct) => Types::Struct(r#struct),
-            Resolved::Trait(r#trait) => Types::Trait(r#trait),
-            _ => panic!("Type is not valid"),
-        })
-        .or_else(|| primitive_type(name))
-        .map(|found_type| {
-            if is_collection {
-                found_type.collection_type()
-            } else {
-                found_type
-            }
-        })
+    expand_type_name(environment, name, is_collection, &mut vec![])
+}
+
+/// Name of a declared type for messages, keeping the name of a type alias instead of its expansion,
+/// also within tuples
+pub(in crate::env) fn type_name<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    declared_type: &'ast Type<'a>,
+    resolved_type: &Types<'ast, 'a>,
+) -> String {
+    match (declared_type, resolved_type) {
+        (Type::Named { name, .. }, _)
+            if matches!(
+                environment.resolved_names.get(name),
+                Some(Resolved::TypeAlias(_))
+            ) =>
+        {
+            declared_type.to_string()
+        }
+        (Type::Tuple(declared_elements), Types::Tuple(resolved_elements)) => {
+            let element_names = declared_elements
+                .iter()
+                .zip(resolved_elements.iter())
+                .map(|(declared, resolved)| type_name(environment, declared, resolved))
+                .collect::<Vec<_>>();
+            format!("({})", element_names.join(", "))
+        }
+        _ => resolved_type.to_string(),
+    }
+}
+
+fn expand_type<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    declared_type: &'ast Type<'a>,
+    expanding: &mut Vec<&'ast TypeAlias<'a>>,
+) -> Option<Types<'ast, 'a>> {
+    match declared_type {
+        Type::Named {
+            name,
+            is_collection,
+        } => expand_type_name(environment, name, *is_collection, expanding),
+        Type::Tuple(element_types) => element_types
+            .iter()
+            .map(|element_type| expand_type(environment, element_type, expanding))
+            .collect::<Option<_>>()
+            .map(Types::Tuple),
+    }
+}
+
+/// Expands the name, where `expanding` holds the aliases being expanded to detect cycles
+fn expand_type_name<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    name: &'ast Name<'a>,
+    is_collection: bool,
+    expanding: &mut Vec<&'ast TypeAlias<'a>>,
+) -> Option<Types<'ast, 'a>> {
+    let found_type = match environment.resolved_names.get(name) {
+        Some(Resolved::Struct(r#struct)) => Types::Struct(r#struct),
+        Some(Resolved::Trait(r#trait)) => Types::Trait(r#trait),
+        Some(Resolved::TypeAlias(alias)) => {
+            assert!(
+                !expanding.iter().any(|seen| std::ptr::eq(*seen, *alias)),
+                "Type alias `{}` refers to itself",
+                alias.name
+            );
+            expanding.push(alias);
+            let aliased = expand_type(environment, &alias.aliased, expanding)?;
+            expanding.pop();
+            aliased
+        }
+        Some(_) => panic!("Type is not valid"),
+        None => primitive_type(name)?,
+    };
+    if !is_collection {
+        return Some(found_type);
+    }
+    assert!(
+        !found_type.is_collection(),
+        "Collections of collections are not supported, found `[{name}]`"
+    );
+    let collection_type = found_type.collection_type();
+    assert!(
+        collection_type.is_some(),
+        "Collections of tuples are not supported, found `[{name}]`"
+    );
+    collection_type
 }
 
 fn primitive_type<'ast, 'a>(name: &Name) -> Option<Types<'ast, 'a>> {
@@ -35,12 +113,16 @@ fn primitive_type<'ast, 'a>(name: &Name) -> Option<Types<'ast, 'a>> {
         Moniker::Simple("String") => Some(Types::STRING),
         Moniker::Simple("Bool") => Some(Types::BOOL),
         Moniker::Simple("Void") => Some(Types::VOID),
+        Moniker::Simple("Length") => Some(Types::LENGTH),
+        Moniker::Simple("Color") => Some(Types::COLOR),
         Moniker::Qualified(full_name) => match *full_name.as_ref() {
             ["std", "Int"] => Some(Types::INT),
             ["std", "Float"] => Some(Types::INT),
             ["std", "String"] => Some(Types::INT),
             ["std", "Bool"] => Some(Types::INT),
             ["std", "Void"] => Some(Types::VOID),
+            ["std", "Length"] => Some(Types::LENGTH),
+            ["std", "Color"] => Some(Types::COLOR),
             _ => None,
         },
         _ => None,
@@ -49,9 +131,16 @@ fn primitive_type<'ast, 'a>(name: &Name) -> Option<Types<'ast, 'a>> {
 
 pub(in crate::env) fn resolve_literal<'ast, 'a>(literal_kind: &LiteralKind) -> Types<'ast, 'a> {
     match literal_kind {
-        LiteralKind::Binary | LiteralKind::Hex | LiteralKind::Integer => Types::INT,
+        LiteralKind::Binary | LiteralKind::Octal | LiteralKind::Hex | LiteralKind::Integer => {
+            Types::INT
+        }
         LiteralKind::Boolean => Types::BOOL,
         LiteralKind::Floating => Types::FLOAT,
         LiteralKind::String => Types::STRING,
+        LiteralKind::Length => Types::LENGTH,
+        LiteralKind::Color => Types::COLOR,
+        LiteralKind::StringHead | LiteralKind::StringMiddle | LiteralKind::StringTail => {
+            unreachable!("String parts are resolved within interpolated strings")
+        }
     }
 }
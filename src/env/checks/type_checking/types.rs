@@ -1,8 +1,9 @@
 use crate::ast::{ConstantDeclaration, Field, StructDeclaration, TraitDeclaration};
 use crate::env::TypedElement;
 use std::fmt::{Display, Formatter};
+use std::rc::Rc;
 
-#[derive(Copy, Clone, Debug, Eq)]
+#[derive(Clone, Debug, Eq)]
 pub enum Types<'ast, 'a> {
     Primitive(Primitive),
     Struct(&'ast StructDeclaration<'a>),
@@ -11,6 +12,10 @@ pub enum Types<'ast, 'a> {
     PrimitiveCollection(Primitive),
     StructCollection(&'ast StructDeclaration<'a>),
     TraitCollection(&'ast TraitDeclaration<'a>),
+    /// Collection whose elements are collections, only created by collection literals
+    NestedCollection(Rc<Types<'ast, 'a>>),
+
+    Tuple(Rc<[Types<'ast, 'a>]>),
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -20,6 +25,8 @@ pub enum Primitive {
     Float,
     Bool,
     String,
+    Length,
+    Color,
 }
 
 impl<'ast, 'a> PartialEq for Types<'ast, 'a> {
@@ -39,6 +46,10 @@ impl<'ast, 'a> PartialEq for Types<'ast, 'a> {
             | (TraitCollection(self_trait), TraitCollection(other_trait)) => {
                 std::ptr::eq(*self_trait, *other_trait)
             }
+            (NestedCollection(self_element), NestedCollection(other_element)) => {
+                self_element == other_element
+            }
+            (Tuple(self_elements), Tuple(other_elements)) => self_elements == other_elements,
             _ => false,
         }
     }
@@ -50,6 +61,8 @@ impl<'ast, 'a> Types<'ast, 'a> {
     pub const INT: Self = Types::Primitive(Primitive::Int);
     pub const FLOAT: Self = Types::Primitive(Primitive::Float);
     pub const BOOL: Self = Types::Primitive(Primitive::Bool);
+    pub const LENGTH: Self = Types::Primitive(Primitive::Length);
+    pub const COLOR: Self = Types::Primitive(Primitive::Color);
 
     pub fn access(&self, name: &str) -> Option<TypedElement<'ast, 'a>> {
         self.field(name)
@@ -68,16 +81,24 @@ impl<'ast, 'a> Types<'ast, 'a> {
     }
 
     pub fn attribute(&self, name: &str) -> Option<&'ast ConstantDeclaration<'a>> {
-        let struct_body = match self {
+        let body = match self {
             Types::Struct(r#struct) => r#struct.body.as_ref()?,
+            Types::Trait(r#trait) => r#trait.body.as_ref()?,
             _ => None?,
         };
-        struct_body
-            .attributes
+        body.attributes
             .iter()
             .find(|constant| constant.name == name)
     }
 
+    /// Type of the tuple element at the given position
+    pub fn element(&self, index: usize) -> Option<Self> {
+        match self {
+            Types::Tuple(elements) => elements.get(index).cloned(),
+            _ => None,
+        }
+    }
+
     pub fn fields(&self) -> &'ast [Field<'a>] {
         match self {
             Self::Struct(struct_declaration) => &struct_declaration.fields,
@@ -97,26 +118,45 @@ impl<'ast, 'a> Display for Types<'ast, 'a> {
             Self::TraitCollection(r#trait) => write!(f, "[{}]", r#trait.name),
             Self::StructCollection(r#struct) => write!(f, "[{}]", r#struct.name),
             Self::PrimitiveCollection(primitive) => write!(f, "[{:?}]", primitive),
+            Self::NestedCollection(element) => write!(f, "[{}]", element),
+
+            Self::Tuple(elements) => {
+                let elements = elements.iter().map(Types::to_string).collect::<Vec<_>>();
+                write!(f, "({})", elements.join(", "))
+            }
         }
     }
 }
 
 // Array related
 impl<'ast, 'a> Types<'ast, 'a> {
-    pub fn collection_type(self) -> Self {
+    /// Type of a collection of this type, or `None` for tuples which cannot be collected
+    pub fn collection_type(self) -> Option<Self> {
         match self {
-            Self::Primitive(primitive) => Self::PrimitiveCollection(primitive),
-            Self::Struct(r#struct) => Self::StructCollection(r#struct),
-            Self::Trait(r#trait) => Self::TraitCollection(r#trait),
-            _ => self,
+            Self::Primitive(primitive) => Some(Self::PrimitiveCollection(primitive)),
+            Self::Struct(r#struct) => Some(Self::StructCollection(r#struct)),
+            Self::Trait(r#trait) => Some(Self::TraitCollection(r#trait)),
+            Self::Tuple(_) => None,
+            collection => Some(Self::NestedCollection(Rc::new(collection))),
         }
     }
 
+    pub fn is_collection(&self) -> bool {
+        matches!(
+            self,
+            Self::PrimitiveCollection(_)
+                | Self::StructCollection(_)
+                | Self::TraitCollection(_)
+                | Self::NestedCollection(_)
+        )
+    }
+
     pub fn element_type(self) -> Self {
         match self {
             Self::PrimitiveCollection(primitive) => Self::Primitive(primitive),
             Self::StructCollection(r#struct) => Self::Struct(r#struct),
             Self::TraitCollection(r#trait) => Self::Trait(r#trait),
+            Self::NestedCollection(element) => Rc::unwrap_or_clone(element),
             _ => self,
         }
     }
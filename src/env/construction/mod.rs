@@ -20,5 +20,5 @@ pub(in crate::env) fn generate_scope<'ast, 'a>(
     syntax_trees: &mut [AbstractSyntaxTree<'a>],
     module_paths: &[Vec<&'a str>],
 ) {
-    scope_operations::ScopeGenerator(environment).generate(syntax_trees, module_paths)
+    scope_operations::ScopeGenerator::new(environment).generate(syntax_trees, module_paths)
 }
@@ -1,16 +1,27 @@
+use super::super::declaration_resolution;
 use super::super::scope::*;
 use super::subdivide_struct_init;
 use super::Environment;
 use crate::ast::{
-    AbstractSyntaxTree, ConstantDeclaration, Declaration, Expression, Parameter, Statement,
-    StructDeclaration, TraitDeclaration,
+    AbstractSyntaxTree, Assertion, ConstantDeclaration, Declaration, DestructuringDeclaration,
+    Expression, Parameter, Statement, StringPart, StructDeclaration, TraitDeclaration, TypeAlias,
 };
+use std::collections::HashMap;
 
-pub(in crate::env::construction) struct ScopeGenerator<'ast, 'a, 'env>(
-    pub &'env mut Environment<'ast, 'a>,
-);
+pub(in crate::env::construction) struct ScopeGenerator<'ast, 'a, 'env> {
+    environment: &'env mut Environment<'ast, 'a>,
+    /// Paths of the modules imported by the file being generated, by their imported names
+    imported_modules: HashMap<&'a str, Vec<&'a str>>,
+}
 
 impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
+    pub fn new(environment: &'env mut Environment<'ast, 'a>) -> Self {
+        Self {
+            environment,
+            imported_modules: HashMap::new(),
+        }
+    }
+
     pub fn generate(
         mut self,
         syntax_trees: &mut [AbstractSyntaxTree<'a>],
@@ -18,9 +29,14 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
     ) {
         for (syntax_tree, module_path) in syntax_trees.iter_mut().zip(module_paths.iter()) {
             let module_scope = self
-                .0
+                .environment
                 .find_module(module_path)
                 .unwrap_or_else(|| panic!("Failed to find module `{}`", module_path.join(".")));
+            self.imported_modules = declaration_resolution::imported_modules(
+                self.environment,
+                syntax_tree,
+                module_path,
+            );
             self.generate_scopes(syntax_tree, module_scope)
         }
     }
@@ -38,10 +54,15 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
     fn generate_for_declaration(&mut self, declaration: &mut Declaration<'a>, scope_id: ScopeId) {
         match declaration {
             Declaration::Constant(constant) => self.generate_for_constant(constant, scope_id),
+            Declaration::Destructuring(destructuring) => {
+                self.generate_for_destructuring(destructuring, scope_id)
+            }
             Declaration::Struct(r#struct) => {
                 self.generate_for_struct_declaration(r#struct, scope_id)
             }
             Declaration::Trait(r#trait) => self.generate_for_trait_declaration(r#trait, scope_id),
+            Declaration::Assertion(assertion) => self.generate_for_assertion(assertion, scope_id),
+            Declaration::TypeAlias(alias) => self.generate_for_type_alias(alias, scope_id),
             Declaration::Import(_) => (), // import does not need a scope
         }
     }
@@ -50,8 +71,21 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
         self.generate_for_expression(&mut constant.value, scope_id)
     }
 
+    fn generate_for_assertion(&mut self, assertion: &mut Assertion<'a>, scope_id: ScopeId) {
+        self.generate_for_expression(&mut assertion.condition, scope_id);
+        self.generate_for_expression(&mut assertion.message, scope_id)
+    }
+
+    fn generate_for_destructuring(
+        &mut self,
+        destructuring: &mut DestructuringDeclaration<'a>,
+        scope_id: ScopeId,
+    ) {
+        self.generate_for_expression(&mut destructuring.value, scope_id)
+    }
+
     fn generate_for_expression(&mut self, expression: &mut Expression<'a>, scope_id: ScopeId) {
-        subdivide_struct_init::subdivide(self.0, expression);
+        subdivide_struct_init::subdivide(self.environment, &self.imported_modules, expression);
         match expression {
             Expression::ConstUse(constant_name) => {
                 constant_name.set_scope(scope_id);
@@ -68,7 +102,7 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
                     .iter_mut()
                     .for_each(|parameter| self.generate_for_parameter(parameter, scope_id));
                 if let Some(init_content) = init_content {
-                    let body_scope_id = self.0.add_child_scope(scope_id).id;
+                    let body_scope_id = self.environment.add_child_scope(scope_id).id;
                     init_content.0.iter_mut().for_each(|expression| {
                         self.generate_for_expression(expression, body_scope_id)
                     });
@@ -90,7 +124,7 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
                 if block.statements.is_empty() {
                     return;
                 }
-                let body_scope_id = self.0.add_child_scope(scope_id).id;
+                let body_scope_id = self.environment.add_child_scope(scope_id).id;
                 block.set_scope(body_scope_id);
                 block
                     .statements
@@ -100,11 +134,55 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
             Expression::FieldAccess { receiver, .. } => {
                 self.generate_for_expression(receiver, scope_id);
             }
-            Expression::Collection(elements) => {
+            Expression::Collection(elements) | Expression::Tuple(elements) => {
                 for element in elements {
                     self.generate_for_expression(element, scope_id)
                 }
             }
+            Expression::Arithmetic { left, right, .. }
+            | Expression::Comparison { left, right, .. } => {
+                self.generate_for_expression(left, scope_id);
+                self.generate_for_expression(right, scope_id);
+            }
+            Expression::InterpolatedString(parts) => {
+                for expression in parts.iter_mut().filter_map(StringPart::expression_mut) {
+                    self.generate_for_expression(expression, scope_id)
+                }
+            }
+            Expression::Index {
+                receiver, index, ..
+            } => {
+                self.generate_for_expression(receiver, scope_id);
+                self.generate_for_expression(index, scope_id);
+            }
+            Expression::Slice {
+                receiver,
+                start,
+                end,
+                ..
+            } => {
+                self.generate_for_expression(receiver, scope_id);
+                for bound in start.iter_mut().chain(end) {
+                    self.generate_for_expression(bound, scope_id)
+                }
+            }
+            Expression::Match(r#match) => {
+                self.generate_for_expression(&mut r#match.subject, scope_id);
+                for arm in r#match.arms.iter_mut() {
+                    if let Some(name) = arm.pattern.name_mut() {
+                        name.set_scope(scope_id);
+                    }
+                    // the binding is only visible in its own arm
+                    let arm_scope_id = if arm.binding().is_some() {
+                        let arm_scope_id = self.environment.add_child_scope(scope_id).id;
+                        arm.set_scope(arm_scope_id);
+                        arm_scope_id
+                    } else {
+                        scope_id
+                    };
+                    self.generate_for_expression(&mut arm.value, arm_scope_id);
+                }
+            }
         }
     }
 
@@ -114,6 +192,11 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
             | Parameter::Labelled {
                 label: _,
                 content: value,
+            }
+            | Parameter::Spread(value)
+            | Parameter::Nested {
+                path: _,
+                content: value,
             } => self.generate_for_expression(value, scope_id),
         }
     }
@@ -124,6 +207,9 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
             Statement::ConstantDeclaration(constant) => {
                 self.generate_for_constant(constant, scope_id)
             }
+            Statement::Destructuring(destructuring) => {
+                self.generate_for_destructuring(destructuring, scope_id)
+            }
         };
     }
 
@@ -132,19 +218,38 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
         r#struct: &mut StructDeclaration<'a>,
         scope_id: ScopeId,
     ) {
-        for field in r#struct.fields.iter_mut() {
-            field.field_type.name.set_scope(scope_id);
-            if let Some(default_value) = field.default_value.as_mut() {
-                // field default value is not in the body scope
-                self.generate_for_expression(default_value, scope_id);
-            }
-        }
         if let Some(body) = r#struct.body.as_mut() {
-            let body_scope = self.0.add_child_scope(scope_id).id;
+            let body_scope = self.environment.add_child_scope(scope_id).id;
             r#body.set_scope(body_scope);
             for declaration in body.attributes.iter_mut() {
                 self.generate_for_constant(declaration, body_scope)
             }
+            for assertion in body.assertions.iter_mut() {
+                self.generate_for_assertion(assertion, body_scope)
+            }
+        }
+        // field default values are not in the body scope, but in a scope where `self` is bound
+        let fields_scope = r#struct
+            .has_default_values()
+            .then(|| self.environment.add_child_scope(scope_id).id);
+        for field in r#struct.fields.iter_mut() {
+            for type_name in field.field_type.names_mut() {
+                type_name.set_scope(scope_id);
+            }
+            if let (Some(default_value), Some(fields_scope)) =
+                (field.default_value.as_mut(), fields_scope)
+            {
+                self.generate_for_expression(default_value, fields_scope);
+            }
+        }
+        if let Some(fields_scope) = fields_scope {
+            r#struct.set_scope(fields_scope);
+        }
+    }
+
+    fn generate_for_type_alias(&mut self, alias: &mut TypeAlias<'a>, scope_id: ScopeId) {
+        for type_name in alias.aliased.names_mut() {
+            type_name.set_scope(scope_id)
         }
     }
 
@@ -154,7 +259,19 @@ impl<'ast, 'a, 'env> ScopeGenerator<'ast, 'a, 'env> {
         scope_id: ScopeId,
     ) {
         for required_field in &mut r#trait.required {
-            required_field.field_type.name.set_scope(scope_id)
+            for type_name in required_field.field_type.names_mut() {
+                type_name.set_scope(scope_id)
+            }
+        }
+        for super_trait in &mut r#trait.super_traits {
+            super_trait.set_scope(scope_id)
+        }
+        if let Some(body) = r#trait.body.as_mut() {
+            let body_scope = self.environment.add_child_scope(scope_id).id;
+            body.set_scope(body_scope);
+            for declaration in body.attributes.iter_mut() {
+                self.generate_for_constant(declaration, body_scope)
+            }
         }
     }
 }
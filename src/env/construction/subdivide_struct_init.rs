@@ -1,11 +1,16 @@
 use crate::ast::{Accessor, Expression, Name, Parameter};
 use crate::env::Environment;
+use std::collections::HashMap;
 
 /// This function clarifies StructInit which is ambiguous given the grammar.
 /// After this method, StructInit will be the case of calling Struct constructor,
 /// the case that creates struct using chaining method will be made to ChainingMethodInvocation
 /// explicitly
-pub fn subdivide<'ast, 'a>(environment: &Environment<'ast, 'a>, struct_init: &mut Expression<'a>) {
+pub fn subdivide<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    imported_modules: &HashMap<&'a str, Vec<&'a str>>,
+    struct_init: &mut Expression<'a>,
+) {
     let (name, parameters, init_content) = match struct_init {
         Expression::StructInit {
             name,
@@ -14,17 +19,42 @@ pub fn subdivide<'ast, 'a>(environment: &Environment<'ast, 'a>, struct_init: &mu
         } => (name, parameters, init_content),
         _ => return,
     };
-    if !(init_content.is_some()
+    if is_update(
+        environment,
+        imported_modules,
+        name,
+        parameters,
+        init_content.is_some(),
+    ) {
+        let (prefix_name, _) = split_name(name);
+        *struct_init = Expression::ChainingMethodInvocation {
+            receiver: Box::new(Expression::ConstUse(prefix_name)),
+            accessors: std::mem::take(parameters)
+                .into_iter()
+                .map(Accessor::update)
+                .collect(),
+        }
+    } else if !(init_content.is_some()
         || name.moniker.as_slice().len() == 1
         || parameter_indicates_struct_init(parameters)
-        || name_exists_directly_in_module(environment, name))
+        || name_exists_directly_in_module(environment, imported_modules, name))
     {
         let (prefix_name, field_name) = split_name(name);
         let prefix_expression = Box::new(Expression::ConstUse(prefix_name));
-        let field_value = parameters.pop().map(Parameter::expression_owned);
+        // several arguments are passed together as a tuple
+        let field_value = match parameters.len() {
+            0 | 1 => parameters.pop().map(Parameter::expression_owned),
+            _ => Some(Expression::Tuple(
+                std::mem::take(parameters)
+                    .into_iter()
+                    .map(Parameter::expression_owned)
+                    .collect(),
+            )),
+        };
         *struct_init = Expression::ChainingMethodInvocation {
             receiver: prefix_expression,
             accessors: vec![Accessor {
+                path: vec![],
                 identifier: field_name,
                 value: field_value,
             }],
@@ -32,21 +62,47 @@ pub fn subdivide<'ast, 'a>(environment: &Environment<'ast, 'a>, struct_init: &mu
     }
 }
 
+/// `instance.with(field: value)` updates fields of an instance,
+/// unless `with` is a struct declared in the module
+fn is_update<'ast, 'a>(
+    environment: &Environment<'ast, 'a>,
+    imported_modules: &HashMap<&'a str, Vec<&'a str>>,
+    name: &Name<'a>,
+    parameters: &[Parameter],
+    has_init_content: bool,
+) -> bool {
+    !has_init_content
+        && name.moniker.as_slice().len() > 1
+        && name.moniker.as_slice().last() == Some(&Accessor::UPDATE_METHOD)
+        && !parameters.is_empty()
+        && parameters.iter().all(Parameter::is_labelled)
+        && !name_exists_directly_in_module(environment, imported_modules, name)
+}
+
 fn parameter_indicates_struct_init(parameters: &[Parameter]) -> bool {
-    parameters.len() > 1 || (parameters.len() == 1 && parameters[0].is_labelled())
+    parameters
+        .iter()
+        .any(|parameter| !matches!(parameter, Parameter::Plain(_)))
 }
 
-/// Structs resides directly in modules. So modules.suffix is definitely a struct
+/// Structs resides directly in modules. So modules.suffix is definitely a struct,
+/// whether the module is written by its full path or by the name it is imported as
 fn name_exists_directly_in_module<'ast, 'a>(
     environment: &Environment<'ast, 'a>,
-    name: &'ast Name<'a>,
+    imported_modules: &HashMap<&'a str, Vec<&'a str>>,
+    name: &Name<'a>,
 ) -> bool {
     debug_assert!(
         name.moniker.as_slice().len() > 1,
         "Moniker name should be >= 1"
     );
     let (_, prefix) = name.moniker.as_slice().split_last().unwrap();
+    let (first, rest) = prefix.split_first().unwrap();
+    let imported_path = imported_modules
+        .get(first)
+        .map(|module_path| [module_path, rest].concat());
     environment.find_module(prefix).is_some()
+        || imported_path.is_some_and(|module_path| environment.find_module(&module_path).is_some())
 }
 
 // Split name: `Name(a.b.c)` => `Name(a.b), "c"`
@@ -14,7 +14,7 @@ fn test_scope_for_block() {
     let mut env = Environment::default();
     let mut ast = vec![abstract_tree(parse(tokenize("const a = { 3 }\n")))];
     let module_paths = prepare_module_paths();
-    ScopeGenerator(&mut env).generate(&mut ast, &module_paths);
+    ScopeGenerator::new(&mut env).generate(&mut ast, &module_paths);
     let constant = constants(ast.pop().unwrap()).pop().unwrap();
     let block = constant.value.into_block().unwrap();
     assert_eq!(block.scope(), 1);
@@ -27,7 +27,7 @@ fn test_scope_for_struct_init_content() {
         "const a = View { Text(\"\") }\n",
     )))];
     let module_paths = prepare_module_paths();
-    ScopeGenerator(&mut env).generate(&mut ast, &module_paths);
+    ScopeGenerator::new(&mut env).generate(&mut ast, &module_paths);
     let constant = constants(ast.pop().unwrap()).pop().unwrap();
     let mut init_content = constant.value.into_struct_init().unwrap().2.unwrap();
     let text = init_content.0.pop().unwrap().into_struct_init().unwrap().0;
@@ -41,7 +41,7 @@ fn test_scope_for_struct_definition() {
         "struct Test { const a = 3\n }\n",
     )))];
     let module_paths = prepare_module_paths();
-    ScopeGenerator(&mut env).generate(&mut ast, &module_paths);
+    ScopeGenerator::new(&mut env).generate(&mut ast, &module_paths);
     let struct_definition = struct_definitions(ast.pop().unwrap()).pop().unwrap();
     let body = struct_definition.body.expect("Body is empty");
     assert_eq!(body.scope(), 1)
@@ -1,6 +1,6 @@
 use crate::ast::{
-    AbstractSyntaxTree, ConstantDeclaration, Declaration, Expression, Parameter, Statement,
-    StructDeclaration, TraitDeclaration,
+    AbstractSyntaxTree, Assertion, ConstantDeclaration, Declaration, DestructuringDeclaration,
+    Expression, Parameter, Statement, StringPart, StructDeclaration, TraitDeclaration, TypeAlias,
 };
 use crate::env::declaration_resolution::UnresolvedNames;
 use crate::env::scope::*;
@@ -37,17 +37,47 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
         seen_names: &mut UnresolvedNames<'ast, 'a>,
     ) {
         match declaration {
-            Declaration::Constant(constant) => self.add_constant(constant, scope_id, seen_names),
+            Declaration::Constant(constant) => {
+                self.add_constant(constant, scope_id, seen_names);
+                self.export(constant.is_public, [constant.name], scope_id)
+            }
+            Declaration::Destructuring(destructuring) => {
+                self.add_destructuring(destructuring, scope_id, seen_names);
+                let names = destructuring.names.iter().copied();
+                self.export(destructuring.is_public, names, scope_id)
+            }
             Declaration::Struct(r#struct) => {
-                self.add_struct_declaration(r#struct, scope_id, seen_names)
+                self.add_struct_declaration(r#struct, scope_id, seen_names);
+                self.export(r#struct.is_public, [r#struct.name], scope_id)
             }
             Declaration::Trait(r#trait) => {
-                self.add_trait_declaration(r#trait, scope_id, seen_names)
+                self.add_trait_declaration(r#trait, scope_id, seen_names);
+                self.export(r#trait.is_public, [r#trait.name], scope_id)
+            }
+            Declaration::TypeAlias(alias) => {
+                self.add_type_alias(alias, scope_id, seen_names);
+                self.export(alias.is_public, [alias.name], scope_id)
+            }
+            Declaration::Assertion(assertion) => {
+                self.add_assertion(assertion, scope_id, seen_names);
+                self.0.assertions.push(assertion)
             }
             Declaration::Import(_) => (), // imports are ignored
         }
     }
 
+    fn export(
+        &mut self,
+        is_public: bool,
+        names: impl IntoIterator<Item = &'a str>,
+        scope_id: ScopeId,
+    ) {
+        if is_public {
+            let scope = self.0.get_scope_mut(scope_id);
+            scope.name_spaces.exported.extend(names);
+        }
+    }
+
     fn add_constant(
         &mut self,
         constant: &'ast ConstantDeclaration<'a>,
@@ -68,6 +98,37 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
         self.add_expression(&constant.value, scope_id, seen_names)
     }
 
+    fn add_assertion(
+        &mut self,
+        assertion: &'ast Assertion<'a>,
+        scope_id: ScopeId,
+        seen_names: &mut UnresolvedNames<'ast, 'a>,
+    ) {
+        self.add_expression(&assertion.condition, scope_id, seen_names);
+        self.add_expression(&assertion.message, scope_id, seen_names)
+    }
+
+    fn add_destructuring(
+        &mut self,
+        destructuring: &'ast DestructuringDeclaration<'a>,
+        scope_id: ScopeId,
+        seen_names: &mut UnresolvedNames<'ast, 'a>,
+    ) {
+        let scope = self.0.get_scope_mut(scope_id);
+        for (index, name) in destructuring.names.iter().enumerate() {
+            let duplicate_declaration = scope
+                .name_spaces
+                .declared
+                .insert(name, DeclaredElement::Binding(destructuring, index));
+            assert!(
+                duplicate_declaration.is_none(),
+                "Cannot redefine constant in the same module with name: {}",
+                name
+            );
+        }
+        self.add_expression(&destructuring.value, scope_id, seen_names)
+    }
+
     fn add_expression(
         &mut self,
         expression: &'ast Expression<'a>,
@@ -119,9 +180,56 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
             Expression::FieldAccess { receiver, .. } => {
                 self.add_expression(receiver, scope_id, seen_names);
             }
-            Expression::Collection(elements) => elements
+            Expression::Collection(elements) | Expression::Tuple(elements) => elements
                 .iter()
                 .for_each(|element| self.add_expression(element, scope_id, seen_names)),
+            Expression::Arithmetic { left, right, .. }
+            | Expression::Comparison { left, right, .. } => {
+                self.add_expression(left, scope_id, seen_names);
+                self.add_expression(right, scope_id, seen_names);
+            }
+            Expression::InterpolatedString(parts) => parts
+                .iter()
+                .filter_map(StringPart::expression)
+                .for_each(|expression| self.add_expression(expression, scope_id, seen_names)),
+            Expression::Index {
+                receiver, index, ..
+            } => {
+                self.add_expression(receiver, scope_id, seen_names);
+                self.add_expression(index, scope_id, seen_names);
+            }
+            Expression::Slice {
+                receiver,
+                start,
+                end,
+                ..
+            } => {
+                self.add_expression(receiver, scope_id, seen_names);
+                for bound in start.iter().chain(end) {
+                    self.add_expression(bound, scope_id, seen_names)
+                }
+            }
+            Expression::Match(r#match) => {
+                let module_scope = self.0.module_of(scope_id);
+                self.0.match_modules.insert(r#match, module_scope);
+                self.add_expression(&r#match.subject, scope_id, seen_names);
+                for (index, arm) in r#match.arms.iter().enumerate() {
+                    seen_names.expression_names.extend(arm.pattern.name());
+                    let arm_scope_id = match arm.binding() {
+                        Some(binding) => {
+                            let arm_scope_id = arm.scope();
+                            self.0
+                                .get_scope_mut(arm_scope_id)
+                                .name_spaces
+                                .declared
+                                .insert(binding, DeclaredElement::PatternBinding(r#match, index));
+                            arm_scope_id
+                        }
+                        None => scope_id,
+                    };
+                    self.add_expression(&arm.value, arm_scope_id, seen_names);
+                }
+            }
         }
     }
 
@@ -136,6 +244,11 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
             | Parameter::Labelled {
                 label: _,
                 content: value,
+            }
+            | Parameter::Spread(value)
+            | Parameter::Nested {
+                path: _,
+                content: value,
             } => self.add_expression(value, scope_id, seen_names),
         }
     }
@@ -153,6 +266,9 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
             Statement::ConstantDeclaration(constant) => {
                 self.add_constant(constant, scope_id, seen_names)
             }
+            Statement::Destructuring(destructuring) => {
+                self.add_destructuring(destructuring, scope_id, seen_names)
+            }
         };
     }
 
@@ -172,10 +288,18 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
             "Cannot redefine struct in the same module with name: {}",
             r#struct.name
         );
+        self.0.struct_modules.insert(r#struct, scope_id);
+        if r#struct.has_default_values() {
+            self.0
+                .get_scope_mut(r#struct.scope())
+                .name_spaces
+                .declared
+                .insert("self", r#struct.into());
+        }
         for field in &r#struct.fields {
-            seen_names.type_names.insert(&field.field_type.name);
+            seen_names.type_names.extend(field.field_type.names());
             if let Some(default_value) = &field.default_value {
-                self.add_expression(default_value, scope_id, seen_names);
+                self.add_expression(default_value, r#struct.scope(), seen_names);
             }
         }
         if let Some(body) = &r#struct.body {
@@ -188,9 +312,28 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
             for declaration in &body.attributes {
                 self.add_constant(declaration, body_scope_id, seen_names)
             }
+            for assertion in &body.assertions {
+                self.add_assertion(assertion, body_scope_id, seen_names)
+            }
         }
     }
 
+    fn add_type_alias(
+        &mut self,
+        alias: &'ast TypeAlias<'a>,
+        scope_id: ScopeId,
+        seen_names: &mut UnresolvedNames<'ast, 'a>,
+    ) {
+        let scope = self.0.get_scope_mut(scope_id);
+        let duplicate_declaration = scope.name_spaces.declared.insert(alias.name, alias.into());
+        assert!(
+            duplicate_declaration.is_none(),
+            "Cannot redefine type in the same module with name: {}",
+            alias.name
+        );
+        seen_names.type_names.extend(alias.aliased.names());
+    }
+
     fn add_trait_declaration(
         &mut self,
         r#trait: &'ast TraitDeclaration<'a>,
@@ -208,7 +351,21 @@ impl<'ast, 'a, 'env> DeclarationAdder<'ast, 'a, 'env> {
             r#trait.name
         );
         for required in &r#trait.required {
-            seen_names.type_names.insert(&required.field_type.name);
+            seen_names.type_names.extend(required.field_type.names());
+        }
+        seen_names.type_names.extend(&r#trait.super_traits);
+        self.0.traits.push(r#trait);
+        if let Some(body) = &r#trait.body {
+            self.0.default_traits.push(r#trait);
+            let body_scope_id = body.scope();
+            let body_scope = self.0.get_scope_mut(body_scope_id);
+            body_scope
+                .name_spaces
+                .declared
+                .insert("self", r#trait.into());
+            for declaration in &body.attributes {
+                self.add_constant(declaration, body_scope_id, seen_names)
+            }
         }
     }
 }
@@ -1,8 +1,10 @@
 use super::super::scope::GLOBAL_SCOPE;
 use super::super::scope::{DeclaredElement, Scope, ScopeId};
 use super::Environment;
-use crate::ast::{AbstractSyntaxTree, Declaration, Import};
+use crate::ast::{AbstractSyntaxTree, Declaration, Import, ImportDeclaration, ImportedName};
+use crate::env::name_resolution::{ResolveHelper, Resolved};
 use crate::iterating::Iterating;
+use std::collections::HashMap;
 
 pub(in crate::env::declaration_resolution) struct Importer<'ast, 'a, 'env>(
     pub &'env mut Environment<'ast, 'a>,
@@ -21,40 +23,81 @@ impl<'ast, 'a, 'env> Importer<'ast, 'a, 'env> {
         ];
         let global_scope = self.0.get_scope_mut(GLOBAL_SCOPE);
         for stdlib in std {
-            stdlib.import_to(global_scope);
+            stdlib.import_to(global_scope, false);
         }
         self
     }
 
+    /// Imports can use names re-exported by other imports with `pub use`,
+    /// so the imports are repeated until all of them are resolved
     pub fn import_from(
         &mut self,
         syntax_trees: &'ast [AbstractSyntaxTree<'a>],
         module_paths: &[Vec<&'a str>],
     ) {
-        let mut importing_elements = vec![];
-        // process import declarations, then do import separately
+        let mut pending = vec![];
         for (syntax_tree, module_path) in syntax_trees.iter().zip(module_paths.iter()) {
             let target_scope_id = self
                 .0
                 .find_module(module_path)
                 .unwrap_or_else(|| panic!("Cannot find module: {}", module_path.join(".")));
-            let elements = Self::find_imports(syntax_tree)
-                .flat_map(|import| self.process_import(import))
-                .collect::<Vec<_>>();
-            importing_elements.push((target_scope_id, elements));
-        }
-        // actual imports
-        for (target_scope_id, elements) in importing_elements {
-            let target_scope = self.0.get_scope_mut(target_scope_id);
-            for element in elements {
-                element.import_to(target_scope)
+            pending.extend(Self::find_imports(syntax_tree).flat_map(|import| {
+                Self::requests(import, module_path)
+                    .into_iter()
+                    .map(move |request| (target_scope_id, request))
+            }));
+        }
+        let mut imported_names = HashMap::new();
+        while !pending.is_empty() {
+            let pending_count = pending.len();
+            pending.retain(|(target_scope_id, request)| {
+                match self.process_request(request, *target_scope_id) {
+                    Some(importing) => {
+                        self.check_name_clash(&importing, *target_scope_id, &mut imported_names);
+                        let target_scope = self.0.get_scope_mut(*target_scope_id);
+                        importing.import_to(target_scope, request.is_public);
+                        false
+                    }
+                    None => true,
+                }
+            });
+            if pending.len() == pending_count {
+                let (_, request) = pending.first().expect("Pending imports are not empty");
+                panic!("Unknown type for {}", request.path.join("."))
+            }
+        }
+    }
+
+    /// Different elements cannot be imported into a module with the same name,
+    /// nor with the name of a declaration or a std element already visible in the module.
+    /// Imports of the root file land in the global scope, so they would shadow it for every module
+    fn check_name_clash(
+        &self,
+        importing: &Importing<'ast, 'a>,
+        target_scope_id: ScopeId,
+        imported_names: &mut HashMap<(ScopeId, &'a str), DeclaredElement<'ast, 'a>>,
+    ) {
+        if let Importing::ExpressionOrStruct(element, name) = importing {
+            let existing = imported_names.insert((target_scope_id, *name), *element);
+            if let Some(existing) = existing {
+                assert!(
+                    existing.refers_to_same(element),
+                    "Name `{name}` is imported more than once, use `as` to rename one of them"
+                );
+                return;
+            }
+            if let Some(visible) = ResolveHelper(self.0).resolve(target_scope_id, name) {
+                assert!(
+                    visible.refers_to_same(&Resolved::from(*element)),
+                    "Imported name `{name}` clashes with `{name}` visible in the module, use `as` to rename it"
+                );
             }
         }
     }
 
     fn find_imports(
         syntax_tree: &'ast AbstractSyntaxTree<'a>,
-    ) -> impl Iterator<Item = &'ast Import<'a>> {
+    ) -> impl Iterator<Item = &'ast ImportDeclaration<'a>> {
         syntax_tree
             .compilation_unit
             .declarations
@@ -65,81 +108,171 @@ impl<'ast, 'a, 'env> Importer<'ast, 'a, 'env> {
             })
     }
 
-    fn process_import(
-        &self,
-        import: &'ast Import<'a>,
-    ) -> impl Iterator<Item = Importing<'ast, 'a>> {
-        match import {
-            Import::Single(name) => Iterating::once(self.process_single_import(name)),
-            Import::Wildcard(module) => Iterating::once(self.process_wildcard_import(module)),
+    fn requests(
+        declaration: &'ast ImportDeclaration<'a>,
+        module_path: &[&'a str],
+    ) -> Vec<ImportRequest<'a>> {
+        let is_public = declaration.is_public;
+        match &declaration.import {
+            Import::Single(name) => Iterating::once(ImportRequest::single(name, &[], is_public)),
+            Import::Wildcard(module) => Iterating::once(ImportRequest::wildcard(module, is_public)),
             Import::Multiple { prefix, suffices } => suffices
                 .iter()
-                .map(|suffix| self.process_multiple_import(prefix, suffix))
+                .map(|suffix| ImportRequest::single(suffix, prefix, is_public))
                 .collect(),
         }
+        .map(|request| request.relative_to(module_path))
+        .collect()
     }
 
-    fn process_wildcard_import(&self, module: &'ast [&'a str]) -> Importing<'ast, 'a> {
+    fn process_request(
+        &self,
+        request: &ImportRequest<'a>,
+        target_scope_id: ScopeId,
+    ) -> Option<Importing<'ast, 'a>> {
+        match request.name {
+            Some(name) => self.process_single_import(&request.path, name, target_scope_id),
+            None => Some(self.process_wildcard_import(&request.path)),
+        }
+    }
+
+    fn process_wildcard_import(&self, module: &[&'a str]) -> Importing<'ast, 'a> {
         let source_scope_id = self
             .0
             .find_module(module)
             .unwrap_or_else(|| panic!("Module name ({}) is invalid", module.join(".")));
-        Importing::Wildcard(source_scope_id, module)
+        Importing::Wildcard(source_scope_id, module.to_vec())
     }
 
-    fn process_multiple_import(
+    /// Returns `None` if the name is not found yet, it may be re-exported by an import later
+    fn process_single_import(
         &self,
-        prefix: &'ast [&'a str],
-        suffix: &'ast [&'a str],
-    ) -> Importing<'ast, 'a> {
-        self.process_single_import(&[prefix, suffix].concat())
-    }
-
-    fn process_single_import(&self, import: &[&'a str]) -> Importing<'ast, 'a> {
-        debug_assert!(!import.is_empty(), "Cannot import empty module");
-        let (last_element, module_path) =
-            import.split_last().unwrap_or((import.last().unwrap(), &[]));
-        let scope = self
+        import: &[&'a str],
+        name: &'a str,
+        target_scope_id: ScopeId,
+    ) -> Option<Importing<'ast, 'a>> {
+        let (last_element, module_path) = import.split_last().expect("Cannot import empty module");
+        let scope_id = self
             .0
             .find_module(module_path)
-            .map(|scope_id| self.0.get_scope(scope_id))
             .unwrap_or_else(|| panic!("Failed to resolve module: {}", module_path.join(".")));
-        if let Some(declared) = scope.name_spaces.declared.get(last_element) {
-            Importing::ExpressionOrStruct(*declared, last_element)
-        } else if let Some(&scope_id) = scope.name_spaces.modules.get(last_element) {
-            Importing::Module(scope_id, last_element)
+        let scope = self.0.get_scope(scope_id);
+        if let Some(declared) = self.0.find_visible(scope_id, last_element, target_scope_id) {
+            Some(Importing::ExpressionOrStruct(declared, name))
+        } else if scope.name_spaces.declared.contains_key(last_element) {
+            panic!(
+                "`{}` is private in module `{}`",
+                last_element,
+                module_path.join(".")
+            )
         } else {
-            panic!("Unknown type for {}", import.join("."))
+            let &module_scope_id = scope.name_spaces.modules.get(last_element)?;
+            Some(Importing::Module(module_scope_id, name))
+        }
+    }
+}
+
+/// Paths of the modules imported by the syntax tree, by the names they are imported as.
+/// All modules are added before the imports are processed, so they are known beforehand
+pub(in crate::env) fn imported_modules<'a>(
+    environment: &Environment<'_, 'a>,
+    syntax_tree: &AbstractSyntaxTree<'a>,
+    module_path: &[&'a str],
+) -> HashMap<&'a str, Vec<&'a str>> {
+    Importer::find_imports(syntax_tree)
+        .flat_map(|import| Importer::requests(import, module_path))
+        .filter_map(|request| Some((request.name?, request.path)))
+        .filter(|(_, path)| environment.find_module(path).is_some())
+        .collect()
+}
+
+/// A single name or a wildcard to be imported, multiple imports are split into single ones
+struct ImportRequest<'a> {
+    path: Vec<&'a str>,
+    /// Name in the importing module, `None` for wildcard imports
+    name: Option<&'a str>,
+    is_public: bool,
+}
+
+impl<'a> ImportRequest<'a> {
+    fn single(imported: &ImportedName<'a>, prefix: &[&'a str], is_public: bool) -> Self {
+        ImportRequest {
+            path: [prefix, &imported.path].concat(),
+            name: Some(imported.local_name()),
+            is_public,
         }
     }
+
+    fn wildcard(module: &[&'a str], is_public: bool) -> Self {
+        ImportRequest {
+            path: module.to_vec(),
+            name: None,
+            is_public,
+        }
+    }
+
+    /// Replaces the leading `self` and `super` with the module path of the importing file
+    fn relative_to(mut self, module_path: &[&'a str]) -> Self {
+        let relative_length = self
+            .path
+            .iter()
+            .take_while(|&&component| component == "self" || component == "super")
+            .count();
+        if relative_length == 0 {
+            return self;
+        }
+        let mut absolute_path = module_path.to_vec();
+        for &component in &self.path[..relative_length] {
+            if component == "super" {
+                absolute_path.pop().unwrap_or_else(|| {
+                    panic!(
+                        "`super` cannot be used in the global module: {}",
+                        self.path.join(".")
+                    )
+                });
+            }
+        }
+        absolute_path.extend_from_slice(&self.path[relative_length..]);
+        self.path = absolute_path;
+        self
+    }
 }
 
 enum Importing<'ast, 'a> {
-    Wildcard(ScopeId, &'ast [&'a str]),
+    Wildcard(ScopeId, Vec<&'a str>),
     Module(ScopeId, &'a str),
     ExpressionOrStruct(DeclaredElement<'ast, 'a>, &'a str),
 }
 
 impl<'ast, 'a> Importing<'ast, 'a> {
-    pub fn import_to(self, target_scope: &mut Scope<'ast, 'a>) {
+    pub fn import_to(self, target_scope: &mut Scope<'ast, 'a>, is_public: bool) {
         use Importing::*;
         match self {
-            Wildcard(scope_id, name) => Self::import_wildcard(scope_id, name, target_scope),
+            Wildcard(scope_id, name) => {
+                Self::import_wildcard(scope_id, &name, target_scope, is_public)
+            }
             Module(scope_id, name) => Self::import_module(scope_id, name, target_scope),
             ExpressionOrStruct(declared, name) => {
-                Self::import_expression(declared, name, target_scope)
+                Self::import_expression(declared, name, target_scope, is_public)
             }
         }
     }
 
     fn import_wildcard(
         scope_id: ScopeId,
-        name: &'ast [&'a str],
+        name: &[&'a str],
         target_scope: &mut Scope<'ast, 'a>,
+        is_public: bool,
     ) {
         if !target_scope.name_spaces.wildcard_imports.insert(scope_id) {
             panic!("Module `{}` has already being imported", name.join("."))
         }
+        if is_public {
+            target_scope
+                .name_spaces
+                .re_exported_wildcards
+                .insert(scope_id);
+        }
     }
 
     fn import_module(scope_id: ScopeId, module_name: &'a str, target_scope: &mut Scope<'ast, 'a>) {
@@ -159,11 +292,16 @@ impl<'ast, 'a> Importing<'ast, 'a> {
         element: DeclaredElement<'ast, 'a>,
         name: &'a str,
         target_scope: &mut Scope<'ast, 'a>,
+        is_public: bool,
     ) {
-        target_scope
+        let declared = *target_scope
             .name_spaces
             .declared
             .entry(name)
             .or_insert(element);
+        // a shadowed import is not exported
+        if is_public && declared.refers_to_same(&element) {
+            target_scope.name_spaces.exported.insert(name);
+        }
     }
 }
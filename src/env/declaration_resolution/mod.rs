@@ -8,6 +8,7 @@ mod import_operations;
 mod tests;
 
 use declaration_operations::DeclarationAdder;
+pub(in crate::env) use import_operations::imported_modules;
 use import_operations::Importer;
 
 #[derive(Default)]
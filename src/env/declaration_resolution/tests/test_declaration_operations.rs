@@ -65,6 +65,23 @@ fn test_add_struct_declaration() {
     assert!(std::ptr::eq(expected, actual))
 }
 
+#[test]
+fn test_declared_documentation() {
+    let mut env = Environment::default();
+    let syntax_tree = abstract_tree(parse(tokenize(
+        "/// Width of A4 paper\n///in points\nconst width = 595\n",
+    )));
+    kick_off(&mut env, &syntax_tree);
+    let documentation = env
+        .get_scope(GLOBAL_SCOPE)
+        .name_spaces
+        .declared
+        .get("width")
+        .expect("Failed to get from namespace")
+        .documentation();
+    assert_eq!(documentation.to_string(), "Width of A4 paper\nin points")
+}
+
 fn kick_off<'ast, 'a>(
     env: &mut Environment<'ast, 'a>,
     syntax_tree: &'ast AbstractSyntaxTree<'a>,
@@ -14,9 +14,11 @@ macro_rules! import {
         let module_paths = [vec![], vec!["test"], vec!["test", "nested"]];
         let syntax_trees = [
             abstract_tree(parse(tokenize(concat!($code, "\n")))),
-            abstract_tree(parse(tokenize("const target = 42\nstruct Empty\n"))),
             abstract_tree(parse(tokenize(
-                "const deeper_target = 42\nstruct DeeperEmpty\n",
+                "pub const target = 42\npub struct Empty\nconst hidden = 0\n",
+            ))),
+            abstract_tree(parse(tokenize(
+                "pub const deeper_target = 42\npub struct DeeperEmpty\n",
             ))),
         ];
 
@@ -39,6 +41,13 @@ fn test_single_import_deeper_constant() {
     assert_constant(&env, "deeper_target", &["test", "nested"]);
 }
 
+#[test]
+#[should_panic(expected = "`hidden` is private in module `test`")]
+fn test_single_import_private_constant() {
+    let mut env = Environment::default();
+    import!(env, "use test.hidden");
+}
+
 fn assert_constant(env: &Environment, name: &str, scope_name: &[&str]) {
     let source_scope = env.find_module(scope_name).expect("Cannot find scope name");
     let actual = *env
@@ -136,3 +145,140 @@ fn test_single_import_module() {
     let expected = env.find_module(&["test", "nested"]);
     assert_eq!(actual, expected)
 }
+
+#[test]
+fn test_import_alias() {
+    let mut env = Environment::default();
+    import!(env, "use test.{ target as renamed, Empty as Nothing }");
+    let global_scope = env.get_scope(GLOBAL_SCOPE);
+    assert!(!global_scope.name_spaces.declared.contains_key("target"));
+    let source_scope = env.get_scope(env.find_module(&["test"]).unwrap());
+    let actual = global_scope.name_spaces.declared.get("renamed").unwrap();
+    let expected = source_scope.name_spaces.declared.get("target").unwrap();
+    assert!(actual.refers_to_same(expected));
+    let actual = global_scope.name_spaces.declared.get("Nothing").unwrap();
+    let expected = source_scope.name_spaces.declared.get("Empty").unwrap();
+    assert!(actual.refers_to_same(expected));
+}
+
+#[test]
+#[should_panic(expected = "Name `target` is imported more than once")]
+fn test_conflicting_imports() {
+    let mut env = Environment::default();
+    import!(
+        env,
+        "use test.target\nuse test.nested.deeper_target as target"
+    );
+}
+
+#[test]
+#[should_panic(expected = "Imported name `Label` clashes with `Label` visible in the module")]
+fn test_import_alias_clashing_with_declaration() {
+    let mut env = Environment::default();
+    import!(env, "pub struct Label\nuse test.Empty as Label");
+}
+
+#[test]
+#[should_panic(expected = "Imported name `Empty` clashes with `Empty` visible in the module")]
+fn test_import_alias_clashing_with_wildcard_import() {
+    let mut env = Environment::default();
+    import!(env, "use test.*\nuse test.nested.DeeperEmpty as Empty");
+}
+
+#[test]
+fn test_import_already_visible_through_wildcard() {
+    let mut env = Environment::default();
+    import!(env, "use test.*\nuse test.Empty");
+    assert_struct(&env, "Empty", &["test"]);
+}
+
+// the importing module comes first, so it is processed before the re-exporting one
+macro_rules! import_through_facade {
+    ($env: ident, $facade: expr, $code: expr) => {
+        let formula = crate::formula_suppress::FormulaSuppress::all();
+        formula.suppress();
+
+        let module_paths = [vec![], vec!["facade"], vec!["facade", "inner"]];
+        let syntax_trees = [
+            abstract_tree(parse(tokenize($code))),
+            abstract_tree(parse(tokenize($facade))),
+            abstract_tree(parse(tokenize("pub struct Theme\n"))),
+        ];
+
+        construction::add_modules(&mut $env, &module_paths);
+        declaration_resolution::resolve(&mut $env, &syntax_trees, &module_paths);
+    };
+}
+
+#[test]
+fn test_single_re_export() {
+    let mut env = Environment::default();
+    import_through_facade!(
+        env,
+        "pub use facade.inner.Theme as FacadeTheme\n",
+        "use facade.FacadeTheme\n"
+    );
+    assert_theme_imported(&env, "FacadeTheme");
+}
+
+#[test]
+fn test_wildcard_re_export() {
+    let mut env = Environment::default();
+    import_through_facade!(env, "pub use facade.inner.*\n", "use facade.Theme\n");
+    assert_theme_imported(&env, "Theme");
+}
+
+#[test]
+#[should_panic(expected = "`Theme` is private in module `facade`")]
+fn test_import_not_re_exported() {
+    let mut env = Environment::default();
+    import_through_facade!(env, "use facade.inner.Theme\n", "use facade.Theme\n");
+}
+
+fn assert_theme_imported(env: &Environment, name: &str) {
+    let source_scope = env.find_module(&["facade", "inner"]).unwrap();
+    let actual = env
+        .get_scope(GLOBAL_SCOPE)
+        .name_spaces
+        .declared
+        .get(name)
+        .unwrap();
+    let expected = env
+        .get_scope(source_scope)
+        .name_spaces
+        .declared
+        .get("Theme")
+        .unwrap();
+    assert!(actual.refers_to_same(expected))
+}
+
+#[test]
+fn test_import_relative_to_self() {
+    let mut env = Environment::default();
+    import_through_facade!(env, "pub use self.inner.Theme\n", "use facade.Theme\n");
+    assert_theme_imported(&env, "Theme");
+}
+
+#[test]
+fn test_import_relative_to_super() {
+    let mut env = Environment::default();
+    import_through_facade!(env, "use super.Logo\n", "pub struct Logo\n");
+    let facade = env.find_module(&["facade"]).unwrap();
+    let actual = env.get_scope(facade).name_spaces.declared.get("Logo");
+    let expected = env.get_scope(GLOBAL_SCOPE).name_spaces.declared.get("Logo");
+    assert!(actual.unwrap().refers_to_same(expected.unwrap()));
+}
+
+#[test]
+fn test_wildcard_import_relative_to_super() {
+    let mut env = Environment::default();
+    import_through_facade!(env, "pub use super.facade.inner.*\n", "use facade.Theme\n");
+    assert_theme_imported(&env, "Theme");
+}
+
+#[test]
+#[should_panic(expected = "`super` cannot be used in the global module: super.super.Logo")]
+fn test_super_beyond_global_module() {
+    let mut env = Environment::default();
+    import_through_facade!(env, "use super.super.Logo\n", "pub struct Logo\n");
+}
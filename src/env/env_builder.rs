@@ -23,10 +23,10 @@ impl<'ast, 'a> EnvironmentBuilder<'ast, 'a, CONSTRUCTED> {
         }
     }
 
-    /// Add module information to environment based on file paths
+    /// Add module information to environment based on source files
     ///
     /// # Parameters
-    /// * `paths` - a slice of paths to source files
+    /// * `sources` - paths to source files along with their contents
     ///
     /// # Note
     /// file path starts from src folder of the project,
@@ -34,12 +34,14 @@ impl<'ast, 'a> EnvironmentBuilder<'ast, 'a, CONSTRUCTED> {
     ///
     /// For example, path `src/models/tree/tree.ds` generate
     /// modules `models` and `models.tree`
-    pub fn add_modules_from_paths(
+    pub fn add_modules_from_sources(
         mut self,
-        paths: impl Iterator<Item = &'a Path>,
+        sources: impl Iterator<Item = (&'a Path, &'a str)>,
     ) -> EnvironmentBuilder<'ast, 'a, MODULE_ADDED> {
-        let module_paths = paths.map(Self::convert_to_module);
-        self.module_paths.extend(module_paths);
+        for (path, content) in sources {
+            self.module_paths.push(Self::convert_to_module(path));
+            self.environment.sources.add(path, content);
+        }
         construction::add_modules(&mut self.environment, &self.module_paths);
         self.migrate()
     }
@@ -90,7 +92,8 @@ impl<'ast, 'a> EnvironmentBuilder<'ast, 'a, MODULE_ADDED> {
 }
 
 impl<'ast, 'a> EnvironmentBuilder<'ast, 'a, SCOPE_GENERATED> {
-    ///
+    /// Add declarations and imports to their scopes, then resolve every name used in
+    /// the syntax trees to the declaration it refers to.
     pub fn resolve_names(
         mut self,
         syntax_trees: &'ast [AbstractSyntaxTree<'a>],
@@ -107,10 +110,10 @@ impl<'ast, 'a> EnvironmentBuilder<'ast, 'a, SCOPE_GENERATED> {
 
 impl<'ast, 'a> EnvironmentBuilder<'ast, 'a, NAME_RESOLVED> {
     pub fn validate(
-        self,
+        mut self,
         syntax_trees: &'ast [AbstractSyntaxTree<'a>],
     ) -> EnvironmentBuilder<'ast, 'a, VALIDATED> {
-        checks::check(&self.environment, syntax_trees);
+        self.environment.inherited_attributes = checks::check(&self.environment, syntax_trees);
         self.migrate()
     }
 }
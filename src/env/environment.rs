@@ -1,19 +1,49 @@
+use super::address_hash::hash;
 pub use super::name_resolution::Resolved;
 use super::scope::{DeclaredElement, Scope, ScopeId, GLOBAL_SCOPE};
-use crate::ast::{ConstantDeclaration, Name};
+use super::source_map::SourceMap;
+use crate::ast::{
+    Assertion, ConstantDeclaration, Documentation, Match, Name, StructDeclaration, TraitDeclaration,
+};
 use crate::env::EnvironmentBuilder;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+hash!(Match);
 
 pub struct Environment<'ast, 'a> {
     scopes: Vec<Scope<'ast, 'a>>,
     pub resolved_names: HashMap<Name<'a>, Resolved<'ast, 'a>>,
+    /// Traits with a body, which provide default attributes to conforming structs
+    pub(in crate::env) default_traits: Vec<&'ast TraitDeclaration<'a>>,
+    /// All traits in declaration order, the candidates for the common type of match arms
+    pub(in crate::env) traits: Vec<&'ast TraitDeclaration<'a>>,
+    /// Module declaring each struct, which limits the traits the struct inherits attributes from
+    pub(in crate::env) struct_modules: HashMap<&'ast StructDeclaration<'a>, ScopeId>,
+    /// Module declaring each match, which limits the traits its arms can have in common
+    pub(in crate::env) match_modules: HashMap<&'ast Match<'a>, ScopeId>,
+    /// Attributes every struct inherits from the traits it conforms to, filled after validation
+    pub inherited_attributes: InheritedAttributes<'ast, 'a>,
+    /// Assertions declared in modules, checked before the entry is evaluated
+    pub assertions: Vec<&'ast Assertion<'a>>,
+    /// Contents of the compiled files, used to report where errors were written
+    pub sources: SourceMap<'a>,
 }
 
+pub type InheritedAttributes<'ast, 'a> =
+    HashMap<&'ast StructDeclaration<'a>, Vec<&'ast ConstantDeclaration<'a>>>;
+
 impl<'ast, 'a> Default for Environment<'ast, 'a> {
     fn default() -> Self {
         Environment {
             scopes: vec![Scope::global()],
             resolved_names: HashMap::new(),
+            default_traits: vec![],
+            traits: vec![],
+            struct_modules: HashMap::new(),
+            match_modules: HashMap::new(),
+            inherited_attributes: HashMap::new(),
+            assertions: vec![],
+            sources: SourceMap::default(),
         }
     }
 }
@@ -23,7 +53,7 @@ impl<'ast, 'a> Environment<'ast, 'a> {
         EnvironmentBuilder::new()
     }
 
-    pub fn find_module<'b>(&self, names: &[&'b str]) -> Option<ScopeId> {
+    pub fn find_module(&self, names: &[&str]) -> Option<ScopeId> {
         let mut scope_id = GLOBAL_SCOPE;
         for module_name in names.iter() {
             let scope = self.get_scope(scope_id);
@@ -32,6 +62,23 @@ impl<'ast, 'a> Environment<'ast, 'a> {
         Some(scope_id)
     }
 
+    /// The module enclosing the scope, the closest one its parent lists as a sub module
+    pub(in crate::env) fn module_of(&self, mut scope_id: ScopeId) -> ScopeId {
+        while scope_id != GLOBAL_SCOPE {
+            let parent = self.get_scope(self.get_scope(scope_id).parent);
+            if parent
+                .name_spaces
+                .modules
+                .values()
+                .any(|&module| module == scope_id)
+            {
+                break;
+            }
+            scope_id = parent.id;
+        }
+        scope_id
+    }
+
     pub fn get_scope_mut(&mut self, scope_id: ScopeId) -> &mut Scope<'ast, 'a> {
         self.scopes.get_mut(scope_id).expect("Invalid scope id")
     }
@@ -51,20 +98,118 @@ impl<'ast, 'a> Environment<'ast, 'a> {
         self.scopes.last_mut().expect("Child scope expected")
     }
 
+    /// Checks whether the scope is the given module or is nested inside it
+    pub(in crate::env) fn is_within(&self, scope_id: ScopeId, module_scope: ScopeId) -> bool {
+        let mut scope = self.get_scope(scope_id);
+        loop {
+            if scope.id == module_scope {
+                return true;
+            }
+            if scope.id == GLOBAL_SCOPE {
+                return false;
+            }
+            scope = self.get_scope(scope.parent);
+        }
+    }
+
+    /// Private declarations can only be used in the module declaring them
+    pub(in crate::env) fn is_accessible(
+        &self,
+        module_scope: ScopeId,
+        name: &str,
+        from: ScopeId,
+    ) -> bool {
+        self.get_scope(module_scope)
+            .name_spaces
+            .exported
+            .contains(name)
+            || self.is_within(from, module_scope)
+    }
+
+    /// Finds a declaration of the module that can be used from the given scope,
+    /// including the ones re-exported by `pub use module.*`
+    pub(in crate::env) fn find_visible(
+        &self,
+        module_scope: ScopeId,
+        name: &str,
+        from: ScopeId,
+    ) -> Option<DeclaredElement<'ast, 'a>> {
+        let mut searched_modules = HashSet::new();
+        self.find_visible_in(module_scope, name, from, &mut searched_modules)
+    }
+
+    fn find_visible_in(
+        &self,
+        module_scope: ScopeId,
+        name: &str,
+        from: ScopeId,
+        searched_modules: &mut HashSet<ScopeId>,
+    ) -> Option<DeclaredElement<'ast, 'a>> {
+        if !searched_modules.insert(module_scope) {
+            return None;
+        }
+        let name_spaces = &self.get_scope(module_scope).name_spaces;
+        match name_spaces.declared.get(name) {
+            Some(declared) if self.is_accessible(module_scope, name, from) => Some(*declared),
+            _ => name_spaces
+                .re_exported_wildcards
+                .iter()
+                .find_map(|&source| self.find_visible_in(source, name, from, searched_modules)),
+        }
+    }
+
+    /// Elements declared in the global scope and in every module,
+    /// excluding the ones declared in blocks and struct bodies
+    pub fn module_declarations(&self) -> Vec<DeclaredElement<'ast, 'a>> {
+        let mut visited = HashSet::new();
+        let mut modules = vec![GLOBAL_SCOPE];
+        let mut declarations = vec![];
+        while let Some(scope_id) = modules.pop() {
+            if !visited.insert(scope_id) {
+                continue;
+            }
+            let name_spaces = &self.get_scope(scope_id).name_spaces;
+            declarations.extend(name_spaces.declared.values().copied());
+            modules.extend(name_spaces.modules.values().copied());
+        }
+        declarations
+    }
+
+    /// Documentation of the declaration at the path, made of its module names followed by its name
+    pub fn documentation(&self, path: &[&str]) -> Option<&'ast Documentation<'a>> {
+        let (name, module) = path.split_last()?;
+        let module_scope = self.find_module(module)?;
+        let declared = self
+            .get_scope(module_scope)
+            .name_spaces
+            .declared
+            .get(name)?;
+        Some(declared.documentation())
+    }
+
     pub fn entry(&self) -> Option<&'ast ConstantDeclaration<'a>> {
         self.scopes
-            .get(0)?
+            .first()?
             .name_spaces
             .declared
             .get("Main")
             .map(|declared| match declared {
                 DeclaredElement::Constant(constant) => *constant,
+                DeclaredElement::Binding(_, _) => {
+                    panic!("Main can only be declared as constant. Found destructuring")
+                }
+                DeclaredElement::PatternBinding(_, _) => {
+                    unreachable!("Patterns cannot bind names in the global scope")
+                }
                 DeclaredElement::Struct(_) => {
                     panic!("Main can only be declared as constant. Found struct")
                 }
                 DeclaredElement::Trait(_) => {
                     panic!("Main can only be declared as constant. Found trait")
                 }
+                DeclaredElement::TypeAlias(_) => {
+                    panic!("Main can only be declared as constant. Found type alias")
+                }
             })
     }
 }
@@ -7,6 +7,7 @@ mod environment;
 mod module_verifier;
 mod name_resolution;
 pub mod scope;
+mod source_map;
 
 pub use environment::{Environment, Resolved};
 pub type EnvironmentBuilder<'ast, 'a> = env_builder::EnvironmentBuilder<'ast, 'a, 0>;
@@ -56,18 +56,17 @@ impl<'ast, 'a, 'env> ResolveHelper<'ast, 'a, 'env> {
             .iter()
             .copied()
             .filter_map(|scope_id| match searched_scopes.insert(scope_id) {
-                true => {
-                    let scope = self.0.get_scope(scope_id);
-                    self.try_resolve_name(scope, name, searched_scopes)
-                }
+                true => self.resolve_exported(scope_id, name, scope.id),
                 false => None,
             })
-            .take(2)
             .collect::<Vec<_>>();
-        if resolved.len() > 1 {
-            panic!(
-                "Name `{name}` is ambiguous. There are more than one options in wildcard imports"
-            )
+        // the same declaration can be reached from different modules through re-exports
+        if let Some((first, rest)) = resolved.split_first() {
+            if rest.iter().any(|other| !first.refers_to_same(other)) {
+                panic!(
+                    "Name `{name}` is ambiguous. There are more than one options in wildcard imports"
+                )
+            }
         }
         resolved.pop()
     }
@@ -99,14 +98,31 @@ impl<'ast, 'a, 'env> ResolveHelper<'ast, 'a, 'env> {
         while let Some(component) = access_iter.peek() {
             last_resolved = match last_resolved {
                 Resolved::Module(module_scope) => self
-                    .resolve_in_module(module_scope, component)
+                    .resolve_in_module(module_scope, component, scope)
                     .unwrap_or_else(|| panic!("`{component}` cannot be found in module")),
                 Resolved::Constant(constant) => {
                     return Resolved::InstanceAccess(constant, access_iter.copied().collect())
                 }
+                Resolved::Binding(destructuring, index) => {
+                    return Resolved::BindingAccess(
+                        destructuring,
+                        index,
+                        access_iter.copied().collect(),
+                    )
+                }
+                Resolved::PatternBinding(r#match, index) => {
+                    return Resolved::PatternBindingAccess(
+                        r#match,
+                        index,
+                        access_iter.copied().collect(),
+                    )
+                }
                 Resolved::Trait(_) => panic!("Cannot access field from trait type definition"),
                 Resolved::Struct(_) => panic!("Cannot access field from struct type definition"),
-                Resolved::InstanceAccess { .. } => {
+                Resolved::TypeAlias(_) => panic!("Cannot access field from type alias"),
+                Resolved::InstanceAccess { .. }
+                | Resolved::BindingAccess { .. }
+                | Resolved::PatternBindingAccess { .. } => {
                     unreachable!("Field cannot be found at this stage")
                 }
             };
@@ -115,12 +131,35 @@ impl<'ast, 'a, 'env> ResolveHelper<'ast, 'a, 'env> {
         last_resolved
     }
 
+    /// Resolves a name declared in another module, which must be visible from the `from` scope
     pub(in crate::env::name_resolution) fn resolve_in_module(
         &self,
         module_scope: ScopeId,
         name: &'a str,
+        from: ScopeId,
     ) -> Option<Resolved<'ast, 'a>> {
-        let module_scope = self.0.get_scope(module_scope);
-        Self::resolve_declared(module_scope, name).or_else(|| Self::resolve_mod(module_scope, name))
+        let module = self.0.get_scope(module_scope);
+        let resolved = self
+            .0
+            .find_visible(module_scope, name, from)
+            .map(Resolved::from);
+        if resolved.is_none() && module.name_spaces.declared.contains_key(name) {
+            panic!("`{name}` is private and cannot be used outside its module")
+        }
+        resolved.or_else(|| Self::resolve_mod(module, name))
+    }
+
+    /// Wildcard imports only bring in names that are visible from the importing scope,
+    /// names imported by the source module are not imported again
+    fn resolve_exported(
+        &self,
+        module_scope: ScopeId,
+        name: &str,
+        from: ScopeId,
+    ) -> Option<Resolved<'ast, 'a>> {
+        self.0
+            .find_visible(module_scope, name, from)
+            .map(Resolved::from)
+            .or_else(|| Self::resolve_mod(self.0.get_scope(module_scope), name))
     }
 }
@@ -1,5 +1,8 @@
 use super::super::scope::ScopeId;
-use crate::ast::{ConstantDeclaration, StructDeclaration, TraitDeclaration};
+use crate::ast::{
+    ConstantDeclaration, DestructuringDeclaration, Match, StructDeclaration, TraitDeclaration,
+    TypeAlias,
+};
 use crate::env::scope::DeclaredElement;
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
@@ -11,7 +14,12 @@ pub enum Resolved<'ast, 'a> {
     Constant(&'ast ConstantDeclaration<'a>),
     Struct(&'ast StructDeclaration<'a>),
     Trait(&'ast TraitDeclaration<'a>),
+    TypeAlias(&'ast TypeAlias<'a>),
     InstanceAccess(&'ast ConstantDeclaration<'a>, Vec<&'a str>),
+    Binding(&'ast DestructuringDeclaration<'a>, usize),
+    BindingAccess(&'ast DestructuringDeclaration<'a>, usize, Vec<&'a str>),
+    PatternBinding(&'ast Match<'a>, usize),
+    PatternBindingAccess(&'ast Match<'a>, usize, Vec<&'a str>),
 }
 
 impl<'ast, 'a> From<DeclaredElement<'ast, 'a>> for Resolved<'ast, 'a> {
@@ -20,6 +28,42 @@ impl<'ast, 'a> From<DeclaredElement<'ast, 'a>> for Resolved<'ast, 'a> {
             DeclaredElement::Struct(r#struct) => Self::Struct(r#struct),
             DeclaredElement::Constant(constant) => Self::Constant(constant),
             DeclaredElement::Trait(r#trait) => Self::Trait(r#trait),
+            DeclaredElement::TypeAlias(alias) => Self::TypeAlias(alias),
+            DeclaredElement::Binding(destructuring, index) => Self::Binding(destructuring, index),
+            DeclaredElement::PatternBinding(r#match, index) => Self::PatternBinding(r#match, index),
+        }
+    }
+}
+
+impl<'ast, 'a> Resolved<'ast, 'a> {
+    /// Checks whether both resolve to the same module or declaration
+    pub fn refers_to_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Resolved::Module(first), Resolved::Module(second)) => first == second,
+            _ => match (self.declared(), other.declared()) {
+                (Some(first), Some(second)) => first.refers_to_same(&second),
+                _ => false,
+            },
+        }
+    }
+
+    /// The declaration the name refers to, modules and accesses into instances have none
+    fn declared(&self) -> Option<DeclaredElement<'ast, 'a>> {
+        match self {
+            Resolved::Constant(constant) => Some(DeclaredElement::Constant(constant)),
+            Resolved::Struct(r#struct) => Some(DeclaredElement::Struct(r#struct)),
+            Resolved::Trait(r#trait) => Some(DeclaredElement::Trait(r#trait)),
+            Resolved::TypeAlias(alias) => Some(DeclaredElement::TypeAlias(alias)),
+            Resolved::Binding(destructuring, index) => {
+                Some(DeclaredElement::Binding(destructuring, *index))
+            }
+            Resolved::PatternBinding(r#match, index) => {
+                Some(DeclaredElement::PatternBinding(r#match, *index))
+            }
+            Resolved::Module(_)
+            | Resolved::InstanceAccess(..)
+            | Resolved::BindingAccess(..)
+            | Resolved::PatternBindingAccess(..) => None,
         }
     }
 }
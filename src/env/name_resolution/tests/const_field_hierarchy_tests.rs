@@ -64,7 +64,6 @@ fn test_indirect_cycle_reference() {
 }
 
 #[test]
-#[should_panic]
 fn test_field_reference_to_field() {
     test_constant_field_cycle_reference(
         r#"
@@ -74,6 +73,30 @@ fn test_field_reference_to_field() {
     )
 }
 
+#[test]
+#[should_panic(expected = "Cycle detected in default values of struct `A`")]
+fn test_field_reference_cycle() {
+    test_constant_field_cycle_reference(
+        r#"
+    struct A(field1: String = self.field2, field2: String = self.field1)
+    "#,
+        vec![vec![]],
+    )
+}
+
+#[test]
+#[should_panic(expected = "which is not a field of struct `A`")]
+fn test_field_reference_to_attribute() {
+    test_constant_field_cycle_reference(
+        r#"
+    struct A(field1: String, field2: String = self.attribute) {
+        const attribute = self.field1
+    }
+    "#,
+        vec![vec![]],
+    )
+}
+
 #[test]
 #[should_panic]
 fn test_field_reference_to_field_without_self() {
@@ -30,8 +30,8 @@ fn test_constant_field() {
         const val = person.id.digit
         "#,
         ))),
-        abstract_tree(parse(tokenize("struct Person(id: system.Id)\n"))),
-        abstract_tree(parse(tokenize("struct Id(digit: Int)\n"))),
+        abstract_tree(parse(tokenize("pub struct Person(id: system.Id)\n"))),
+        abstract_tree(parse(tokenize("pub struct Id(digit: Int)\n"))),
     ];
     let module_paths = vec![vec![], vec!["person"], vec!["system"]];
     let mut target_name = Name::qualified(["person", "id", "digit"]);
@@ -54,7 +54,7 @@ fn test_field_over_package() {
         abstract_tree(parse(tokenize(
             r#"
         struct Test(test: Int)
-        const test = Test(3)
+        pub const test = Test(3)
         "#,
         ))),
         abstract_tree(parse(tokenize("const test = 5\n"))),
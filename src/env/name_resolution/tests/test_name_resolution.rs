@@ -19,7 +19,7 @@ fn resolve_module_constant() {
 
     let mut syntax_trees = vec![
         abstract_tree(parse(tokenize("const a = test.target\n"))),
-        abstract_tree(parse(tokenize("const target = 3\n"))),
+        abstract_tree(parse(tokenize("pub const target = 3\n"))),
     ];
     let module_paths = vec![vec![], vec!["test"]];
     let mut name = Name::qualified(["test", "target"]);
@@ -53,7 +53,7 @@ fn resolve_module_struct() {
 
     let mut syntax_trees = vec![
         abstract_tree(parse(tokenize("const a = empty.Empty()\n"))),
-        abstract_tree(parse(tokenize("struct Empty\n"))),
+        abstract_tree(parse(tokenize("pub struct Empty\n"))),
     ];
     let module_paths = vec![vec![], vec!["empty"]];
     let mut target_name = Name::qualified(["empty", "Empty"]);
@@ -79,3 +79,56 @@ fn resolve_module_struct() {
     );
     assert!(std::ptr::eq(actual, expected));
 }
+
+#[test]
+#[should_panic(expected = "`target` is private and cannot be used outside its module")]
+fn resolve_private_module_constant() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = vec![
+        abstract_tree(parse(tokenize("const a = test.target\n"))),
+        abstract_tree(parse(tokenize("const target = 3\n"))),
+    ];
+    let module_paths = vec![vec![], vec!["test"]];
+    Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build();
+}
+
+#[test]
+fn resolve_re_exported_constant() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let mut syntax_trees = vec![
+        abstract_tree(parse(tokenize("const a = facade.target\n"))),
+        abstract_tree(parse(tokenize("pub use facade.inner.*\n"))),
+        abstract_tree(parse(tokenize("pub const target = 3\n"))),
+    ];
+    let module_paths = vec![vec![], vec!["facade"], vec!["facade", "inner"]];
+    let mut name = Name::qualified(["facade", "target"]);
+    name.set_scope(GLOBAL_SCOPE);
+    let resolved = Environment::builder()
+        .add_modules(&module_paths)
+        .generate_scopes(&mut syntax_trees)
+        .resolve_names(&syntax_trees)
+        .build()
+        .resolved_names;
+    let actual = try_block!(
+        &ConstantDeclaration,
+        resolved.get(&name)?.as_constant().copied()
+    );
+    let expected = try_block!(
+        &ConstantDeclaration,
+        syntax_trees
+            .last()?
+            .compilation_unit
+            .declarations
+            .last()?
+            .as_constant()
+    );
+    assert!(std::ptr::eq(actual, expected))
+}
@@ -9,7 +9,7 @@ use crate::tokenizer::tokenize;
 #[test]
 fn test_resolve_constant_from_block() {
     test_resolve_from_wildcard_import([
-        "const name = \"Test Name\"\n",
+        "pub const name = \"Test Name\"\n",
         r#"use test.source.*
            const test = { name }
         "#,
@@ -19,7 +19,7 @@ fn test_resolve_constant_from_block() {
 #[test]
 fn test_resolve_constant_from_struct_init() {
     test_resolve_from_wildcard_import([
-        "const name = \"Test Name\"\n",
+        "pub const name = \"Test Name\"\n",
         r#"use test.source.*
            const test = View { Text(name) }
         "#,
@@ -29,7 +29,7 @@ fn test_resolve_constant_from_struct_init() {
 #[test]
 fn test_resolve_constant_from_struct_body() {
     test_resolve_from_wildcard_import([
-        "const name = \"Test Name\"\n",
+        "pub const name = \"Test Name\"\n",
         r#"use test.source.*
            struct View {
                 const title = name
@@ -98,6 +98,27 @@ fn test_unresolvable_name() {
     assert!(unresolved.is_none())
 }
 
+#[test]
+fn test_private_name_from_wildcard_import() {
+    let formula = FormulaSuppress::all();
+    formula.suppress();
+
+    let module_path: Vec<Vec<&str>> = vec![vec!["test", "source"], vec!["test", "target"]];
+    let mut syntax_trees = vec![
+        abstract_tree(parse(tokenize("const name = \"Test Name\"\n"))),
+        abstract_tree(parse(tokenize("use test.source.*\n"))),
+    ];
+    let mut env = Environment::builder()
+        .add_modules(&module_path)
+        .generate_scopes(&mut syntax_trees)
+        .build();
+    declaration_resolution::resolve(&mut env, &syntax_trees, &module_path);
+    let helper = ResolveHelper(&env);
+    let source_scope_id = env.find_module(&["test", "target"]).unwrap();
+    let unresolved = helper.resolve(source_scope_id, "name");
+    assert!(unresolved.is_none())
+}
+
 #[test]
 fn test_shaded_name() {
     let formula = FormulaSuppress::all();
@@ -141,8 +162,8 @@ fn test_shaded_name() {
 fn test_ambiguous_wildcard_imports() {
     let module_path = [vec!["first"], vec!["second"], vec!["target"]];
     let mut syntax_trees = [
-        abstract_tree(parse(tokenize("const name = \"Test Name\"\n"))),
-        abstract_tree(parse(tokenize("const name = 3\n"))),
+        abstract_tree(parse(tokenize("pub const name = \"Test Name\"\n"))),
+        abstract_tree(parse(tokenize("pub const name = 3\n"))),
         abstract_tree(parse(tokenize(
             r#"
         use first.*
@@ -34,11 +34,15 @@ impl<'ast, 'a, 'env> TypeLinker<'ast, 'a, 'env> {
             }
         }
         .map(|resolved| match resolved {
-            Resolved::Struct(_) | Resolved::Trait(_) => resolved,
-            Resolved::InstanceAccess(_, _) => {
+            Resolved::Struct(_) | Resolved::Trait(_) | Resolved::TypeAlias(_) => resolved,
+            Resolved::InstanceAccess(_, _)
+            | Resolved::BindingAccess(_, _, _)
+            | Resolved::PatternBindingAccess(_, _, _) => {
                 panic!("Type name `{}` resolved to field access", name)
             }
-            Resolved::Constant(_) => panic!("Type name `{}` resolved to constant", name),
+            Resolved::Constant(_) | Resolved::Binding(_, _) | Resolved::PatternBinding(_, _) => {
+                panic!("Type name `{}` resolved to constant", name)
+            }
             Resolved::Module(_) => panic!("Type name `{}` resolved to module", name),
         })
     }
@@ -46,7 +50,7 @@ impl<'ast, 'a, 'env> TypeLinker<'ast, 'a, 'env> {
     fn link_type_in_module(&self, name: &'ast Name<'a>) -> Option<Resolved<'ast, 'a>> {
         let resolved = ResolveHelper(self.0).disambiguate(name.scope(), &name.moniker);
         match resolved {
-            Resolved::Struct(_) | Resolved::Trait(_) => Some(resolved),
+            Resolved::Struct(_) | Resolved::Trait(_) | Resolved::TypeAlias(_) => Some(resolved),
             _ => None,
         }
     }
@@ -55,7 +59,7 @@ impl<'ast, 'a, 'env> TypeLinker<'ast, 'a, 'env> {
         let is_primitive_name = |name: &str| {
             matches!(
                 name,
-                "Int" | "Float" | "String" | "Bool" | "Void" | "Children"
+                "Int" | "Float" | "String" | "Bool" | "Void" | "Length" | "Color" | "Children"
             )
         };
         match &name.moniker {
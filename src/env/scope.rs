@@ -1,4 +1,7 @@
-use crate::ast::{ConstantDeclaration, StructDeclaration, TraitDeclaration};
+use crate::ast::{
+    ConstantDeclaration, DestructuringDeclaration, Documentation, Match, StructDeclaration,
+    TraitDeclaration, TypeAlias,
+};
 #[cfg(test)]
 use enum_as_inner::EnumAsInner;
 use std::collections::{HashMap, HashSet};
@@ -6,6 +9,8 @@ use std::collections::{HashMap, HashSet};
 pub(crate) type ScopeId = usize;
 pub(crate) const GLOBAL_SCOPE: usize = 0;
 
+static UNDOCUMENTED: Documentation<'static> = Documentation(Vec::new());
+
 pub trait Scoped {
     fn set_scope(&mut self, scope: ScopeId);
     fn scope(&self) -> ScopeId;
@@ -33,6 +38,10 @@ pub struct NameSpaces<'ast, 'a> {
     pub modules: HashMap<&'a str, ScopeId>,
     pub wildcard_imports: HashSet<ScopeId>,
     pub declared: HashMap<&'a str, DeclaredElement<'ast, 'a>>,
+    /// Declared names that are marked as `pub` and can be used outside the module
+    pub exported: HashSet<&'a str>,
+    /// Modules imported with `pub use module.*`, whose exported names are exported here as well
+    pub re_exported_wildcards: HashSet<ScopeId>,
 }
 
 #[cfg_attr(test, derive(Debug, EnumAsInner))]
@@ -41,6 +50,11 @@ pub enum DeclaredElement<'ast, 'a> {
     Constant(&'ast ConstantDeclaration<'a>),
     Struct(&'ast StructDeclaration<'a>),
     Trait(&'ast TraitDeclaration<'a>),
+    TypeAlias(&'ast TypeAlias<'a>),
+    /// One of the names declared by a destructuring, with its position in the tuple
+    Binding(&'ast DestructuringDeclaration<'a>, usize),
+    /// The instance matched by a pattern like `figure: Figure`, with the position of its arm
+    PatternBinding(&'ast Match<'a>, usize),
 }
 
 impl<'ast, 'a> From<&'ast ConstantDeclaration<'a>> for DeclaredElement<'ast, 'a> {
@@ -61,6 +75,12 @@ impl<'ast, 'a> From<&'ast TraitDeclaration<'a>> for DeclaredElement<'ast, 'a> {
     }
 }
 
+impl<'ast, 'a> From<&'ast TypeAlias<'a>> for DeclaredElement<'ast, 'a> {
+    fn from(alias: &'ast TypeAlias<'a>) -> Self {
+        Self::TypeAlias(alias)
+    }
+}
+
 impl<'ast, 'a> PartialEq<TraitDeclaration<'a>> for DeclaredElement<'ast, 'a> {
     fn eq(&self, other: &TraitDeclaration<'a>) -> bool {
         let trait_declaration = match self {
@@ -97,6 +117,51 @@ impl<'ast, 'a> DeclaredElement<'ast, 'a> {
             DeclaredElement::Constant(constant) => constant.name,
             DeclaredElement::Struct(struct_declaration) => struct_declaration.name,
             DeclaredElement::Trait(trait_declaration) => trait_declaration.name,
+            DeclaredElement::TypeAlias(alias) => alias.name,
+            DeclaredElement::Binding(destructuring, index) => destructuring.names[*index],
+            DeclaredElement::PatternBinding(r#match, index) => r#match.arms[*index]
+                .binding()
+                .expect("Arm should bind a name"),
+        }
+    }
+
+    /// Checks whether both elements come from the same declaration,
+    /// an element can be reached through different names because of aliases and re-exports
+    pub fn refers_to_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DeclaredElement::Constant(first), DeclaredElement::Constant(second)) => {
+                std::ptr::eq(*first, *second)
+            }
+            (DeclaredElement::Struct(first), DeclaredElement::Struct(second)) => {
+                std::ptr::eq(*first, *second)
+            }
+            (DeclaredElement::Trait(first), DeclaredElement::Trait(second)) => {
+                std::ptr::eq(*first, *second)
+            }
+            (DeclaredElement::TypeAlias(first), DeclaredElement::TypeAlias(second)) => {
+                std::ptr::eq(*first, *second)
+            }
+            (
+                DeclaredElement::Binding(first, first_index),
+                DeclaredElement::Binding(second, second_index),
+            ) => std::ptr::eq(*first, *second) && first_index == second_index,
+            (
+                DeclaredElement::PatternBinding(first, first_index),
+                DeclaredElement::PatternBinding(second, second_index),
+            ) => std::ptr::eq(*first, *second) && first_index == second_index,
+            _ => false,
+        }
+    }
+
+    /// The `///` comments written before the declaration, kept for tooling such as hover hints
+    pub fn documentation(&self) -> &'ast Documentation<'a> {
+        match self {
+            DeclaredElement::Constant(constant) => &constant.documentation,
+            DeclaredElement::Struct(struct_declaration) => &struct_declaration.documentation,
+            DeclaredElement::Trait(trait_declaration) => &trait_declaration.documentation,
+            DeclaredElement::TypeAlias(alias) => &alias.documentation,
+            DeclaredElement::Binding(destructuring, _) => &destructuring.documentation,
+            DeclaredElement::PatternBinding(_, _) => &UNDOCUMENTED,
         }
     }
 }
@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Contents of the compiled files, used to find where a lexeme was written
+#[derive(Default)]
+pub struct SourceMap<'a>(Vec<(&'a Path, &'a str)>);
+
+/// Position of a lexeme in a source file, lines and columns start from 1
+#[derive(Debug, PartialEq)]
+pub struct Location<'a> {
+    pub path: &'a Path,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn add(&mut self, path: &'a Path, content: &'a str) {
+        self.0.push((path, content));
+    }
+
+    /// Finds the source containing the lexeme, which should be a slice of one of the contents
+    pub fn locate(&self, lexeme: &str) -> Option<Location<'a>> {
+        let address = lexeme.as_ptr() as usize;
+        self.0.iter().find_map(|(path, content)| {
+            let offset = address.checked_sub(content.as_ptr() as usize)?;
+            let preceding = content.get(..offset)?;
+            content.get(offset..offset + lexeme.len())?;
+            let line = preceding.matches('\n').count() + 1;
+            let line_start = preceding.rfind('\n').map_or(0, |index| index + 1);
+            let column = preceding[line_start..].chars().count() + 1;
+            Some(Location { path, line, column })
+        })
+    }
+}
+
+impl Display for Location<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_lexeme() {
+        let content = "const A = 1\nconst Bé = 2\n";
+        let mut sources = SourceMap::default();
+        sources.add(Path::new("main.ds"), content);
+        let location = sources.locate(&content[22..23]).unwrap();
+        assert_eq!(location.to_string(), "main.ds:2:10");
+    }
+
+    #[test]
+    fn test_locate_foreign_lexeme() {
+        let mut sources = SourceMap::default();
+        sources.add(Path::new("main.ds"), "const A = 1\n");
+        assert_eq!(sources.locate(&String::from("A")), None);
+    }
+}
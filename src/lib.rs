@@ -14,6 +14,35 @@ mod stdlib;
 mod tokenizer;
 
 pub fn compile<P: AsRef<Path>>(source_file_names: &[P]) -> Vec<u8> {
+    with_environment(source_file_names, |environment| {
+        code_generation::generate_code(environment).unwrap_or_else(|failures| {
+            let errors = failures
+                .iter()
+                .map(|failure| match environment.sources.locate(failure.keyword) {
+                    Some(location) => format!("{location}: {failure}"),
+                    None => format!("<unknown>: {failure}"),
+                })
+                .collect::<Vec<_>>();
+            panic!("{}", errors.join("\n"))
+        })
+    })
+}
+
+/// Finds the `///` documentation of a declaration by its path, e.g. `["models", "Tree"]`,
+/// where declarations of the entry file are found by their names only
+pub fn documentation<P: AsRef<Path>>(source_file_names: &[P], path: &[&str]) -> Option<String> {
+    with_environment(source_file_names, |environment| {
+        environment
+            .documentation(path)
+            .map(|documentation| documentation.to_string())
+    })
+}
+
+/// Compiles the source files along with the standard library into a validated environment
+fn with_environment<P: AsRef<Path>, R>(
+    source_file_names: &[P],
+    action: impl FnOnce(&env::Environment) -> R,
+) -> R {
     let file_content = source_file_names.iter().map(read_file).collect::<Vec<_>>();
     let mut compiled_syntax_trees = stdlib::CONTENT
         .into_iter()
@@ -23,19 +52,20 @@ pub fn compile<P: AsRef<Path>>(source_file_names: &[P]) -> Vec<u8> {
         .map(ast::abstract_tree)
         .collect::<Vec<_>>();
     let environment = env::Environment::builder()
-        .add_modules_from_paths(
-            stdlib::PATHS.into_iter().map(Path::new).chain(
+        .add_modules_from_sources(
+            stdlib::PATHS.into_iter().map(Path::new).zip(stdlib::CONTENT).chain(
                 source_file_names
                     .iter()
                     .inspect(prohibit_std_injection)
-                    .map(AsRef::as_ref),
+                    .map(AsRef::as_ref)
+                    .zip(file_content.iter().map(AsRef::as_ref)),
             ),
         )
         .generate_scopes(&mut compiled_syntax_trees)
         .resolve_names(&compiled_syntax_trees)
         .validate(&compiled_syntax_trees)
         .build();
-    code_generation::generate_code(&environment)
+    action(&environment)
 }
 
 fn read_file<P: AsRef<Path>>(path: P) -> impl AsRef<str> {
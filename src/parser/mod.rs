@@ -16,42 +16,76 @@ pub fn parse<'a>(tokens: impl Iterator<Item = Token<'a>>) -> models::ParseTree<'
                 .expect("Unable to start"),
         ];
     let mut node_stack: Vec<Node> = vec![Node::Leaf(parsing::START_TOKEN)];
-    let top = |stack: &[_]| stack.last().copied().expect("Empty stack");
-    let mut tokens = tokens.chain(std::iter::once(parsing::END_TOKEN)).peekable();
-    while let Some(token) = tokens.next() {
-        let normalized_tokens = skip_or_insert_new_lines(token, tokens.peek());
-        for token in normalized_tokens {
-            while let Some(production) = parsing::reduce(top(&state_stack), token) {
-                let new_stack_size = node_stack.len() - production.rhs.len();
-                let children = node_stack.drain(new_stack_size..).collect::<Vec<_>>();
-                state_stack.truncate(new_stack_size);
-
-                node_stack.push(Node::Internal {
-                    kind: production.lhs,
-                    children,
-                });
-                state_stack.push(
-                    parsing::transit(top(&state_stack), Symbol::NonTerminal(production.lhs))
-                        .unwrap_or_else(|| {
-                            panic!("Unable to transit. node_stack={:?}", node_stack)
-                        }),
-                );
-            }
-            node_stack.push(Node::Leaf(token));
-            state_stack.push(
-                parsing::transit(top(&state_stack), Symbol::Terminal(token)).unwrap_or_else(|| {
-                    panic!(
-                        "Parsing error at token: {:?}. Stack: {:?}",
-                        token, state_stack
-                    )
-                }),
-            );
+    let tokens = tokens.chain(std::iter::once(parsing::END_TOKEN));
+    let mut tokens = with_documentation(tokens).peekable();
+    while let Some((mut documentation, token)) = tokens.next() {
+        let next_token = tokens.peek().map(|(_, next_token)| next_token);
+        for token in skip_or_insert_new_lines(token, next_token) {
+            shift(&mut state_stack, &mut node_stack, token, &mut documentation);
         }
     }
     node_stack.pop();
     ParseTree::from(node_stack.pop().expect("node_stack is empty"))
 }
 
+/// Reduces the stacks with the token as lookahead, then shifts the token.
+///
+/// Documentations are only kept before declarations, which is where the action table reduces
+/// an empty documentation with the token as lookahead. They are shifted at that point,
+/// and anywhere else they are skipped like normal comments.
+fn shift<'a>(
+    state_stack: &mut Vec<State>,
+    node_stack: &mut Vec<Node<'a>>,
+    token: Token<'a>,
+    documentation: &mut Vec<Token<'a>>,
+) {
+    let top = |stack: &[_]| stack.last().copied().expect("Empty stack");
+    while let Some(production) = parsing::reduce(top(state_stack), token) {
+        let new_stack_size = node_stack.len() - production.rhs.len();
+        let children = node_stack.drain(new_stack_size..).collect::<Vec<_>>();
+        state_stack.truncate(new_stack_size);
+
+        node_stack.push(Node::Internal {
+            kind: production.lhs,
+            children,
+        });
+        state_stack.push(
+            parsing::transit(top(state_stack), Symbol::NonTerminal(production.lhs))
+                .unwrap_or_else(|| panic!("Unable to transit. node_stack={:?}", node_stack)),
+        );
+        if production.lhs == NodeKind::Documentation && production.rhs.is_empty() {
+            for doc_comment in std::mem::take(documentation) {
+                shift(state_stack, node_stack, doc_comment, &mut vec![]);
+            }
+        }
+    }
+    node_stack.push(Node::Leaf(token));
+    state_stack.push(
+        parsing::transit(top(state_stack), Symbol::Terminal(token)).unwrap_or_else(|| {
+            panic!(
+                "Parsing error at token: {:?}. Stack: {:?}",
+                token, state_stack
+            )
+        }),
+    );
+}
+
+/// Groups every token with the documentations preceding it
+fn with_documentation<'a>(
+    mut tokens: impl Iterator<Item = Token<'a>>,
+) -> impl Iterator<Item = (Vec<Token<'a>>, Token<'a>)> {
+    std::iter::from_fn(move || {
+        let mut documentation = vec![];
+        loop {
+            let token = tokens.next()?;
+            if token.kind != TokenKind::DocComment {
+                return Some((documentation, token));
+            }
+            documentation.push(token);
+        }
+    })
+}
+
 /// Skip or insert new line based on the current and next token.
 /// When it skips, it returns an empty iterator
 /// When it inserts, it return the an iterator with current token followed by a new line token
@@ -141,7 +175,47 @@ mod parse_tests {
         assert_eq!(actual, expected)
     }
 
-    fn tokenize(text: &str) -> impl Iterator<Item = Token> {
+    #[test]
+    fn test_documentation_at_end_of_file() {
+        let text = "const i = 3\n/// trailing\n";
+        let parse_tree = super::parse(tokenize(text));
+        let actual = first_child(&parse_tree.root, 3)
+            .and_then(Node::kind)
+            .expect("None obtained");
+        assert_eq!(actual, NodeKind::ConstantDeclarationStatement)
+    }
+
+    #[test]
+    fn test_documentation_before_block_expression() {
+        let text = "const i = {\n/// not a declaration\n3\n}\n";
+        let parse_tree = super::parse(tokenize(text));
+        let actual = first_child(&parse_tree.root, 3)
+            .and_then(Node::kind)
+            .expect("None obtained");
+        assert_eq!(actual, NodeKind::ConstantDeclarationStatement)
+    }
+
+    #[test]
+    fn test_documentation_in_struct_init_arguments() {
+        let text = "const s = View(\n/// not a field\nsize: 5)\n";
+        let parse_tree = super::parse(tokenize(text));
+        let actual = first_child(&parse_tree.root, 3)
+            .and_then(Node::kind)
+            .expect("None obtained");
+        assert_eq!(actual, NodeKind::ConstantDeclarationStatement)
+    }
+
+    #[test]
+    fn test_documentation_at_end_of_struct_body() {
+        let text = "struct S {\nconst i = 3\n/// trailing\n}\n";
+        let parse_tree = super::parse(tokenize(text));
+        let actual = first_child(&parse_tree.root, 3)
+            .and_then(Node::kind)
+            .expect("None obtained");
+        assert_eq!(actual, NodeKind::StructDeclarationStatement)
+    }
+
+    fn tokenize(text: &str) -> impl Iterator<Item = Token<'_>> {
         crate::tokenizer::tokenize(text)
     }
 
@@ -6,17 +6,52 @@ pub fn comment(cursor: &mut Cursor) -> usize {
     cursor.eat_while(|c| !whitespace::is_line_terminator_start(c))
 }
 
+/// Documentations start with exactly three slashes, `////` is still a normal comment
+pub fn is_doc_comment(cursor: &Cursor) -> bool {
+    debug_assert!(has_comment_header(cursor));
+    cursor.nth(2) == Some('/') && cursor.nth(3) != Some('/')
+}
+
+/// Block comments can be nested, `/* /* */ */` is a single comment
+pub fn block_comment(cursor: &mut Cursor) -> usize {
+    debug_assert!(has_block_comment_header(cursor));
+    let mut length = 0;
+    let mut depth = 0;
+    loop {
+        match (cursor.first(), cursor.second()) {
+            (Some('/'), Some('*')) => depth += 1,
+            (Some('*'), Some('/')) => depth -= 1,
+            (Some(c), _) => {
+                cursor.bump();
+                length += c.len_utf8();
+                continue;
+            }
+            (None, _) => panic!("Unterminated block comment"),
+        }
+        cursor.bump();
+        cursor.bump();
+        length += 2;
+        if depth == 0 {
+            return length;
+        }
+    }
+}
+
 fn has_comment_header(cursor: &Cursor) -> bool {
     matches!(cursor.first().zip(cursor.second()), Some(('/', '/')))
 }
 
+fn has_block_comment_header(cursor: &Cursor) -> bool {
+    matches!(cursor.first().zip(cursor.second()), Some(('/', '*')))
+}
+
 #[cfg(test)]
 mod comment_test {
     use quickcheck_macros::quickcheck;
 
-    use super::comment;
     use super::whitespace;
     use super::Cursor;
+    use super::{block_comment, comment, is_doc_comment};
 
     #[quickcheck]
     fn test_tokenize_comment(mut content: String) -> bool {
@@ -27,4 +62,36 @@ mod comment_test {
         let actual_length = comment(&mut Cursor::from_iter(content.chars()));
         expected_length == actual_length
     }
+
+    #[test]
+    fn test_doc_comment() {
+        assert!(is_doc_comment(&Cursor::from_iter("/// doc".chars())));
+        assert!(is_doc_comment(&Cursor::from_iter("///".chars())));
+        assert!(!is_doc_comment(&Cursor::from_iter("// comment".chars())));
+        assert!(!is_doc_comment(&Cursor::from_iter("//// comment".chars())));
+    }
+
+    #[test]
+    fn test_block_comment() {
+        let content = "/* a\n b */ rest";
+        assert_eq!(
+            block_comment(&mut Cursor::from_iter(content.chars())),
+            content.len() - " rest".len()
+        );
+    }
+
+    #[test]
+    fn test_nested_block_comment() {
+        let content = "/* outer /* inner */ still outer */ rest";
+        assert_eq!(
+            block_comment(&mut Cursor::from_iter(content.chars())),
+            content.len() - " rest".len()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unterminated_block_comment() {
+        block_comment(&mut Cursor::from_iter("/* /* */".chars()));
+    }
 }
@@ -16,6 +16,10 @@ pub fn is_keyword(s: &str) -> bool {
             | "use"
             | "trait"
             | "in"
+            | "pub"
+            | "as"
+            | "match"
+            | "assert"
     )
 }
 
@@ -25,7 +29,7 @@ mod keyword_tests {
 
     const KEYWORDS: &[&str] = &[
         "break", "const", "continue", "else", "for", "fn", "if", "impl", "return", "super",
-        "struct", "self", "use", "trait", "in",
+        "struct", "self", "use", "trait", "in", "pub", "as", "match", "assert",
     ];
 
     #[test]
@@ -0,0 +1,58 @@
+use crate::tokenizer::LiteralKind;
+
+use super::Cursor;
+
+pub fn color(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    debug_assert!(matches!(cursor.first(), Some('#')));
+    let _hash = cursor.bump().expect("color should start with #").len_utf8();
+    let digits = cursor.eat_while(|c| c.is_ascii_hexdigit());
+    if let Some(c) = cursor.first().filter(|&c| c.is_alphanumeric() || c == '_') {
+        panic!("Invalid color literal, `{c}` is not a hex digit")
+    }
+    assert!(
+        matches!(digits, 3 | 4 | 6 | 8),
+        "Color literal should have 3, 4, 6 or 8 hex digits following `#`"
+    );
+    (_hash + digits, LiteralKind::Color)
+}
+
+#[cfg(test)]
+mod color_tests {
+    use super::*;
+
+    #[test]
+    fn test_color() {
+        let targets = ["#fff", "#FFF8", "#1e90ff", "#1E90FF80"];
+        for target in targets {
+            let mut cursor = Cursor::from_iter(target.chars());
+            assert_eq!(color(&mut cursor), (target.len(), LiteralKind::Color))
+        }
+    }
+
+    #[test]
+    fn test_color_followed_by_separator() {
+        let mut cursor = Cursor::from_iter("#1e90ff)".chars());
+        assert_eq!(color(&mut cursor).0, "#1e90ff".len())
+    }
+
+    #[test]
+    #[should_panic(expected = "Color literal should have 3, 4, 6 or 8 hex digits")]
+    fn test_empty_color() {
+        let mut cursor = Cursor::from_iter("#".chars());
+        color(&mut cursor);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid color literal, `g` is not a hex digit")]
+    fn test_color_with_non_hex_digit() {
+        let mut cursor = Cursor::from_iter("#abcdefg".chars());
+        color(&mut cursor);
+    }
+
+    #[test]
+    #[should_panic(expected = "Color literal should have 3, 4, 6 or 8 hex digits")]
+    fn test_color_with_five_digits() {
+        let mut cursor = Cursor::from_iter("#12345".chars());
+        color(&mut cursor);
+    }
+}
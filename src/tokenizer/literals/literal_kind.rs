@@ -1,9 +1,18 @@
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum LiteralKind {
     String,
+    /// Beginning of a string until its first interpolation, e.g. `"Page {`
+    StringHead,
+    /// Part of a string between two interpolations, e.g. `} of {`
+    StringMiddle,
+    /// End of a string after its last interpolation, e.g. `}"`
+    StringTail,
     Integer,
     Binary,
+    Octal,
     Hex,
     Floating,
     Boolean,
+    Length,
+    Color,
 }
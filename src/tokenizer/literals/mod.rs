@@ -3,6 +3,7 @@ pub use literal_kind::LiteralKind;
 use super::Cursor;
 
 pub mod boolean;
+pub mod color;
 mod literal_kind;
 pub mod number;
 pub mod string;
@@ -1,7 +1,12 @@
+use crate::tokenizer::identifier::is_identifier_continue;
 use crate::tokenizer::LiteralKind;
+use std::num::ParseIntError;
 
 use super::Cursor;
 
+/// Units following a number that make it a length literal, e.g. `12pt` or `50%`
+pub const LENGTH_UNITS: [&str; 5] = ["pt", "mm", "cm", "in", "%"];
+
 pub fn binary(cursor: &mut Cursor) -> (usize, LiteralKind) {
     debug_assert!(matches!(
         cursor.first().zip(cursor.second()),
@@ -15,7 +20,7 @@ pub fn binary(cursor: &mut Cursor) -> (usize, LiteralKind) {
         .bump()
         .expect("binary should start with 0b")
         .len_utf8();
-    let rest = cursor.eat_while(|c| matches!(c, '0' | '1'));
+    let rest = digits(cursor, |c| matches!(c, '0' | '1'));
     assert!(
         rest > 0,
         "Binary literal should have at least 1 number following `0b`"
@@ -30,7 +35,7 @@ pub fn hex(cursor: &mut Cursor) -> (usize, LiteralKind) {
     ));
     let _zero = cursor.bump().expect("hex should start with 0x").len_utf8();
     let _x = cursor.bump().expect("hex should start with 0x").len_utf8();
-    let rest = cursor.eat_while(|c| matches!(c, '0'..='9' | 'a'..='f' | 'A'..='F'));
+    let rest = digits(cursor, |c| c.is_ascii_hexdigit());
     assert!(
         rest > 0,
         "Hex literal should have at least 1 number following `0x`"
@@ -38,7 +43,60 @@ pub fn hex(cursor: &mut Cursor) -> (usize, LiteralKind) {
     (_zero + _x + rest, LiteralKind::Hex)
 }
 
+pub fn octal(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    debug_assert!(matches!(
+        cursor.first().zip(cursor.second()),
+        Some(('0', 'o') | ('0', 'O'))
+    ));
+    let _zero = cursor
+        .bump()
+        .expect("octal should start with 0o")
+        .len_utf8();
+    let _o = cursor
+        .bump()
+        .expect("octal should start with 0o")
+        .len_utf8();
+    let rest = digits(cursor, |c| matches!(c, '0'..='7'));
+    assert!(
+        rest > 0,
+        "Octal literal should have at least 1 number following `0o`"
+    );
+    (_zero + _o + rest, LiteralKind::Octal)
+}
+
+/// Number literal with a leading minus sign, e.g. `-3` or `-2.5cm`
+pub fn negative(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    debug_assert!(matches!(cursor.first(), Some('-')));
+    let _minus = cursor
+        .bump()
+        .expect("negative number should start with -")
+        .len_utf8();
+    let (length, kind) = match (cursor.first(), cursor.second()) {
+        (Some('0'), Some('b' | 'B')) => binary(cursor),
+        (Some('0'), Some('x' | 'X')) => hex(cursor),
+        (Some('0'), Some('o' | 'O')) => octal(cursor),
+        _ => number(cursor),
+    };
+    (_minus + length, kind)
+}
+
 pub fn number(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    let (length, kind) = unitless_number(cursor);
+    match length_unit(cursor) {
+        Some(unit) => (length + unit, LiteralKind::Length),
+        None => (length, kind),
+    }
+}
+
+fn unitless_number(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    let (length, kind) = mantissa(cursor);
+    match exponent(cursor) {
+        Some(exponent) => (length + exponent, LiteralKind::Floating),
+        None => (length, kind),
+    }
+}
+
+fn mantissa(cursor: &mut Cursor) -> (usize, LiteralKind) {
     debug_assert!(matches!(cursor.first(), Some('0'..='9' | '.')));
     let first_digit = cursor
         .bump()
@@ -50,7 +108,7 @@ pub fn number(cursor: &mut Cursor) -> (usize, LiteralKind) {
             _ => (1, LiteralKind::Integer),
         },
         ('0', None) => (1, LiteralKind::Integer),
-        ('0', Some(following)) if !matches!(following, '0'..='9') => (1, LiteralKind::Integer),
+        ('0', Some(following)) if !following.is_ascii_digit() => (1, LiteralKind::Integer),
         ('1'..='9', _) => {
             let integer_part = 1 + integer(cursor);
             match (cursor.first(), cursor.second()) {
@@ -68,6 +126,37 @@ pub fn number(cursor: &mut Cursor) -> (usize, LiteralKind) {
     }
 }
 
+pub fn tuple_index(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    debug_assert!(matches!(cursor.first(), Some('0'..='9')));
+    let has_leading_zero = cursor.first() == Some('0');
+    let length = cursor.eat_while(|c| c.is_ascii_digit());
+    assert!(
+        !has_leading_zero || length == 1,
+        "Tuple index should not have leading zeros"
+    );
+    (length, LiteralKind::Integer)
+}
+
+/// Eats the unit directly following a number.
+/// A unit followed by other identifier characters is not a unit, e.g. `1inch`
+fn length_unit(cursor: &mut Cursor) -> Option<usize> {
+    let unit = LENGTH_UNITS.iter().find(|unit| {
+        let unit_len = unit.chars().count();
+        unit.chars()
+            .enumerate()
+            .all(|(index, c)| cursor.nth(index) == Some(c))
+            && !cursor
+                .nth(unit_len)
+                .map(is_identifier_continue)
+                .unwrap_or_default()
+    })?;
+    Some(
+        unit.chars()
+            .map(|_| cursor.bump().expect("Unit is checked").len_utf8())
+            .sum(),
+    )
+}
+
 fn floating(cursor: &mut Cursor) -> usize {
     debug_assert!(matches!(cursor.first(), Some('.')));
     let _dot = cursor
@@ -78,8 +167,59 @@ fn floating(cursor: &mut Cursor) -> usize {
     _dot + fraction_part
 }
 
+/// Eats the exponent following a number, e.g. `e-3` in `1.5e-3`
+fn exponent(cursor: &mut Cursor) -> Option<usize> {
+    let sign = match (cursor.first(), cursor.second()) {
+        (Some('e' | 'E'), Some('+' | '-')) => 1,
+        (Some('e' | 'E'), _) => 0,
+        _ => return None,
+    };
+    if !cursor
+        .nth(1 + sign)
+        .map(|c| c.is_ascii_digit())
+        .unwrap_or_default()
+    {
+        return None;
+    }
+    let marker_and_sign = (0..=sign)
+        .map(|_| cursor.bump().expect("Exponent is checked").len_utf8())
+        .sum::<usize>();
+    Some(marker_and_sign + integer(cursor))
+}
+
+/// Digits following the first digit of a decimal number
 fn integer(cursor: &mut Cursor) -> usize {
-    cursor.eat_while(|c| matches!(c, '0'..='9'))
+    cursor.eat_while(|c| c.is_ascii_digit() || c == '_')
+}
+
+/// Digits which can be separated by underscores after the first digit, e.g. `1_000`
+fn digits(cursor: &mut Cursor, is_digit: impl Fn(char) -> bool) -> usize {
+    match cursor.first() {
+        Some(first) if is_digit(first) => cursor.eat_while(|c| is_digit(c) || c == '_'),
+        _ => 0,
+    }
+}
+
+/// Value of an integer literal in any base, with an error when it is out of range
+pub fn integer_value(kind: LiteralKind, lexeme: &str) -> Result<isize, ParseIntError> {
+    let (sign, unsigned) = match lexeme.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", lexeme),
+    };
+    let (radix, digits) = match kind {
+        LiteralKind::Integer => (10, unsigned),
+        LiteralKind::Binary => (2, &unsigned[2..]),
+        LiteralKind::Octal => (8, &unsigned[2..]),
+        LiteralKind::Hex => (16, &unsigned[2..]),
+        kind => unreachable!("Unexpected integer literal kind: {:?}", kind),
+    };
+    let digits = format!("{sign}{}", digits.replace('_', ""));
+    isize::from_str_radix(&digits, radix)
+}
+
+/// Value of a floating literal, which is infinite when it is out of range
+pub fn float_value(lexeme: &str) -> f64 {
+    lexeme.replace('_', "").parse().expect("float")
 }
 
 #[cfg(test)]
@@ -195,9 +335,127 @@ mod number_tests {
         assert_eq!(hex(&mut cursor).0, "0xCA".len());
     }
 
+    #[test]
+    fn test_tuple_index() {
+        let mut cursor = Cursor::from_iter("0.1".chars());
+        assert_eq!(tuple_index(&mut cursor), (1, LiteralKind::Integer));
+        let mut cursor = Cursor::from_iter("12.3".chars());
+        assert_eq!(tuple_index(&mut cursor), (2, LiteralKind::Integer));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_leading_tuple_index() {
+        let mut cursor = Cursor::from_iter("01".chars());
+        tuple_index(&mut cursor);
+    }
+
     #[test]
     fn test_incomplete_float() {
         let mut cursor = Cursor::from_iter("3.".chars());
         assert_eq!(number(&mut cursor).0, "3".len())
     }
+
+    #[test]
+    fn test_underscores() {
+        let targets = [
+            ("1_000_000", LiteralKind::Integer),
+            ("3.141_592", LiteralKind::Floating),
+            ("1_000pt", LiteralKind::Length),
+        ];
+        for (target, kind) in targets {
+            let mut cursor = Cursor::from_iter(target.chars());
+            assert_eq!(number(&mut cursor), (target.len(), kind))
+        }
+        let mut cursor = Cursor::from_iter("0xFF_FF".chars());
+        assert_eq!(hex(&mut cursor).0, "0xFF_FF".len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_underscore_leading_binary() {
+        let mut cursor = Cursor::from_iter("0b_1".chars());
+        binary(&mut cursor);
+    }
+
+    #[test]
+    fn test_octal() {
+        for target in ["0o755", "0O17", "0o7_55"] {
+            let mut cursor = Cursor::from_iter(target.chars());
+            assert_eq!(octal(&mut cursor), (target.len(), LiteralKind::Octal))
+        }
+        let mut cursor = Cursor::from_iter("0o758".chars());
+        assert_eq!(octal(&mut cursor).0, "0o75".len())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_octal() {
+        let mut cursor = Cursor::from_iter("0o".chars());
+        octal(&mut cursor);
+    }
+
+    #[test]
+    fn test_exponent() {
+        let targets = ["1.5e-3", "1e10", "2E+5", "0e0", "6.02e23"];
+        for target in targets {
+            let mut cursor = Cursor::from_iter(target.chars());
+            assert_eq!(number(&mut cursor), (target.len(), LiteralKind::Floating))
+        }
+        let mut cursor = Cursor::from_iter("2e-3pt".chars());
+        assert_eq!(number(&mut cursor), ("2e-3pt".len(), LiteralKind::Length));
+    }
+
+    #[test]
+    fn test_not_exponent() {
+        let mut cursor = Cursor::from_iter("1em".chars());
+        assert_eq!(number(&mut cursor), (1, LiteralKind::Integer));
+        let mut cursor = Cursor::from_iter("1e-".chars());
+        assert_eq!(number(&mut cursor), (1, LiteralKind::Integer));
+    }
+
+    #[test]
+    fn test_negative() {
+        let targets = [
+            ("-42", LiteralKind::Integer),
+            ("-0.5", LiteralKind::Floating),
+            ("-1.5e-3", LiteralKind::Floating),
+            ("-2cm", LiteralKind::Length),
+            ("-0b10", LiteralKind::Binary),
+            ("-0o7", LiteralKind::Octal),
+            ("-0xFF", LiteralKind::Hex),
+        ];
+        for (target, kind) in targets {
+            let mut cursor = Cursor::from_iter(target.chars());
+            assert_eq!(negative(&mut cursor), (target.len(), kind))
+        }
+    }
+
+    #[test]
+    fn test_integer_value() {
+        assert_eq!(integer_value(LiteralKind::Integer, "1_000"), Ok(1000));
+        assert_eq!(integer_value(LiteralKind::Octal, "0o755"), Ok(493));
+        assert_eq!(integer_value(LiteralKind::Hex, "-0xFF"), Ok(-255));
+        assert_eq!(integer_value(LiteralKind::Binary, "0b1_0"), Ok(2));
+        assert!(integer_value(LiteralKind::Integer, "9223372036854775808").is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let targets = [
+            "12pt", "2.5cm", "1in", "10mm", "50%", "0pt", "0.5in", ".5cm",
+        ];
+        for target in targets {
+            let mut cursor = Cursor::from_iter(target.chars());
+            assert_eq!(number(&mut cursor), (target.len(), LiteralKind::Length))
+        }
+    }
+
+    #[test]
+    fn test_not_length() {
+        let mut cursor = Cursor::from_iter("1inch".chars());
+        assert_eq!(number(&mut cursor), (1, LiteralKind::Integer));
+        let mut cursor = Cursor::from_iter("2.5px".chars());
+        assert_eq!(number(&mut cursor), (3, LiteralKind::Floating));
+    }
 }
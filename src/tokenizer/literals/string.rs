@@ -12,28 +12,80 @@ fn is_string_header(cursor: &Cursor) -> bool {
     }
 }
 
+/// A simple string is split into several literals if it has interpolations.
+/// For example, `"Page {number} of {TOTAL}"` is split into
+/// `"Page {` (head), `} of {` (middle) and `}"` (tail), with the expressions in between
 pub fn string(cursor: &mut Cursor) -> (usize, LiteralKind) {
     debug_assert!(is_string_header(cursor));
-    let length = match cursor.first() {
-        Some('"') => simple_string(cursor),
-        Some('r') => raw_string(cursor),
+    match cursor.first() {
+        Some('"') if is_text_block_quotes(cursor) => (text_block(cursor), LiteralKind::String),
+        Some('"') => match simple_string(cursor) {
+            (length, false) => (length, LiteralKind::String),
+            (length, true) => (length, LiteralKind::StringHead),
+        },
+        Some('r') => (raw_string(cursor), LiteralKind::String),
         leading => unreachable!("string cannot start with: {:?}", leading),
-    };
-    (length, LiteralKind::String)
+    }
+}
+
+/// The rest of a string after an interpolated expression, starting from the closing `}`
+pub fn string_continuation(cursor: &mut Cursor) -> (usize, LiteralKind) {
+    debug_assert_eq!(cursor.first(), Some('}'));
+    match simple_string(cursor) {
+        (length, false) => (length, LiteralKind::StringTail),
+        (length, true) => (length, LiteralKind::StringMiddle),
+    }
 }
 
-fn simple_string(cursor: &mut Cursor) -> usize {
-    let mut total_len = cursor.bump().expect("start quote").len_utf8(); // start quote
+/// Eats the string until the closing quote, or the `{` starting an interpolation.
+/// Returns the length and whether an interpolation starts
+fn simple_string(cursor: &mut Cursor) -> (usize, bool) {
+    let mut total_len = cursor.bump().expect("start quote").len_utf8(); // start quote or `}`
     loop {
-        let eaten_length = cursor.eat_while(|c| c != '"' && c != '\\');
+        let eaten_length = cursor.eat_while(|c| c != '"' && c != '\\' && c != '{');
         total_len += eaten_length;
         total_len += match cursor.first() {
             Some('\\') => eat_escaped_char(cursor),
             Some('"') => break,
+            Some('{') => return (total_len + cursor.bump().unwrap().len_utf8(), true),
             _ => panic!("string not closed"),
         }
     }
-    total_len + cursor.bump().unwrap().len_utf8() // close quote
+    (total_len + cursor.bump().unwrap().len_utf8(), false) // close quote
+}
+
+fn is_text_block_quotes(cursor: &Cursor) -> bool {
+    (0..3).all(|n| cursor.nth(n) == Some('"'))
+}
+
+/// A text block starts with `"""` and a line break, and ends with `"""`.
+/// Escapes work as in simple strings, but braces are not interpolated
+fn text_block(cursor: &mut Cursor) -> usize {
+    let mut total_len = 0;
+    for _ in 0..3 {
+        total_len += cursor.bump().unwrap().len_utf8(); // open quotes
+    }
+    total_len += cursor.eat_while(is_whitespace);
+    assert!(
+        cursor
+            .first()
+            .map(is_line_terminator_start)
+            .unwrap_or_default(),
+        r#"Text block should start with a line break after `"""`"#
+    );
+    loop {
+        total_len += cursor.eat_while(|c| c != '"' && c != '\\');
+        total_len += match cursor.first() {
+            Some('\\') => eat_escaped_char(cursor),
+            Some('"') if is_text_block_quotes(cursor) => break,
+            Some('"') => cursor.bump().unwrap().len_utf8(),
+            _ => panic!("text block not closed"),
+        }
+    }
+    for _ in 0..3 {
+        total_len += cursor.bump().unwrap().len_utf8(); // close quotes
+    }
+    total_len
 }
 
 fn raw_string(cursor: &mut Cursor) -> usize {
@@ -68,15 +120,57 @@ fn eat_escaped_char(cursor: &mut Cursor) -> usize {
     let backslash = cursor.bump().unwrap().len_utf8();
     let leading_char = cursor.first().expect("No character after backslash");
     let escaped_length = match leading_char {
-        't' | 'n' | 'r' | '"' | '\'' | '\\' => cursor.bump().unwrap().len_utf8(),
+        '0' | 't' | 'n' | 'r' | '"' | '\'' | '\\' | '{' | '}' => cursor.bump().unwrap().len_utf8(),
+        'x' => ascii_escape(cursor),
+        'u' => unicode_escape(cursor),
         whitespace if is_whitespace(whitespace) || is_line_terminator_start(whitespace) => {
             whitespace_and_newline(cursor)
         }
-        _ => panic!("Escape invalid character"),
+        unknown => panic!("Unknown character escape (\\{unknown})"),
     };
     backslash + escaped_length
 }
 
+/// `\x` followed by exactly two hex digits, up to `7F`, e.g. `\x41`
+fn ascii_escape(cursor: &mut Cursor) -> usize {
+    let x = cursor.bump().unwrap().len_utf8();
+    let (value, digits) = hex_digits(cursor, 2);
+    assert!(
+        digits == 2 && value <= 0x7F,
+        "ASCII escape should be `\\x` followed by two hex digits up to 7F"
+    );
+    x + digits
+}
+
+/// `\u` followed by 1 to 6 hex digits in braces, e.g. `\u{1F600}`
+fn unicode_escape(cursor: &mut Cursor) -> usize {
+    const MALFORMED: &str = "Unicode escape should be `\\u{` followed by 1 to 6 hex digits and `}`";
+    let u = cursor.bump().unwrap().len_utf8();
+    assert_eq!(cursor.bump(), Some('{'), "{MALFORMED}");
+    let (value, digits) = hex_digits(cursor, 6);
+    assert!(digits > 0 && cursor.bump() == Some('}'), "{MALFORMED}");
+    assert!(
+        char::from_u32(value).is_some(),
+        "Unicode escape ({value:X}) is not a valid character"
+    );
+    u + '{'.len_utf8() + digits + '}'.len_utf8()
+}
+
+/// Eats at most `limit` hex digits, returns their value and how many are eaten
+fn hex_digits(cursor: &mut Cursor, limit: usize) -> (u32, usize) {
+    let mut value = 0;
+    let mut digits = 0;
+    while let Some(digit) = cursor.first().and_then(|c| c.to_digit(16)) {
+        if digits == limit {
+            break;
+        }
+        cursor.bump();
+        value = value * 16 + digit;
+        digits += 1;
+    }
+    (value, digits)
+}
+
 fn eat_pound(cursor: &mut Cursor, limit: Option<usize>) -> usize {
     if !matches!(cursor.first(), Some('#')) {
         return 0;
@@ -96,7 +190,8 @@ fn eat_pound(cursor: &mut Cursor, limit: Option<usize>) -> usize {
 mod string_tests {
     use quickcheck::{quickcheck, TestResult};
 
-    use super::{string, Cursor};
+    use super::{string, string_continuation, Cursor};
+    use crate::tokenizer::LiteralKind;
 
     fn test_string(target: &str) {
         let mut cursor = Cursor::from_iter(target.chars());
@@ -155,7 +250,53 @@ mod string_tests {
     }
 
     #[test]
-    #[should_panic]
+    fn test_char_code_escapes() {
+        let targets = [
+            r#""null\0""#,
+            r#""\x41\x7f""#,
+            r#""\u{a0}\u{2014}\u{1F600}""#,
+        ];
+        for target in targets {
+            test_string(target)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "ASCII escape should be `\\x` followed by two hex digits up to 7F")]
+    fn test_ascii_escape_out_of_range() {
+        test_string(r#""\x80""#)
+    }
+
+    #[test]
+    #[should_panic(expected = "ASCII escape should be `\\x` followed by two hex digits up to 7F")]
+    fn test_ascii_escape_one_digit() {
+        test_string(r#""\x4""#)
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Unicode escape should be `\\u{` followed by 1 to 6 hex digits and `}`"
+    )]
+    fn test_unicode_escape_too_long() {
+        test_string(r#""\u{1234567}""#)
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Unicode escape should be `\\u{` followed by 1 to 6 hex digits and `}`"
+    )]
+    fn test_unicode_escape_without_braces() {
+        test_string(r#""\u1F600""#)
+    }
+
+    #[test]
+    #[should_panic(expected = "Unicode escape (D800) is not a valid character")]
+    fn test_unicode_escape_surrogate() {
+        test_string(r#""\u{D800}""#)
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown character escape (\\a)")]
     fn test_string_invalid_escaped() {
         test_string(r#""escaped\a"#)
     }
@@ -190,7 +331,7 @@ mod string_tests {
     }
 
     fn quickcheck_test_strings(s: String) -> TestResult {
-        if s.chars().any(|c| matches!(c, '"' | '\\')) {
+        if s.chars().any(|c| matches!(c, '"' | '\\' | '{')) {
             TestResult::discard()
         } else {
             let target = format!(r#""{}""#, s);
@@ -206,7 +347,7 @@ mod string_tests {
     }
 
     fn test_string_followed_by_other_token_by_length(s: String) -> TestResult {
-        if s.chars().any(|c| matches!(c, '"' | '\\')) {
+        if s.chars().any(|c| matches!(c, '"' | '\\' | '{')) {
             TestResult::discard()
         } else {
             let target = format!(r#""{}".toUppercase()"#, s);
@@ -230,6 +371,72 @@ mod string_tests {
         TestResult::from_bool(expected == length)
     }
 
+    #[test]
+    fn test_interpolated_string_parts() {
+        let target = r#""Page {n} of {TOTAL}""#;
+        let mut cursor = Cursor::from_iter(target.chars());
+        assert_eq!(
+            string(&mut cursor),
+            ("\"Page {".len(), LiteralKind::StringHead)
+        );
+        cursor.eat_while(|c| c != '}');
+        assert_eq!(
+            string_continuation(&mut cursor),
+            ("} of {".len(), LiteralKind::StringMiddle)
+        );
+        cursor.eat_while(|c| c != '}');
+        assert_eq!(
+            string_continuation(&mut cursor),
+            ("}\"".len(), LiteralKind::StringTail)
+        );
+    }
+
+    #[test]
+    fn test_escaped_braces_are_not_interpolated() {
+        let target = r#""\{not interpolated\}""#;
+        let mut cursor = Cursor::from_iter(target.chars());
+        assert_eq!(string(&mut cursor), (target.len(), LiteralKind::String));
+    }
+
+    #[test]
+    fn test_raw_string_is_not_interpolated() {
+        let target = r##"r#"{not interpolated}"#"##;
+        let mut cursor = Cursor::from_iter(target.chars());
+        assert_eq!(string(&mut cursor), (target.len(), LiteralKind::String));
+    }
+
+    #[test]
+    fn test_text_blocks() {
+        let targets = [
+            "\"\"\"\n    text\n    \"\"\"",
+            "\"\"\"  \r\n  with \"quotes\" and {braces}\r\n  \"\"\"",
+            "\"\"\"\n  escaped \\\"\"\" quotes\"\"\"",
+        ];
+        for target in targets {
+            test_string(target)
+        }
+    }
+
+    #[test]
+    fn test_text_block_followed_by_other_token() {
+        let target = "\"\"\"\n  text\"\"\".trim()";
+        let mut cursor = Cursor::from_iter(target.chars());
+        let length = string(&mut cursor).0;
+        assert_eq!(length, target.len() - ".trim()".len())
+    }
+
+    #[test]
+    #[should_panic(expected = r#"Text block should start with a line break after `"""`"#)]
+    fn test_text_block_on_one_line() {
+        test_string(r#""""text""""#)
+    }
+
+    #[test]
+    #[should_panic(expected = "text block not closed")]
+    fn test_text_block_unclosed() {
+        test_string("\"\"\"\n  text\"\"")
+    }
+
     #[test]
     fn test_string_escape_newline() {
         let target = r#""a string with\ 
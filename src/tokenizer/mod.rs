@@ -1,3 +1,4 @@
+pub use literals::number::{float_value, integer_value};
 pub use literals::LiteralKind;
 pub use token::{Token, TokenKind};
 
@@ -13,8 +14,8 @@ mod whitespace;
 
 pub type Cursor<'a> = cursor::Cursor<std::str::Chars<'a>>;
 
-pub fn tokenize(text: &str) -> impl Iterator<Item = Token> {
+pub fn tokenize(text: &str) -> impl Iterator<Item = Token<'_>> {
     tokenizing::Tokenizer::tokenize(text).filter(Token::should_keep)
 }
 
-pub use whitespace::is_whitespace_or_newline;
+pub use whitespace::{is_whitespace, is_whitespace_or_newline};
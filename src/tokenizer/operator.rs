@@ -11,6 +11,7 @@ pub fn operator(cursor: &mut Cursor) -> usize {
     debug_assert!(cursor.first().map(is_operator_start).unwrap_or_default());
     match cursor.bump().unwrap() {
         unary @ '~' => unary.len_utf8(),
+        '=' if cursor.first() == Some('>') => '='.len_utf8() + cursor.bump().unwrap().len_utf8(),
         op_with_equals @ ('+' | '-' | '=' | '!' | '^' | '/' | '%') => {
             eat_operator_with_equals(op_with_equals, cursor)
         }
@@ -61,9 +62,10 @@ mod operator_tests {
 
     use super::{is_operator_start, operator, Cursor};
 
-    const ALL_OPERATORS: [&str; 32] = [
-        "=", "==", ">", ">=", ">>", ">>=", "<", "<=", "<<", "<<=", "!", "!=", "~", "+", "+=", "-",
-        "-=", "*", "**", "*=", "/", "/=", "&", "&&", "&=", "|", "||", "|=", "^", "^=", "%", "%=",
+    const ALL_OPERATORS: [&str; 33] = [
+        "=", "==", "=>", ">", ">=", ">>", ">>=", "<", "<=", "<<", "<<=", "!", "!=", "~", "+", "+=",
+        "-", "-=", "*", "**", "*=", "/", "/=", "&", "&&", "&=", "|", "||", "|=", "^", "^=", "%",
+        "%=",
     ];
 
     #[test]
@@ -10,6 +10,7 @@ pub struct Token<'a> {
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum TokenKind {
     Comment,
+    DocComment,
     WhiteSpace,
     NewLine,
     Identifier,
@@ -46,7 +47,7 @@ impl<'a> Token<'a> {
                 lexeme: "{" | "," | "." | ";"
             } | Token {
                 kind: TokenKind::Operator,
-                lexeme: "="
+                lexeme: "=" | "=>"
             } | Token {
                 kind: TokenKind::NewLine,
                 lexeme: _
@@ -3,6 +3,9 @@ use crate::tokenizer::{LiteralKind, Token, TokenKind};
 use super::Cursor;
 use super::{comment, identifier, keyword, literals, operator, whitespace};
 
+/// Marks an interpolation inside a string in the stack of unbalanced brackets
+const INTERPOLATION: char = '"';
+
 pub struct Tokenizer<'a> {
     cursor: Cursor<'a>,
     text: &'a str,
@@ -16,14 +19,18 @@ impl<'a> Iterator for Tokenizer<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let token = match self.cursor.first() {
+            Some('0'..='9') if self.last_token_is_dot() => self.tuple_index_token(),
             Some('1'..='9') => self.number_token(),
             Some('0') => match self.cursor.second() {
                 Some('b' | 'B') => self.binary_token(),
                 Some('x' | 'X') => self.hex_token(),
+                Some('o' | 'O') => self.octal_token(),
                 _ => self.number_token(),
             },
+            Some('-') if self.starts_negative_number() => self.negative_number_token(),
             Some('"') => self.string_token(),
             Some('r') if self.cursor.second() == Some('#') => self.string_token(),
+            Some('#') => self.color_token(),
             Some('\u{0020}' | '\u{0009}' | '\u{000C}' | '\u{000A}' | '\u{000D}')
                 if self.last_token_suppresses_newline() =>
             {
@@ -33,12 +40,16 @@ impl<'a> Iterator for Tokenizer<'a> {
             Some('\u{000A}' | '\u{000D}') => self.newline_token(),
             Some('/') => match self.cursor.second() {
                 Some('/') => self.comment_token(),
+                Some('*') => self.block_comment_token(),
                 _ => self.operator_token(),
             },
             Some('\\') => self.escape_token(),
             Some('=' | '>' | '<' | '!' | '~' | '+' | '-' | '*' | '&' | '|' | '^' | '%') => {
                 self.operator_token()
             }
+            Some('}') if self.unbalanced_brackets.last() == Some(&INTERPOLATION) => {
+                self.string_continuation_token()
+            }
             Some(bracket @ ('(' | '[' | ')' | ']' | '{' | '}')) => {
                 if bracket == '(' || bracket == '[' || bracket == '{' {
                     self.unbalanced_brackets.push(bracket)
@@ -47,6 +58,7 @@ impl<'a> Iterator for Tokenizer<'a> {
                 }
                 self.separator_token()
             }
+            Some('.') if self.cursor.second() == Some('.') => self.spread_token(),
             Some(';' | ',' | '.' | ':') => self.separator_token(),
             Some(id) if identifier::is_identifier_start(id) => self.identifier_related_token(),
             Some(unexpected) => unreachable!("Unexpected char reached: {}", unexpected),
@@ -54,7 +66,7 @@ impl<'a> Iterator for Tokenizer<'a> {
         };
         if !matches!(
             token.kind,
-            TokenKind::WhiteSpace | TokenKind::Comment | TokenKind::NewLine
+            TokenKind::WhiteSpace | TokenKind::Comment | TokenKind::DocComment | TokenKind::NewLine
         ) {
             self.last_token.replace(token);
         }
@@ -79,6 +91,12 @@ impl<'a> Tokenizer<'a> {
         self.literal_token(literals::number::number)
     }
 
+    /// Numbers right after a dot are positional accesses, like `pair.0.1`,
+    /// so they are never treated as floating numbers
+    fn tuple_index_token(&mut self) -> Token<'a> {
+        self.literal_token(literals::number::tuple_index)
+    }
+
     fn binary_token(&mut self) -> Token<'a> {
         self.literal_token(literals::number::binary)
     }
@@ -87,8 +105,33 @@ impl<'a> Tokenizer<'a> {
         self.literal_token(literals::number::hex)
     }
 
+    fn octal_token(&mut self) -> Token<'a> {
+        self.literal_token(literals::number::octal)
+    }
+
+    fn negative_number_token(&mut self) -> Token<'a> {
+        self.literal_token(literals::number::negative)
+    }
+
+    fn color_token(&mut self) -> Token<'a> {
+        self.literal_token(literals::color::color)
+    }
+
     fn string_token(&mut self) -> Token<'a> {
-        self.literal_token(literals::string::string)
+        let token = self.literal_token(literals::string::string);
+        if token.kind == TokenKind::Literal(LiteralKind::StringHead) {
+            self.unbalanced_brackets.push(INTERPOLATION)
+        }
+        token
+    }
+
+    /// Continues the string after an interpolation is closed
+    fn string_continuation_token(&mut self) -> Token<'a> {
+        let token = self.literal_token(literals::string::string_continuation);
+        if token.kind == TokenKind::Literal(LiteralKind::StringTail) {
+            self.unbalanced_brackets.pop();
+        }
+        token
     }
 
     fn literal_token<F>(&mut self, target_fn: F) -> Token<'a>
@@ -103,8 +146,18 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
+    /// Line comments starting with exactly three slashes are documentations,
+    /// they are kept for the parser to attach to the following declaration
     fn comment_token(&mut self) -> Token<'a> {
-        self.non_literal_token(comment::comment, TokenKind::Comment)
+        if comment::is_doc_comment(&self.cursor) {
+            self.non_literal_token(comment::comment, TokenKind::DocComment)
+        } else {
+            self.non_literal_token(comment::comment, TokenKind::Comment)
+        }
+    }
+
+    fn block_comment_token(&mut self) -> Token<'a> {
+        self.non_literal_token(comment::block_comment, TokenKind::Comment)
     }
 
     fn whitespace_token(&mut self) -> Token<'a> {
@@ -115,7 +168,7 @@ impl<'a> Tokenizer<'a> {
     /// Non meaningful lines include:
     /// 1. Empty line
     /// 2. Lines with only indentations/spaces
-    /// 3. Lines with only comments
+    /// 3. Lines with only comments or documentations
     fn adjust_meaning_content(&mut self, token: &Token) {
         match token {
             Token {
@@ -127,7 +180,7 @@ impl<'a> Tokenizer<'a> {
                 lexeme: _,
             } => self.meaningful_content_count = 0,
             Token {
-                kind: TokenKind::WhiteSpace | TokenKind::Comment,
+                kind: TokenKind::WhiteSpace | TokenKind::Comment | TokenKind::DocComment,
                 ..
             } => (),
             _ => self.meaningful_content_count += 1,
@@ -186,6 +239,17 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
+    /// Two dots in front of the base instance in a struct initialization, like `Essay(..base)`
+    fn spread_token(&mut self) -> Token<'a> {
+        let _ = self.cursor.bump().expect("Checked in match statement");
+        let _ = self.cursor.bump().expect("Checked in match statement");
+        let lexeme = self.eat_chars(2);
+        Token {
+            kind: TokenKind::Separator,
+            lexeme,
+        }
+    }
+
     fn non_literal_token<F>(&mut self, tokenizing_fn: F, kind: TokenKind) -> Token<'a>
     where
         F: Fn(&mut Cursor<'a>) -> usize,
@@ -198,7 +262,7 @@ impl<'a> Tokenizer<'a> {
     fn identifier_related_token(&mut self) -> Token<'a> {
         let size = identifier::identifier(&mut self.cursor);
         let lexeme = self.eat_chars(size);
-        let kind = if keyword::is_keyword(lexeme) {
+        let kind = if keyword::is_keyword(lexeme) || self.starts_type_alias(lexeme) {
             TokenKind::Keyword
         } else if literals::boolean::is_boolean(lexeme) {
             TokenKind::Literal(LiteralKind::Boolean)
@@ -215,6 +279,64 @@ impl<'a> Tokenizer<'a> {
         lexeme
     }
 
+    /// A minus sign directly followed by a digit starts a negative number,
+    /// unless it follows an operand on the same line, where it subtracts, like `width -3`
+    fn starts_negative_number(&self) -> bool {
+        let followed_by_digit = self
+            .cursor
+            .second()
+            .map(|c| c.is_ascii_digit())
+            .unwrap_or_default();
+        let follows_operand = match self.last_token {
+            Some(Token {
+                kind: TokenKind::Literal(LiteralKind::StringHead | LiteralKind::StringMiddle),
+                ..
+            }) => false,
+            Some(
+                Token {
+                    kind: TokenKind::Literal(_) | TokenKind::Identifier,
+                    ..
+                }
+                | Token {
+                    kind: TokenKind::Separator,
+                    lexeme: ")" | "]" | "}",
+                }
+                | Token {
+                    kind: TokenKind::Keyword,
+                    lexeme: "self",
+                },
+            ) => self.meaningful_content_count > 0,
+            _ => false,
+        };
+        followed_by_digit && !follows_operand
+    }
+
+    /// `type` is a keyword only when a name follows it on the same line, like in `type Pt = Int`,
+    /// so fields and constants can still be named `type`
+    fn starts_type_alias(&self, lexeme: &str) -> bool {
+        let name = self
+            .text
+            .trim_start_matches(['\u{0020}', '\u{0009}', '\u{000C}']);
+        let name_length = name
+            .find(|c| !identifier::is_identifier_continue(c))
+            .unwrap_or(name.len());
+        let name = &name[..name_length];
+        lexeme == "type"
+            && name.starts_with(identifier::is_identifier_start)
+            && !keyword::is_keyword(name)
+            && !literals::boolean::is_boolean(name)
+    }
+
+    fn last_token_is_dot(&self) -> bool {
+        matches!(
+            self.last_token,
+            Some(Token {
+                kind: TokenKind::Separator,
+                lexeme: "."
+            })
+        )
+    }
+
     fn last_token_suppresses_newline(&self) -> bool {
         self.last_token
             .as_ref()
@@ -329,6 +451,100 @@ mod token_iter_tests {
         assert_eq!(expected, tokens);
     }
 
+    #[test]
+    fn test_tuple_index_after_dot() {
+        let tokens = tokenize("pair.0.1").collect::<Vec<_>>();
+        let expected = vec![
+            Token {
+                kind: Identifier,
+                lexeme: "pair",
+            },
+            Token {
+                kind: Separator,
+                lexeme: ".",
+            },
+            Token {
+                kind: Literal(Integer),
+                lexeme: "0",
+            },
+            Token {
+                kind: Separator,
+                lexeme: ".",
+            },
+            Token {
+                kind: Literal(Integer),
+                lexeme: "1",
+            },
+        ];
+        assert_eq!(tokens, expected)
+    }
+
+    #[test]
+    fn test_negative_number() {
+        let tokens = tokenize("(-3, width -3, -0x1F)").collect::<Vec<_>>();
+        let lexemes = tokens.iter().map(|token| token.lexeme).collect::<Vec<_>>();
+        assert_eq!(
+            lexemes,
+            ["(", "-3", ",", "width", "-", "3", ",", "-0x1F", ")"]
+        );
+        assert_eq!(tokens[1].kind, Literal(Integer));
+        assert_eq!(tokens[4].kind, Operator);
+        assert_eq!(tokens[7].kind, Literal(Hex));
+    }
+
+    #[test]
+    fn test_negative_number_at_line_start() {
+        let tokens = tokenize("{\nwidth\n-2.5cm\n}").collect::<Vec<_>>();
+        assert_eq!(
+            tokens[3],
+            Token {
+                kind: Literal(Length),
+                lexeme: "-2.5cm",
+            }
+        );
+    }
+
+    #[test]
+    fn test_interpolated_string() {
+        let tokens = tokenize(r#""{-1} and {Size { 2 }.width}!""#).collect::<Vec<_>>();
+        let lexemes = tokens.iter().map(|token| token.lexeme).collect::<Vec<_>>();
+        assert_eq!(
+            lexemes,
+            [r#""{"#, "-1", "} and {", "Size", "{", "2", "}", ".", "width", r#"}!""#]
+        );
+        assert_eq!(tokens[0].kind, Literal(StringHead));
+        assert_eq!(tokens[1].kind, Literal(Integer));
+        assert_eq!(tokens[2].kind, Literal(StringMiddle));
+        assert_eq!(tokens[6].kind, Separator);
+        assert_eq!(tokens[9].kind, Literal(StringTail));
+    }
+
+    #[test]
+    fn test_comments() {
+        let text = "/* a /* nested */ comment */\n/// doc\n//// plain\nvalue";
+        let tokens = tokenize(text).collect::<Vec<_>>();
+        let expected = vec![
+            Token {
+                kind: DocComment,
+                lexeme: "/// doc",
+            },
+            Token {
+                kind: Identifier,
+                lexeme: "value",
+            },
+        ];
+        assert_eq!(tokens, expected)
+    }
+
+    #[test]
+    fn test_contextual_type_keyword() {
+        let tokens = tokenize("pub type Pt = Int\nconst type = shape.type\nstruct Shape(type: Pt)")
+            .filter(|token| token.lexeme == "type")
+            .map(|token| token.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(tokens, [Keyword, Identifier, Identifier, Identifier]);
+    }
+
     #[test]
     fn test_newline_inside_brackets() {
         let text = "(1+\n3)";
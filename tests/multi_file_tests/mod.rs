@@ -13,11 +13,10 @@ fn test_separate_file_hello_world() -> Result<()> {
     ])?;
     let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
 
-    assert!(matches!(
+    assert_eq!(
         compiled_str,
-        r#"Doc: {children: [Text: {content: "Hello World",},],size: Size: {height: 842,width: 595,},}"#
-            | r#"Doc: {children: [Text: {content: "Hello World",},],size: Size: {width: 595,height: 842,},}"#
-    ));
+        r#"Doc: {size: Size: {width: 595pt,height: 842pt,},children: [Text: {content: "Hello World",},],}"#
+    );
     Ok(())
 }
 
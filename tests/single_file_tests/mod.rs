@@ -1,5 +1,5 @@
 use super::COMPILER_LOCK;
-use doc_script::compile;
+use doc_script::{compile, documentation};
 use std::io::Result;
 use tempdir::TempDir;
 
@@ -60,6 +60,231 @@ fn test_wrapped_container() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_tuple_destructuring() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    const GREETING = ("Hello", "World")
+    const (hello, world) = GREETING
+    const Main = Page {
+        Text(hello)
+        Text(GREETING.1)
+    }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Hello",},Text: {content: "World",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_named_colors() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    use std.color.{ DODGER_BLUE, WHITE }
+    struct Swatch(color: Color) {
+        const rendered = Text(match self.color {
+            #1e90ff => "dodger blue",
+            #8fc8ff => "half white",
+            #c7e3ff => "three quarters white",
+            _ => "unexpected",
+        })
+    }
+    const Main = Page {
+        Swatch(DODGER_BLUE)
+        Swatch(DODGER_BLUE.mix(WHITE))
+        Swatch(WHITE.mix(DODGER_BLUE, 0.75))
+    }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        concat!(
+            r#"Page: {children: [Text: {content: "dodger blue",},"#,
+            r#"Text: {content: "half white",},"#,
+            r#"Text: {content: "three quarters white",},],}"#
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_string_interpolation() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    const TOTAL = 12
+    struct Footer(number: Int) {
+        const rendered = Text("Page {self.number} of {TOTAL} \{draft: {true}\}")
+    }
+    const Main = Page { Footer(-3) }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Page -3 of 12 {draft: true}",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_text_block() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    const Main = Page {
+        Text("""
+            A long paragraph
+              keeps relative indentation.
+            """)
+    }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "A long paragraph\n  keeps relative indentation.",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_collection_access() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    struct Credit(authors: [String]) {
+        const rendered = Text("{self.authors[0]} et al., last: {self.authors.last()}")
+    }
+    const Main = Page { Credit(["Ada", "Grace", "Edsger"]) }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Ada et al., last: Edsger",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_match_render_children() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    struct Figure(caption: String) {
+        const rendered = Text(self.caption)
+    }
+    struct Labelled(child: Render) {
+        const rendered = match self.child {
+            figure: Figure => Text("Figure: {figure.caption}"),
+            Text => Text("text"),
+            _ => Text("other"),
+        }
+    }
+    const Main = Page { Labelled(Figure("A cat")) }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Figure: A cat",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_passing_assertions() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    struct Heading(title: String) {
+        assert(self.title.len() <= 80, "Heading should be at most 80 characters")
+        const rendered = Text(self.title)
+    }
+    const CHAPTERS = 3
+    assert(CHAPTERS < 10, "Too many chapters")
+    const Main = Page { Heading("Introduction") }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Introduction",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_struct_of_imported_module() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    use std.rect
+    const size = rect.Size(1pt, 2pt)
+    const title = "Chapter One".replace("One", "1")
+    const Main = Page { Text("{title}: {size.width < size.height}") }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Chapter 1: true",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_type_alias_fields() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    type Titles = [String]
+    struct Contents(titles: Titles) {
+        const rendered = Text(self.titles.join(", "))
+    }
+    const Main = Page { Contents(["Introduction", "Results"]) }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "Introduction, Results",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unrelated_trait_not_inherited_by_std() -> Result<()> {
+    let compiled = compile_single_file(
+        r#"
+    trait Unused(content: String) {
+        const extra = "X"
+    }
+    const Main = Page { Text("hi") }
+    "#,
+    )?;
+    let compiled_str = std::str::from_utf8(&compiled).expect("Not utf8");
+    assert_eq!(
+        compiled_str,
+        r#"Page: {children: [Text: {content: "hi",},],}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_declaration_documentation() -> Result<()> {
+    let found = document_single_file(
+        r#"
+    /// Width of A4 paper
+    /// in points
+    const width = 595pt
+    const Main = Page { }
+    "#,
+        &["width"],
+    )?;
+    assert_eq!(found.as_deref(), Some("Width of A4 paper\nin points"));
+    Ok(())
+}
+
 fn compile_single_file(content: &str) -> Result<Vec<u8>> {
     let id = std::thread::current().id();
     let project_dir = TempDir::new(&format!("example{id:?}"))?;
@@ -70,3 +295,13 @@ fn compile_single_file(content: &str) -> Result<Vec<u8>> {
     let compiled = compile(&["main.ds"]);
     Ok(compiled)
 }
+
+fn document_single_file(content: &str, path: &[&str]) -> Result<Option<String>> {
+    let id = std::thread::current().id();
+    let project_dir = TempDir::new(&format!("example{id:?}"))?;
+    let source_file_path = project_dir.path().join("main.ds");
+    std::fs::write(&source_file_path, content)?;
+    let _locked = COMPILER_LOCK.lock().expect("Failed to lock");
+    std::env::set_current_dir(&project_dir)?;
+    Ok(documentation(&["main.ds"], path))
+}